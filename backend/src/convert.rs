@@ -0,0 +1,124 @@
+use std::{collections::HashMap, fmt};
+
+use anyhow::{bail, ensure};
+use sha2::{Digest, Sha256};
+
+use super::*;
+
+/// Counts of the entries converted from one back-end to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConvertSummary {
+    pub entries: usize,
+    /// Entries converted with the trash of the source back-end.
+    pub trashed: usize,
+    /// Entries which couldn't keep their ids on the target back-end.
+    pub changed_ids: usize,
+}
+
+impl fmt::Display for ConvertSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} journals converted, {} of them in the trash, {} with changed ids",
+            self.entries, self.trashed, self.changed_ids
+        )
+    }
+}
+
+/// Copies all the entries of the source back-end including its trash to the empty target
+/// back-end, one entry at a time, keeping their ids where possible. The entries on the target are
+/// verified against the source afterwards by their counts and content hashes.
+pub async fn convert_entries<S: DataProvider, T: DataProvider>(
+    source: &S,
+    target: &T,
+) -> anyhow::Result<ConvertSummary> {
+    ensure!(
+        target.load_all_entries().await?.is_empty() && target.load_trash().await?.is_empty(),
+        "Target back-end must be empty"
+    );
+
+    let mut entries = source.load_all_entries().await?;
+    entries.extend(source.load_trash().await?);
+    entries.sort_by_key(|entry| entry.id);
+
+    let mut summary = ConvertSummary::default();
+    for entry in entries.iter() {
+        let added = target.add_existing_entry(entry.clone()).await?;
+
+        summary.entries += 1;
+        if entry.deleted_at.is_some() {
+            summary.trashed += 1;
+        }
+        if added.id != entry.id {
+            summary.changed_ids += 1;
+        }
+    }
+
+    verify_conversion(&entries, target).await?;
+
+    Ok(summary)
+}
+
+/// Makes sure the target back-end has the same entries as the given source entries.
+async fn verify_conversion<T: DataProvider>(
+    source_entries: &[Entry],
+    target: &T,
+) -> anyhow::Result<()> {
+    let mut target_entries = target.load_all_entries().await?;
+    target_entries.extend(target.load_trash().await?);
+
+    ensure!(
+        target_entries.len() == source_entries.len(),
+        "Verifying conversion failed: Source has {} journals while target has {}",
+        source_entries.len(),
+        target_entries.len()
+    );
+
+    let target_hashes: HashMap<Uuid, [u8; 32]> = target_entries
+        .iter()
+        .map(|entry| (entry.uuid, content_hash(entry)))
+        .collect();
+
+    let mismatched: Vec<&str> = source_entries
+        .iter()
+        .filter(|entry| target_hashes.get(&entry.uuid) != Some(&content_hash(entry)))
+        .map(|entry| entry.title.as_str())
+        .collect();
+
+    if !mismatched.is_empty() {
+        bail!(
+            "Verifying conversion failed: Journals differ between source and target: {}",
+            mismatched.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Calculates the SHA-256 hash of the data of the entry apart from its id and timestamps.
+pub fn content_hash(entry: &Entry) -> [u8; 32] {
+    let mut tags = entry.tags.clone();
+    tags.sort();
+
+    let mut hasher = Sha256::new();
+    hasher.update(entry.uuid.as_bytes());
+    hasher.update(entry.date.to_rfc3339().as_bytes());
+    hasher.update(entry.title.as_bytes());
+    hasher.update([0]);
+    hasher.update(entry.content.as_bytes());
+    hasher.update([0]);
+    hasher.update(tags.join("\n").as_bytes());
+    hasher.update([0]);
+    hasher.update(
+        entry
+            .priority
+            .map(|p| p.to_string())
+            .unwrap_or_default()
+            .as_bytes(),
+    );
+    hasher.update([0]);
+    hasher.update(entry.notebook.as_bytes());
+    hasher.update([u8::from(entry.deleted_at.is_some())]);
+
+    hasher.finalize().into()
+}
@@ -0,0 +1,303 @@
+use std::{collections::BTreeMap, fmt, path::PathBuf};
+
+use super::*;
+
+/// Problem found in the stored entries by [`diagnose_entries`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryIssue {
+    /// Entries couldn't be loaded at all, e.g. the JSON file is corrupted and no valid backup
+    /// exists.
+    LoadFailed(String),
+    /// Warning raised while loading the entries.
+    Recovered(String),
+    /// Entries file is corrupted while the given backup is valid and can be restored.
+    CorruptedFile {
+        backup: PathBuf,
+    },
+    EmptyTitle {
+        entry_id: u32,
+        expected: String,
+    },
+    /// Title doesn't match the `YYYY_MM_DD_Day` format of the entry date.
+    InvalidTitle {
+        entry_id: u32,
+        title: String,
+        expected: String,
+    },
+    /// Multiple entries exist for the same day in the same notebook.
+    DuplicateDay {
+        day: NaiveDate,
+        notebook: String,
+        entry_ids: Vec<u32>,
+    },
+    DuplicateTags {
+        entry_id: u32,
+        tags: Vec<String>,
+    },
+    /// Stored tag which refers to a non-existing entry.
+    OrphanedTag {
+        entry_id: u32,
+        tag: String,
+    },
+}
+
+impl EntryIssue {
+    /// Checks if the issue can be fixed with [`fix_entries`].
+    pub fn is_fixable(&self) -> bool {
+        !matches!(self, EntryIssue::LoadFailed(_) | EntryIssue::Recovered(_))
+    }
+}
+
+impl fmt::Display for EntryIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EntryIssue::LoadFailed(err) => write!(f, "Journals can't be loaded: {err}"),
+            EntryIssue::Recovered(warning) => write!(f, "{warning}"),
+            EntryIssue::CorruptedFile { backup } => write!(
+                f,
+                "Journals file is corrupted while the backup '{}' is valid and can be restored",
+                backup.display()
+            ),
+            EntryIssue::EmptyTitle { entry_id, expected } => {
+                write!(f, "Journal {entry_id}: Empty title, expected '{expected}'")
+            }
+            EntryIssue::InvalidTitle {
+                entry_id,
+                title,
+                expected,
+            } => write!(
+                f,
+                "Journal {entry_id}: Title '{title}' doesn't match its date, expected '{expected}'"
+            ),
+            EntryIssue::DuplicateDay {
+                day,
+                notebook,
+                entry_ids,
+            } => {
+                let ids: Vec<String> = entry_ids.iter().map(|id| id.to_string()).collect();
+                write!(
+                    f,
+                    "Journals {}: Duplicate journals of the day {day} in notebook '{notebook}'",
+                    ids.join(", ")
+                )
+            }
+            EntryIssue::DuplicateTags { entry_id, tags } => {
+                write!(f, "Journal {entry_id}: Duplicate tags: {}", tags.join(", "))
+            }
+            EntryIssue::OrphanedTag { entry_id, tag } => write!(
+                f,
+                "Journal {entry_id}: Tag '{tag}' is stored for a journal which doesn't exist"
+            ),
+        }
+    }
+}
+
+/// Counts of the changes made by [`fix_entries`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FixSummary {
+    /// Whether the corrupted entries file has been restored from its valid backup.
+    pub restored_backup: bool,
+    pub renamed: usize,
+    /// Entries merged into the first entry of their day and moved to the trash.
+    pub merged: usize,
+    pub deduplicated_tags: usize,
+    pub orphaned_tags: usize,
+}
+
+impl fmt::Display for FixSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.restored_backup {
+            write!(f, "Journals file restored from backup, ")?;
+        }
+        write!(
+            f,
+            "{} journals renamed, {} duplicate journals merged, {} journals with duplicate tags fixed, {} orphaned tags removed",
+            self.renamed, self.merged, self.deduplicated_tags, self.orphaned_tags
+        )
+    }
+}
+
+/// Checks the entries of the given back-end for problems without changing them, reporting
+/// failures in loading them as issues too.
+pub async fn diagnose_entries<D: DataProvider>(provider: &D) -> Vec<EntryIssue> {
+    let mut issues = Vec::new();
+
+    // Loading corrupted entries would restore them from the backup, which is left to the fix.
+    match provider.find_recovery_backup().await {
+        Ok(Some(backup)) => {
+            issues.push(EntryIssue::CorruptedFile { backup });
+            return issues;
+        }
+        Ok(None) => {}
+        Err(err) => {
+            issues.push(EntryIssue::LoadFailed(format!("{err:#}")));
+            return issues;
+        }
+    }
+
+    let entries = match provider.load_all_entries().await {
+        Ok(entries) => entries,
+        Err(err) => {
+            issues.push(EntryIssue::LoadFailed(format!("{err:#}")));
+            return issues;
+        }
+    };
+
+    if let Some(warning) = provider.take_load_warning() {
+        issues.push(EntryIssue::Recovered(warning));
+    }
+
+    let mut sorted: Vec<&Entry> = entries.iter().collect();
+    sorted.sort_by_key(|entry| entry.id);
+
+    for entry in sorted.iter() {
+        let expected = day_title(entry.date);
+        if entry.title.trim().is_empty() {
+            issues.push(EntryIssue::EmptyTitle {
+                entry_id: entry.id,
+                expected,
+            });
+        } else if entry.title != expected {
+            issues.push(EntryIssue::InvalidTitle {
+                entry_id: entry.id,
+                title: entry.title.to_owned(),
+                expected,
+            });
+        }
+
+        let duplicate_tags = duplicate_tags(&entry.tags);
+        if !duplicate_tags.is_empty() {
+            issues.push(EntryIssue::DuplicateTags {
+                entry_id: entry.id,
+                tags: duplicate_tags,
+            });
+        }
+    }
+
+    for ((notebook, day), entry_ids) in day_groups(&sorted) {
+        if entry_ids.len() > 1 {
+            issues.push(EntryIssue::DuplicateDay {
+                day,
+                notebook: notebook.to_owned(),
+                entry_ids,
+            });
+        }
+    }
+
+    match provider.load_orphaned_tags().await {
+        Ok(tags) => issues.extend(
+            tags.into_iter()
+                .map(|(entry_id, tag)| EntryIssue::OrphanedTag { entry_id, tag }),
+        ),
+        Err(err) => issues.push(EntryIssue::LoadFailed(format!("{err:#}"))),
+    }
+
+    issues
+}
+
+/// Fixes the problems of the entries of the given back-end which can be fixed: Corrupted files
+/// are restored from their valid backups, duplicate days are merged into the first entry of the
+/// day, moving the other ones to the trash, titles are renamed from the entries dates, duplicate
+/// tags are removed and orphaned tags are deleted.
+pub async fn fix_entries<D: DataProvider>(provider: &D) -> anyhow::Result<FixSummary> {
+    let mut summary = FixSummary::default();
+
+    if provider.find_recovery_backup().await?.is_some() {
+        // Loading the entries restores them from the backup, keeping a copy of the corrupted file.
+        provider.load_all_entries().await?;
+        provider.take_load_warning();
+        summary.restored_backup = true;
+    }
+
+    let mut entries = provider.load_all_entries().await?;
+    entries.sort_by_key(|entry| entry.id);
+
+    let duplicate_groups: Vec<Vec<u32>> = day_groups(&entries.iter().collect::<Vec<_>>())
+        .into_values()
+        .filter(|ids| ids.len() > 1)
+        .collect();
+
+    for entry_ids in duplicate_groups {
+        let (first_id, duplicate_ids) = entry_ids.split_first().expect("Group can't be empty");
+        let mut merged = entries
+            .iter()
+            .find(|entry| entry.id == *first_id)
+            .cloned()
+            .expect("Entry of the group must exist");
+
+        for duplicate in entries.iter().filter(|e| duplicate_ids.contains(&e.id)) {
+            merged.content = merge_log_lines(&merged.content, &duplicate.content);
+            for tag in duplicate.tags.iter() {
+                if !merged.tags.contains(tag) {
+                    merged.tags.push(tag.to_owned());
+                }
+            }
+            merged.priority = merged.priority.or(duplicate.priority);
+        }
+
+        // Duplicates must be removed first since updating checks for other entries of the day.
+        for duplicate_id in duplicate_ids {
+            provider.remove_entry(*duplicate_id).await?;
+            summary.merged += 1;
+        }
+
+        provider.update_entry(merged).await?;
+    }
+
+    for mut entry in provider.load_all_entries().await? {
+        let expected = day_title(entry.date);
+        let rename = entry.title != expected;
+        let deduplicate = !duplicate_tags(&entry.tags).is_empty();
+
+        if !rename && !deduplicate {
+            continue;
+        }
+
+        if rename {
+            entry.title = expected;
+            summary.renamed += 1;
+        }
+
+        if deduplicate {
+            let mut unique_tags: Vec<String> = Vec::with_capacity(entry.tags.len());
+            for tag in entry.tags.drain(..) {
+                if !unique_tags.contains(&tag) {
+                    unique_tags.push(tag);
+                }
+            }
+            entry.tags = unique_tags;
+            summary.deduplicated_tags += 1;
+        }
+
+        provider.update_entry(entry).await?;
+    }
+
+    summary.orphaned_tags = provider.remove_orphaned_tags().await?;
+
+    Ok(summary)
+}
+
+/// Gets the tags which appear more than once in the given tags.
+fn duplicate_tags(tags: &[String]) -> Vec<String> {
+    let mut duplicates: Vec<String> = Vec::new();
+    for (idx, tag) in tags.iter().enumerate() {
+        if tags[..idx].contains(tag) && !duplicates.contains(tag) {
+            duplicates.push(tag.to_owned());
+        }
+    }
+
+    duplicates
+}
+
+/// Groups the ids of the given entries, sorted by their ids, by their notebooks and days.
+fn day_groups<'a>(entries: &[&'a Entry]) -> BTreeMap<(&'a str, NaiveDate), Vec<u32>> {
+    let mut groups: BTreeMap<(&str, NaiveDate), Vec<u32>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry((entry.notebook.as_str(), entry.date.date_naive()))
+            .or_default()
+            .push(entry.id);
+    }
+
+    groups
+}
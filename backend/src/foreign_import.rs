@@ -0,0 +1,467 @@
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, anyhow, bail, ensure};
+use chrono::{Local, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+
+use crate::log_line::{LOG_TIME_FORMAT, parse_prefixed_tags, parse_tags};
+
+use super::*;
+
+/// Formats of the timestamps in the headings of the entries in jrnl journal files, depending on
+/// the configured time format in jrnl.
+const JRNL_TIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %I:%M:%S %p",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %I:%M %p",
+    "%Y-%m-%d %H:%M",
+];
+
+/// Prefix of the tags in jrnl, which supports tags starting with `#` too.
+const JRNL_TAG_PREFIX: char = '@';
+
+/// Formats of the names of the daily notes files in Obsidian and Logseq.
+const DAILY_NOTE_FORMATS: [&str; 2] = ["%Y-%m-%d", "%Y_%m_%d"];
+
+/// Journaling formats of other apps which can be imported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignFormat {
+    /// jrnl journal file in its plain text format or its JSON export.
+    Jrnl,
+    /// Day One JSON export.
+    DayOne,
+    /// Directory of Obsidian or Logseq daily notes named after their days (`YYYY-MM-DD.md` or
+    /// `YYYY_MM_DD.md`).
+    Obsidian,
+    /// Plain text with a line containing the day (`YYYY-MM-DD`) before the text of each day.
+    Plain,
+}
+
+impl ForeignFormat {
+    pub const ALL: [ForeignFormat; 4] = [
+        ForeignFormat::Jrnl,
+        ForeignFormat::DayOne,
+        ForeignFormat::Obsidian,
+        ForeignFormat::Plain,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ForeignFormat::Jrnl => "jrnl",
+            ForeignFormat::DayOne => "dayone",
+            ForeignFormat::Obsidian => "obsidian",
+            ForeignFormat::Plain => "plain",
+        }
+    }
+}
+
+impl fmt::Display for ForeignFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ForeignFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match ForeignFormat::ALL.into_iter().find(|fmt| fmt.as_str() == s) {
+            Some(format) => Ok(format),
+            None => {
+                bail!("Invalid import format '{s}'. Possible values: jrnl, dayone, obsidian, plain")
+            }
+        }
+    }
+}
+
+/// Note from another app, which is merged with the other notes of its day into one entry.
+struct ForeignNote {
+    day: NaiveDate,
+    /// Time of the note, making its first line a log line.
+    time: Option<NaiveTime>,
+    text: String,
+    tags: Vec<String>,
+}
+
+impl ForeignNote {
+    fn new(day: NaiveDate, time: Option<NaiveTime>, text: String) -> Self {
+        Self {
+            day,
+            time,
+            text,
+            tags: Vec::new(),
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.text.push_str(line);
+    }
+}
+
+/// Reads the entries from the file or directory in the given format of another app, merging
+/// the notes of each day into one entry titled after its day. Notes with times are added as log
+/// lines and the `#tags` in their text are added to the tags of the entries.
+pub fn read_foreign_entries(format: ForeignFormat, path: &Path) -> anyhow::Result<Vec<EntryDraft>> {
+    let notes = match format {
+        ForeignFormat::Jrnl => parse_jrnl(&read_file(path)?)?,
+        ForeignFormat::DayOne => parse_day_one(&read_file(path)?)?,
+        ForeignFormat::Obsidian => read_daily_notes(path)?,
+        ForeignFormat::Plain => parse_plain(&read_file(path)?),
+    };
+
+    Ok(group_by_day(notes))
+}
+
+fn read_file(path: &Path) -> anyhow::Result<String> {
+    fs::read_to_string(path)
+        .with_context(|| format!("Reading import file failed. Path: {}", path.display()))
+}
+
+/// Merges the notes of each day into one entry, ordered by the days and the times of the notes.
+fn group_by_day(mut notes: Vec<ForeignNote>) -> Vec<EntryDraft> {
+    // Sorting is stable, keeping the notes without times in their order.
+    notes.sort_by_key(|note| (note.day, note.time));
+
+    let mut drafts: Vec<EntryDraft> = Vec::new();
+    for note in notes {
+        let text = note.text.trim();
+        let text = match note.time {
+            Some(time) => format!("{} {text}", time.format(LOG_TIME_FORMAT))
+                .trim_end()
+                .to_owned(),
+            None => text.to_owned(),
+        };
+
+        let mut tags = note.tags;
+        tags.extend(
+            parse_tags(&text)
+                .into_iter()
+                .filter(|tag| tag.starts_with(char::is_alphanumeric)),
+        );
+
+        let draft = match drafts.last_mut() {
+            Some(draft) if draft.date.date_naive() == note.day => draft,
+            _ => {
+                let date = Utc.from_utc_datetime(&note.day.and_time(NaiveTime::MIN));
+                drafts.push(EntryDraft::new(date, day_title(date), Vec::new(), None));
+                drafts.last_mut().expect("Draft has just been added")
+            }
+        };
+
+        if !text.is_empty() {
+            if !draft.content.is_empty() {
+                draft.content.push('\n');
+            }
+            draft.content.push_str(&text);
+        }
+
+        for tag in tags {
+            if !draft.tags.contains(&tag) {
+                draft.tags.push(tag);
+            }
+        }
+    }
+
+    drafts
+}
+
+/// Parses the jrnl journal file, which is either in the plain text format with a heading like
+/// `[2024-05-01 09:15] Title` for each entry or exported as JSON.
+fn parse_jrnl(text: &str) -> anyhow::Result<Vec<ForeignNote>> {
+    if text.trim_start().starts_with('{') {
+        return parse_jrnl_json(text);
+    }
+
+    let mut notes: Vec<ForeignNote> = Vec::new();
+    for line in text.lines() {
+        if let Some((date_time, title)) = parse_jrnl_heading(line) {
+            notes.push(ForeignNote::new(
+                date_time.date(),
+                Some(date_time.time()),
+                title.trim().to_owned(),
+            ));
+            continue;
+        }
+
+        match notes.last_mut() {
+            Some(note) => note.push_line(line),
+            None if line.trim().is_empty() => {}
+            None => bail!(
+                "jrnl journal must start with an entry heading like '[2024-05-01 09:15] Title'"
+            ),
+        }
+    }
+
+    for note in notes.iter_mut() {
+        note.tags = parse_prefixed_tags(&note.text, JRNL_TAG_PREFIX);
+    }
+
+    Ok(notes)
+}
+
+fn parse_jrnl_heading(line: &str) -> Option<(NaiveDateTime, &str)> {
+    let (date_txt, title) = line.strip_prefix('[')?.split_once(']')?;
+
+    JRNL_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(date_txt.trim(), format).ok())
+        .map(|date_time| (date_time, title))
+}
+
+#[derive(Deserialize)]
+struct JrnlExport {
+    entries: Vec<JrnlEntry>,
+}
+
+#[derive(Deserialize)]
+struct JrnlEntry {
+    date: NaiveDate,
+    time: String,
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+fn parse_jrnl_json(text: &str) -> anyhow::Result<Vec<ForeignNote>> {
+    let export: JrnlExport =
+        serde_json::from_str(text).context("jrnl JSON export couldn't be parsed")?;
+
+    export
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let time = NaiveTime::parse_from_str(&entry.time, "%H:%M")
+                .or_else(|_| NaiveTime::parse_from_str(&entry.time, "%H:%M:%S"))
+                .with_context(|| format!("Invalid time '{}' in jrnl entry", entry.time))?;
+
+            let mut note = ForeignNote::new(entry.date, Some(time), entry.title);
+            if !entry.body.trim().is_empty() {
+                note.push_line(&entry.body);
+            }
+            note.tags = entry
+                .tags
+                .iter()
+                .map(|tag| tag.trim_start_matches([JRNL_TAG_PREFIX, '#']).to_owned())
+                .filter(|tag| !tag.is_empty())
+                .collect();
+
+            Ok(note)
+        })
+        .collect()
+}
+
+#[derive(Deserialize)]
+struct DayOneExport {
+    entries: Vec<DayOneEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    creation_date: DateTime<Utc>,
+    /// Name of the time zone the entry has been written in, like `Europe/Berlin`.
+    #[serde(default)]
+    time_zone: Option<String>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Parses the JSON file of the Day One export. The times of the entries are converted to the
+/// time zones they have been written in, falling back to the local time zone for the entries
+/// without one.
+fn parse_day_one(text: &str) -> anyhow::Result<Vec<ForeignNote>> {
+    let export: DayOneExport =
+        serde_json::from_str(text).context("Day One JSON export couldn't be parsed")?;
+
+    export
+        .entries
+        .into_iter()
+        .map(|entry| {
+            let date_time = match entry.time_zone.as_deref() {
+                Some(time_zone) => {
+                    let time_zone: Tz = time_zone.parse().map_err(|_| {
+                        anyhow!("Unknown time zone '{time_zone}' in Day One export")
+                    })?;
+                    entry.creation_date.with_timezone(&time_zone).naive_local()
+                }
+                None => entry.creation_date.with_timezone(&Local).naive_local(),
+            };
+
+            let mut note = ForeignNote::new(
+                date_time.date(),
+                Some(date_time.time()),
+                unescape_markdown(&entry.text),
+            );
+            note.tags = entry.tags;
+
+            Ok(note)
+        })
+        .collect()
+}
+
+/// Removes the backslashes Day One adds before the markdown punctuation in the exported text.
+fn unescape_markdown(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\\' && chars.peek().is_some_and(char::is_ascii_punctuation) {
+            continue;
+        }
+        unescaped.push(ch);
+    }
+
+    unescaped
+}
+
+/// Reads the daily notes in the given directory and its sub-directories, skipping the hidden
+/// ones like the settings and trash of Obsidian.
+fn read_daily_notes(dir_path: &Path) -> anyhow::Result<Vec<ForeignNote>> {
+    ensure!(
+        dir_path.is_dir(),
+        "Daily notes path must be a directory. Path: {}",
+        dir_path.display()
+    );
+
+    let mut notes = Vec::new();
+    let mut dirs: Vec<PathBuf> = vec![dir_path.to_owned()];
+    while let Some(dir) = dirs.pop() {
+        for dir_entry in fs::read_dir(&dir)? {
+            let path = dir_entry?.path();
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+
+            if name.starts_with('.') {
+                continue;
+            }
+
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+
+            let Some(day) = name.strip_suffix(".md").and_then(|stem| {
+                DAILY_NOTE_FORMATS
+                    .iter()
+                    .find_map(|format| NaiveDate::parse_from_str(stem, format).ok())
+            }) else {
+                continue;
+            };
+
+            let text = read_file(&path)?;
+            let (tags, body) = split_front_matter(&text);
+
+            let mut note = ForeignNote::new(day, None, body.to_owned());
+            note.tags = tags;
+            notes.push(note);
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Splits the YAML front matter from the note, returning the tags listed in it and the rest of
+/// the note. Both LF and CRLF line endings are accepted.
+fn split_front_matter(text: &str) -> (Vec<String>, &str) {
+    let Some((front_matter, body)) = text
+        .strip_prefix("---")
+        .and_then(|rest| {
+            rest.strip_prefix("\r\n")
+                .or_else(|| rest.strip_prefix('\n'))
+        })
+        .and_then(|rest| {
+            let mut line_start = 0;
+            for line in rest.split_inclusive('\n') {
+                if line.trim_end_matches(['\r', '\n']) == "---" {
+                    return Some((&rest[..line_start], &rest[line_start + line.len()..]));
+                }
+                line_start += line.len();
+            }
+            None
+        })
+    else {
+        return (Vec::new(), text);
+    };
+
+    let mut tags = Vec::new();
+    let mut in_tags_list = false;
+    for line in front_matter.lines() {
+        if let Some(value) = line.strip_prefix("tags:") {
+            in_tags_list = true;
+            tags.extend(
+                value
+                    .trim()
+                    .trim_matches(['[', ']'])
+                    .split(',')
+                    .map(clean_front_matter_tag)
+                    .filter(|tag| !tag.is_empty()),
+            );
+        } else if let Some(item) = line
+            .trim_start()
+            .strip_prefix("- ")
+            .filter(|_| in_tags_list)
+        {
+            tags.push(clean_front_matter_tag(item));
+        } else {
+            in_tags_list = false;
+        }
+    }
+
+    (tags, body)
+}
+
+fn clean_front_matter_tag(tag: &str) -> String {
+    tag.trim()
+        .trim_matches(['"', '\''])
+        .trim_start_matches('#')
+        .to_owned()
+}
+
+/// Parses plain text where each day starts with a line containing the day like `2024-05-01` or
+/// `## 2024-05-01 (Wednesday)`, optionally underlined. Text before the first day is ignored.
+fn parse_plain(text: &str) -> Vec<ForeignNote> {
+    let mut notes: Vec<ForeignNote> = Vec::new();
+    let mut lines = text.lines().peekable();
+    while let Some(line) = lines.next() {
+        if let Some(day) = parse_day_heading(line) {
+            if lines.peek().is_some_and(|next| is_underline(next)) {
+                lines.next();
+            }
+            notes.push(ForeignNote::new(day, None, String::new()));
+            continue;
+        }
+
+        if let Some(note) = notes.last_mut() {
+            note.push_line(line);
+        }
+    }
+
+    notes
+}
+
+fn parse_day_heading(line: &str) -> Option<NaiveDate> {
+    let line = line.trim().trim_start_matches('#').trim_start();
+    let date_txt = line.get(..10)?;
+    let rest = line[10..].trim();
+    if !(rest.is_empty() || rest.starts_with('(') && rest.ends_with(')')) {
+        return None;
+    }
+
+    NaiveDate::parse_from_str(date_txt, "%Y-%m-%d").ok()
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && (line.chars().all(|c| c == '=') || line.chars().all(|c| c == '-'))
+}
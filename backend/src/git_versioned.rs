@@ -0,0 +1,591 @@
+use std::{
+    ffi::OsStr,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, anyhow};
+use git2::{ObjectType, Oid, Repository, Signature, Tree};
+
+use super::*;
+
+const FALLBACK_SIGNATURE_NAME: &str = "tui-journal";
+const FALLBACK_SIGNATURE_EMAIL: &str = "tui-journal@localhost";
+
+const BLOB_FILE_MODE: i32 = 0o100644;
+const TREE_FILE_MODE: i32 = 0o040000;
+
+/// Wraps a data provider, committing the changes on its back-end file or directory to a git
+/// repository after each modification.
+/// The repository must have its working directory in the directory of the back-end (the parent
+/// directory of the back-end file or the back-end directory itself) or in its parent. Otherwise a
+/// new repository will be initialized in the directory of the back-end. Repositories further up
+/// (e.g. a dotfiles repository in the home directory) are never used.
+pub struct GitDataProvide<D: DataProvider> {
+    inner: D,
+    /// Path of the back-end file or directory to version.
+    target_path: PathBuf,
+}
+
+/// A commit in which the versioned back-end file or directory has been changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitVersion {
+    pub commit_id: String,
+    pub time: DateTime<Utc>,
+    pub message: String,
+}
+
+impl<D: DataProvider> GitDataProvide<D> {
+    pub fn new(inner: D, target_path: PathBuf) -> anyhow::Result<Self> {
+        let provider = Self { inner, target_path };
+
+        // Make sure the repository exists before the first change.
+        provider.open_repo()?;
+
+        Ok(provider)
+    }
+
+    /// Returns all the versions of the back-end file or directory starting with the newest one.
+    pub fn versions(&self) -> anyhow::Result<Vec<GitVersion>> {
+        let repo = self.open_repo()?;
+        let rel_path = self.relative_path(&repo)?;
+
+        if repo.head().is_err() {
+            // Repository has no commits yet.
+            return Ok(Vec::new());
+        }
+
+        let mut revwalk = repo.revwalk()?;
+        revwalk.push_head()?;
+        revwalk.set_sorting(git2::Sort::TOPOLOGICAL)?;
+
+        let mut versions = Vec::new();
+        for oid in revwalk {
+            let commit = repo.find_commit(oid?)?;
+            let target_id = path_id(&commit.tree()?, &rel_path);
+
+            let parent_target_id = match commit.parent(0) {
+                Ok(parent) => path_id(&parent.tree()?, &rel_path),
+                Err(_) => None,
+            };
+
+            if target_id == parent_target_id {
+                continue;
+            }
+
+            let time = DateTime::from_timestamp(commit.time().seconds(), 0).unwrap_or_default();
+
+            versions.push(GitVersion {
+                commit_id: commit.id().to_string(),
+                time,
+                message: commit.summary().unwrap_or_default().to_owned(),
+            });
+        }
+
+        Ok(versions)
+    }
+
+    /// Writes the state of the back-end file or directory at the given commit to the given
+    /// destination path. The commit can be given as any revision git accepts (e.g. short id).
+    /// Returns `false` if the back-end didn't exist in that commit.
+    pub fn checkout_version(&self, commit_id: &str, dest_path: &Path) -> anyhow::Result<bool> {
+        let repo = self.open_repo()?;
+        let rel_path = self.relative_path(&repo)?;
+
+        let commit = repo
+            .revparse_single(commit_id)
+            .and_then(|object| object.peel_to_commit())
+            .with_context(|| format!("Commit {commit_id} couldn't be found"))?;
+        let tree = commit.tree()?;
+
+        let Ok(tree_entry) = tree.get_path(&rel_path) else {
+            return Ok(false);
+        };
+
+        let object = tree_entry.to_object(&repo)?;
+        match object.kind() {
+            Some(ObjectType::Blob) => {
+                let blob = object.peel_to_blob()?;
+                if let Some(parent) = dest_path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(dest_path, blob.content())?;
+            }
+            Some(ObjectType::Tree) => {
+                let sub_tree = object.peel_to_tree()?;
+                write_tree(&repo, &sub_tree, dest_path)?;
+            }
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+
+    fn open_repo(&self) -> anyhow::Result<Repository> {
+        let repo_dir = if self.target_path.is_dir() {
+            self.target_path.clone()
+        } else {
+            self.target_path
+                .parent()
+                .map(Path::to_path_buf)
+                .context("Back-end file must have a parent directory")?
+        };
+
+        if !repo_dir.exists() {
+            std::fs::create_dir_all(&repo_dir)?;
+        }
+
+        let existing_repo = Repository::open(&repo_dir)
+            .ok()
+            .or_else(|| repo_dir.parent().and_then(|dir| Repository::open(dir).ok()));
+
+        match existing_repo {
+            Some(repo) => Ok(repo),
+            None => {
+                log::trace!("Initializing git repository in {}", repo_dir.display());
+                Repository::init(&repo_dir).map_err(|err| {
+                    anyhow!(
+                        "Initializing git repository in {} failed. Error info: {err}",
+                        repo_dir.display()
+                    )
+                })
+            }
+        }
+    }
+
+    /// Gets the path of the versioned target relative to the repository working directory.
+    fn relative_path(&self, repo: &Repository) -> anyhow::Result<PathBuf> {
+        let workdir = repo
+            .workdir()
+            .context("Git repositories without working directory aren't supported")?
+            .canonicalize()?;
+
+        // The target may not exist yet, therefore its parent is canonicalized instead.
+        let file_name = self
+            .target_path
+            .file_name()
+            .context("Back-end path must have a name")?;
+        let parent = self
+            .target_path
+            .parent()
+            .context("Back-end path must have a parent directory")?
+            .canonicalize()?;
+
+        let rel_path = parent
+            .join(file_name)
+            .strip_prefix(&workdir)
+            .map(Path::to_path_buf)
+            .context("Back-end path must be inside the git repository")?;
+
+        Ok(rel_path)
+    }
+
+    /// Commits the current state of the versioned target with the given message.
+    /// The tree of the commit is built from the tree of HEAD with only the versioned target
+    /// replaced, so the changes staged by the user aren't committed. The index is updated for the
+    /// versioned target only, keeping the other staged changes as they are.
+    async fn commit_changes(&self, message: &str) -> anyhow::Result<()> {
+        // Changes kept outside the back-end file (e.g. SQLite write-ahead log) must be written
+        // into it to be part of the commit.
+        self.inner.flush_to_files().await?;
+
+        let repo = self.open_repo()?;
+        let rel_path = self.relative_path(&repo)?;
+
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parent_tree = parent.as_ref().map(|p| p.tree()).transpose()?;
+
+        let target = snapshot_path(&repo, &self.target_path, &rel_path)?;
+        let components: Vec<_> = rel_path.iter().collect();
+        let tree_id = match replace_path(&repo, parent_tree.as_ref(), &components, target)? {
+            Some(tree_id) => tree_id,
+            None => repo.treebuilder(None)?.write()?,
+        };
+        let tree = repo.find_tree(tree_id)?;
+
+        if parent.as_ref().is_some_and(|p| p.tree_id() == tree.id()) {
+            log::trace!("Git versioning: No changes to commit for '{message}'");
+            return Ok(());
+        }
+
+        let signature = repo
+            .signature()
+            .or_else(|_| Signature::now(FALLBACK_SIGNATURE_NAME, FALLBACK_SIGNATURE_EMAIL))?;
+
+        let parents: Vec<_> = parent.iter().collect();
+
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?;
+
+        update_index(&repo, &tree, &rel_path)?;
+
+        Ok(())
+    }
+}
+
+impl<D: DataProvider> DataProvider for GitDataProvide<D> {
+    async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
+        self.inner.load_all_entries().await
+    }
+
+    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        let entry = self.inner.add_entry(entry).await?;
+
+        self.commit_changes(&format!("add {}", entry.title)).await?;
+
+        Ok(entry)
+    }
+
+    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let title = self
+            .inner
+            .load_all_entries()
+            .await?
+            .into_iter()
+            .find(|e| e.id == entry_id)
+            .map(|e| e.title);
+
+        self.inner.remove_entry(entry_id).await?;
+
+        if let Some(title) = title {
+            self.commit_changes(&format!("remove {title}")).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let entry = self.inner.update_entry(entry).await?;
+
+        self.commit_changes(&format!("update {}", entry.title))
+            .await?;
+
+        Ok(entry)
+    }
+
+    async fn add_existing_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let entry = self.inner.add_existing_entry(entry).await?;
+
+        self.commit_changes(&format!("add {}", entry.title)).await?;
+
+        Ok(entry)
+    }
+
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        self.inner.load_trash().await
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        let entry = self.inner.restore_entry(entry_id).await?;
+
+        self.commit_changes(&format!("restore {}", entry.title))
+            .await?;
+
+        Ok(entry)
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let title = self
+            .inner
+            .load_trash()
+            .await?
+            .into_iter()
+            .find(|e| e.id == entry_id)
+            .map(|e| e.title);
+
+        self.inner.purge_entry(entry_id).await?;
+
+        if let Some(title) = title {
+            self.commit_changes(&format!("purge {title}")).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        let count = self.inner.purge_trash(deleted_before).await?;
+
+        if count > 0 {
+            self.commit_changes(&format!("purge {count} entries from trash"))
+                .await?;
+        }
+
+        Ok(count)
+    }
+
+    async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
+        self.inner.get_export_object(entries_ids).await
+    }
+
+    async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
+        let count = entries_dto.entries.len();
+
+        self.inner.import_entries(entries_dto).await?;
+
+        self.commit_changes(&format!("import {count} entries"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn import_entries_with_strategy(
+        &self,
+        entries_dto: EntriesDTO,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
+        let count = entries_dto.entries.len();
+
+        let summary = self
+            .inner
+            .import_entries_with_strategy(entries_dto, strategy)
+            .await?;
+
+        self.commit_changes(&format!("import {count} entries ({strategy})"))
+            .await?;
+
+        Ok(summary)
+    }
+
+    async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
+        self.inner.assign_priority_to_entries(priority).await?;
+
+        self.commit_changes(&format!("assign priority {priority}"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        self.inner.search(query).await
+    }
+
+    async fn append_log_line(
+        &self,
+        entry_id: u32,
+        line: LogLine,
+    ) -> Result<Entry, ModifyEntryError> {
+        let entry = self.inner.append_log_line(entry_id, line).await?;
+
+        self.commit_changes(&format!("update {}", entry.title))
+            .await?;
+
+        Ok(entry)
+    }
+
+    async fn query_log_lines(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> anyhow::Result<Vec<EntryLogLine>> {
+        self.inner.query_log_lines(start, end).await
+    }
+
+    async fn load_orphaned_tags(&self) -> anyhow::Result<Vec<(u32, String)>> {
+        self.inner.load_orphaned_tags().await
+    }
+
+    async fn remove_orphaned_tags(&self) -> anyhow::Result<usize> {
+        let count = self.inner.remove_orphaned_tags().await?;
+
+        if count > 0 {
+            self.commit_changes(&format!("remove {count} orphaned tags"))
+                .await?;
+        }
+
+        Ok(count)
+    }
+
+    async fn flush_to_files(&self) -> anyhow::Result<()> {
+        self.inner.flush_to_files().await
+    }
+
+    async fn find_recovery_backup(&self) -> anyhow::Result<Option<std::path::PathBuf>> {
+        self.inner.find_recovery_backup().await
+    }
+
+    fn take_load_warning(&self) -> Option<String> {
+        self.inner.take_load_warning()
+    }
+}
+
+/// Gets the object id of the given path in the tree if exists.
+fn path_id(tree: &Tree, path: &Path) -> Option<Oid> {
+    tree.get_path(path).ok().map(|entry| entry.id())
+}
+
+/// Writes the current state of the given file or directory into the object database of the
+/// repository, returning the id and the file mode of the created object.
+/// Returns `None` if the path doesn't exist or contains no files, since git doesn't track empty
+/// directories.
+fn snapshot_path(
+    repo: &Repository,
+    path: &Path,
+    rel_path: &Path,
+) -> anyhow::Result<Option<(Oid, i32)>> {
+    if path.is_file() {
+        return Ok(Some((repo.blob_path(path)?, BLOB_FILE_MODE)));
+    }
+
+    if !path.is_dir() {
+        return Ok(None);
+    }
+
+    let mut builder = repo.treebuilder(None)?;
+    for dir_entry in std::fs::read_dir(path)? {
+        let dir_entry = dir_entry?;
+        let name = dir_entry.file_name();
+        let entry_rel_path = rel_path.join(&name);
+        if name == ".git" || repo.is_path_ignored(&entry_rel_path)? {
+            continue;
+        }
+
+        if let Some((id, mode)) = snapshot_path(repo, &dir_entry.path(), &entry_rel_path)? {
+            builder.insert(&name, id, mode)?;
+        }
+    }
+
+    if builder.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some((builder.write()?, TREE_FILE_MODE)))
+}
+
+/// Sets the entries of the versioned target in the index to their committed state, otherwise the
+/// index would keep its previous state staged and the next commit of the user would revert it.
+fn update_index(repo: &Repository, tree: &Tree, rel_path: &Path) -> anyhow::Result<()> {
+    if repo.is_bare() {
+        return Ok(());
+    }
+
+    let mut committed_paths = Vec::new();
+    match tree.get_path(rel_path) {
+        Ok(entry) if entry.kind() == Some(ObjectType::Blob) => {
+            committed_paths.push(rel_path.to_path_buf());
+        }
+        Ok(entry) => {
+            let sub_tree = entry.to_object(repo)?.peel_to_tree()?;
+            collect_blob_paths(repo, &sub_tree, rel_path, &mut committed_paths)?;
+        }
+        // The target is the whole working directory of the repository.
+        Err(_) if rel_path.as_os_str().is_empty() => {
+            collect_blob_paths(repo, tree, rel_path, &mut committed_paths)?;
+        }
+        Err(_) => {}
+    }
+
+    let mut index = repo.index()?;
+    if rel_path.as_os_str().is_empty() {
+        index.clear()?;
+    } else {
+        index.remove_all([rel_path], None)?;
+    }
+
+    for path in committed_paths {
+        index.add_path(&path)?;
+    }
+
+    index.write()?;
+
+    Ok(())
+}
+
+/// Collects the paths of all the files in the given tree, prefixed with the given path.
+fn collect_blob_paths(
+    repo: &Repository,
+    tree: &Tree,
+    prefix: &Path,
+    paths: &mut Vec<PathBuf>,
+) -> anyhow::Result<()> {
+    for tree_entry in tree.iter() {
+        let Some(name) = tree_entry.name() else {
+            continue;
+        };
+        let path = prefix.join(name);
+
+        match tree_entry.kind() {
+            Some(ObjectType::Blob) => paths.push(path),
+            Some(ObjectType::Tree) => {
+                let sub_tree = tree_entry.to_object(repo)?.peel_to_tree()?;
+                collect_blob_paths(repo, &sub_tree, &path, paths)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes a copy of the given tree with the entry on the given path components replaced by the
+/// given target, or removed if the target is `None`. Returns `None` if the resulting tree is
+/// empty.
+fn replace_path(
+    repo: &Repository,
+    tree: Option<&Tree>,
+    components: &[&OsStr],
+    target: Option<(Oid, i32)>,
+) -> anyhow::Result<Option<Oid>> {
+    let Some((name, rest)) = components.split_first() else {
+        // The target is the whole working directory of the repository.
+        return Ok(target.map(|(id, _)| id));
+    };
+
+    let new_entry = if rest.is_empty() {
+        target
+    } else {
+        let sub_tree = tree
+            .and_then(|tree| tree.get_path(Path::new(name)).ok())
+            .and_then(|entry| entry.to_object(repo).ok())
+            .and_then(|object| object.into_tree().ok());
+
+        replace_path(repo, sub_tree.as_ref(), rest, target)?.map(|id| (id, TREE_FILE_MODE))
+    };
+
+    let mut builder = repo.treebuilder(tree)?;
+    match new_entry {
+        Some((id, mode)) => {
+            builder.insert(name, id, mode)?;
+        }
+        None => {
+            if builder.get(name)?.is_some() {
+                builder.remove(name)?;
+            }
+        }
+    }
+
+    if builder.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(builder.write()?))
+}
+
+/// Writes the files of the given tree recursively into the destination directory.
+fn write_tree(repo: &Repository, tree: &Tree, dest_dir: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(dest_dir)?;
+
+    for tree_entry in tree.iter() {
+        let Some(name) = tree_entry.name() else {
+            continue;
+        };
+        let dest_path = dest_dir.join(name);
+
+        let object = tree_entry.to_object(repo)?;
+        match object.kind() {
+            Some(ObjectType::Blob) => {
+                std::fs::write(dest_path, object.peel_to_blob()?.content())?;
+            }
+            Some(ObjectType::Tree) => {
+                write_tree(repo, &object.peel_to_tree()?, &dest_path)?;
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
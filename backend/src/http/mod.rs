@@ -0,0 +1,209 @@
+use anyhow::anyhow;
+use reqwest::{Client, Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+mod server;
+pub use server::serve;
+
+/// Body of the error responses of the server.
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorBody {
+    message: String,
+    /// Day and id of the existing entry for [`ModifyEntryError::DuplicateDay`] errors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    duplicate_day: Option<(NaiveDate, u32)>,
+}
+
+/// Body of the request to assign priority to the entries without priority.
+#[derive(Debug, Serialize, Deserialize)]
+struct PriorityBody {
+    priority: u32,
+}
+
+/// Body of the request to purge the entries moved to the trash before the given time.
+#[derive(Debug, Serialize, Deserialize)]
+struct PurgeBody {
+    deleted_before: DateTime<Utc>,
+}
+
+/// Data provider using a journal server started with the `serve` command, which exposes the
+/// data provider configured on the server over HTTP.
+pub struct HttpDataProvide {
+    client: Client,
+    base_url: String,
+    /// Token sent as bearer authorization with each request.
+    token: Option<String>,
+}
+
+impl HttpDataProvide {
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_owned();
+
+        Self {
+            client: Client::new(),
+            base_url,
+            token: None,
+        }
+    }
+
+    /// Sets the token to authorize the requests on the server with.
+    #[must_use]
+    pub fn with_token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}{path}", self.base_url));
+
+        match &self.token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+}
+
+impl DataProvider for HttpDataProvide {
+    async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
+        let entries = send_json(self.request(Method::GET, "/entries")).await?;
+
+        Ok(entries)
+    }
+
+    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        send_json(self.request(Method::POST, "/entries").json(&entry)).await
+    }
+
+    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        send(self.request(Method::DELETE, &format!("/entries/{entry_id}"))).await?;
+
+        Ok(())
+    }
+
+    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let path = format!("/entries/{}", entry.id);
+
+        send_json(self.request(Method::PUT, &path).json(&entry)).await
+    }
+
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        let entries = send_json(self.request(Method::GET, "/trash")).await?;
+
+        Ok(entries)
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        let path = format!("/trash/{entry_id}/restore");
+
+        send_json(self.request(Method::POST, &path)).await
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        send(self.request(Method::DELETE, &format!("/trash/{entry_id}"))).await?;
+
+        Ok(())
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        let request = self
+            .request(Method::POST, "/trash/purge")
+            .json(&PurgeBody { deleted_before });
+
+        let count = send_json(request).await?;
+
+        Ok(count)
+    }
+
+    async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
+        let entries_dto =
+            send_json(self.request(Method::POST, "/export").json(entries_ids)).await?;
+
+        Ok(entries_dto)
+    }
+
+    async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
+        send(self.request(Method::POST, "/import").json(&entries_dto)).await?;
+
+        Ok(())
+    }
+
+    async fn import_entries_with_strategy(
+        &self,
+        entries_dto: EntriesDTO,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
+        let request = self
+            .request(Method::POST, "/import")
+            .query(&[("strategy", strategy.as_str())])
+            .json(&entries_dto);
+
+        let summary = send_json(request).await?;
+
+        Ok(summary)
+    }
+
+    async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
+        let request = self
+            .request(Method::POST, "/assign-priority")
+            .json(&PriorityBody { priority });
+
+        send(request).await?;
+
+        Ok(())
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        let request = self
+            .request(Method::GET, "/search")
+            .query(&[("query", query)]);
+
+        let results = send_json(request).await?;
+
+        Ok(results)
+    }
+}
+
+/// Sends the request, converting the error responses of the server to errors.
+async fn send(request: RequestBuilder) -> Result<Response, ModifyEntryError> {
+    let response = request.send().await.map_err(|err| {
+        log::error!("Sending request to journal server failed. Error info: {err}");
+        anyhow!("Journal server couldn't be reached. Error info: {err}")
+    })?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body: Option<ErrorBody> = response.json().await.ok();
+
+    let err = match (status, body) {
+        (
+            StatusCode::CONFLICT,
+            Some(ErrorBody {
+                duplicate_day: Some((day, existing_id)),
+                ..
+            }),
+        ) => ModifyEntryError::DuplicateDay { day, existing_id },
+        (StatusCode::BAD_REQUEST, Some(body)) => ModifyEntryError::ValidationError(body.message),
+        (status, Some(body)) => anyhow!("Journal server error ({status}): {}", body.message).into(),
+        (status, None) => anyhow!("Journal server error ({status})").into(),
+    };
+
+    Err(err)
+}
+
+/// Sends the request, parsing the JSON body of the successful response.
+async fn send_json<T: DeserializeOwned>(request: RequestBuilder) -> Result<T, ModifyEntryError> {
+    let response = send(request).await?;
+
+    let value = response.json().await.map_err(|err| {
+        anyhow!("Response of journal server couldn't be parsed. Error info: {err}")
+    })?;
+
+    Ok(value)
+}
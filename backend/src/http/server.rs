@@ -0,0 +1,490 @@
+use std::{net::IpAddr, sync::Arc};
+
+use anyhow::{Context, ensure};
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Path, Query, Request, State},
+    http::{HeaderMap, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+};
+use scopeguard::defer;
+use sha2::{Digest, Sha256};
+use tokio::{
+    net::TcpListener,
+    sync::{mpsc, oneshot},
+};
+
+use super::*;
+
+/// Count of the calls waiting for the data provider before the request handlers are suspended.
+const CALLS_BUFFER: usize = 64;
+
+type Responder<T, E = anyhow::Error> = oneshot::Sender<Result<T, E>>;
+
+/// Calls on the data provider sent from the request handlers to the task running the provider.
+enum ProviderCall {
+    LoadAll(Responder<Vec<Entry>>),
+    Add(EntryDraft, Responder<Entry, ModifyEntryError>),
+    Remove(u32, Responder<()>),
+    Update(Entry, Responder<Entry, ModifyEntryError>),
+    LoadTrash(Responder<Vec<Entry>>),
+    Restore(u32, Responder<Entry, ModifyEntryError>),
+    Purge(u32, Responder<()>),
+    PurgeTrash(DateTime<Utc>, Responder<usize>),
+    Export(Vec<u32>, Responder<EntriesDTO>),
+    Import(EntriesDTO, Responder<()>),
+    ImportWithStrategy(EntriesDTO, ImportStrategy, Responder<ImportSummary>),
+    AssignPriority(u32, Responder<()>),
+    Search(String, Responder<Vec<(u32, Vec<MatchSpan>)>>),
+}
+
+#[derive(Clone)]
+struct ServerState {
+    calls: mpsc::Sender<ProviderCall>,
+    /// Token the requests must provide as bearer authorization.
+    token: Arc<str>,
+    /// Host names the requests can be addressed to apart from IP addresses and `localhost`.
+    allowed_hosts: Arc<[String]>,
+}
+
+impl ServerState {
+    /// Sends the call created with the given function to the data provider, waiting for its result.
+    async fn call<T, E>(
+        &self,
+        make_call: impl FnOnce(Responder<T, E>) -> ProviderCall,
+    ) -> Result<T, ApiError>
+    where
+        ApiError: From<E>,
+    {
+        let (tx, rx) = oneshot::channel();
+
+        self.calls
+            .send(make_call(tx))
+            .await
+            .map_err(|_| ApiError::internal("Data provider isn't running"))?;
+
+        let result = rx
+            .await
+            .map_err(|_| ApiError::internal("Data provider didn't respond"))?;
+
+        result.map_err(ApiError::from)
+    }
+}
+
+/// Error response of the server with its status code.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    body: ErrorBody,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: ErrorBody {
+                message: message.into(),
+                duplicate_day: None,
+            },
+        }
+    }
+
+    fn internal(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, message)
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(err: anyhow::Error) -> Self {
+        log::error!("Journal server: Request failed. Error info: {err:?}");
+        Self::internal(err.to_string())
+    }
+}
+
+impl From<ModifyEntryError> for ApiError {
+    fn from(err: ModifyEntryError) -> Self {
+        match err {
+            ModifyEntryError::ValidationError(message) => {
+                Self::new(StatusCode::BAD_REQUEST, message)
+            }
+            ModifyEntryError::DuplicateDay { day, existing_id } => Self {
+                status: StatusCode::CONFLICT,
+                body: ErrorBody {
+                    message: err.to_string(),
+                    duplicate_day: Some((day, existing_id)),
+                },
+            },
+            ModifyEntryError::DataError(err) => err.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(self.body)).into_response()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ImportParams {
+    strategy: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchParams {
+    query: String,
+}
+
+/// Serves the given data provider over HTTP with JSON bodies on the given listener, requiring the
+/// requests to authorize with the given token as bearer.
+/// Requests must be addressed to an IP address, `localhost` or one of the given allowed host names
+/// to protect from web pages reaching the server via DNS rebinding.
+///
+/// The routes are:
+/// - `GET /entries`, `POST /entries` to list and create entries.
+/// - `GET /entries/{id}`, `PUT /entries/{id}`, `DELETE /entries/{id}` for a single entry.
+/// - `GET /trash`, `POST /trash/{id}/restore`, `DELETE /trash/{id}` and `POST /trash/purge` for
+///   the entries in the trash.
+/// - `POST /export` with the ids of the entries, returning the transfer data.
+/// - `POST /import` with transfer data and optional `strategy` query parameter.
+/// - `POST /assign-priority` and `GET /search?query=`.
+///
+/// The server runs until it fails. The data provider is driven on the current task, handling the
+/// calls of the requests one by one.
+pub async fn serve<D: DataProvider>(
+    provider: D,
+    listener: TcpListener,
+    token: String,
+    allowed_hosts: Vec<String>,
+) -> anyhow::Result<()> {
+    ensure!(!token.is_empty(), "Server token can't be empty");
+
+    let (calls_tx, calls_rx) = mpsc::channel(CALLS_BUFFER);
+
+    let state = ServerState {
+        calls: calls_tx,
+        token: Arc::from(token),
+        allowed_hosts: Arc::from(allowed_hosts),
+    };
+
+    let router = Router::new()
+        .route("/entries", get(list_entries).post(create_entry))
+        .route(
+            "/entries/{id}",
+            get(get_entry).put(update_entry).delete(delete_entry),
+        )
+        .route("/trash", get(list_trash))
+        .route("/trash/purge", post(purge_trash))
+        .route("/trash/{id}", delete(purge_entry))
+        .route("/trash/{id}/restore", post(restore_entry))
+        .route("/export", post(export_entries))
+        .route("/import", post(import_entries))
+        .route("/assign-priority", post(assign_priority))
+        .route("/search", get(search))
+        .layer(middleware::from_fn_with_state(state.clone(), authorize))
+        .layer(middleware::from_fn_with_state(state.clone(), check_host))
+        .with_state(state);
+
+    if let Ok(addr) = listener.local_addr() {
+        log::info!("Journal server listening on {addr}");
+    }
+
+    // Data provider futures aren't required to be `Send`, therefore only the HTTP server is
+    // spawned while the provider runs on the current task.
+    let server = tokio::spawn(async move { axum::serve(listener, router).await });
+    let server_abort = server.abort_handle();
+    defer! {
+        server_abort.abort();
+    }
+
+    tokio::select! {
+        result = server => result
+            .context("Journal server task failed")?
+            .context("Journal server failed"),
+        () = run_provider(provider, calls_rx) => Ok(()),
+    }
+}
+
+/// Runs the calls on the data provider until all the senders are dropped.
+async fn run_provider<D: DataProvider>(provider: D, mut calls: mpsc::Receiver<ProviderCall>) {
+    // Results are ignored on send since it fails only if the request has been cancelled.
+    while let Some(call) = calls.recv().await {
+        match call {
+            ProviderCall::LoadAll(tx) => {
+                let _ = tx.send(provider.load_all_entries().await);
+            }
+            ProviderCall::Add(draft, tx) => {
+                let _ = tx.send(provider.add_entry(draft).await);
+            }
+            ProviderCall::Remove(id, tx) => {
+                let _ = tx.send(provider.remove_entry(id).await);
+            }
+            ProviderCall::Update(entry, tx) => {
+                let _ = tx.send(provider.update_entry(entry).await);
+            }
+            ProviderCall::LoadTrash(tx) => {
+                let _ = tx.send(provider.load_trash().await);
+            }
+            ProviderCall::Restore(id, tx) => {
+                let _ = tx.send(provider.restore_entry(id).await);
+            }
+            ProviderCall::Purge(id, tx) => {
+                let _ = tx.send(provider.purge_entry(id).await);
+            }
+            ProviderCall::PurgeTrash(deleted_before, tx) => {
+                let _ = tx.send(provider.purge_trash(deleted_before).await);
+            }
+            ProviderCall::Export(ids, tx) => {
+                let _ = tx.send(provider.get_export_object(&ids).await);
+            }
+            ProviderCall::Import(entries_dto, tx) => {
+                let _ = tx.send(provider.import_entries(entries_dto).await);
+            }
+            ProviderCall::ImportWithStrategy(entries_dto, strategy, tx) => {
+                let result = provider
+                    .import_entries_with_strategy(entries_dto, strategy)
+                    .await;
+                let _ = tx.send(result);
+            }
+            ProviderCall::AssignPriority(priority, tx) => {
+                let _ = tx.send(provider.assign_priority_to_entries(priority).await);
+            }
+            ProviderCall::Search(query, tx) => {
+                let _ = tx.send(provider.search(&query).await);
+            }
+        }
+    }
+}
+
+async fn authorize(
+    State(state): State<ServerState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|provided| tokens_match(provided, &state.token));
+
+    if !authorized {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid authorization token",
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+async fn check_host(
+    State(state): State<ServerState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    let allowed = request
+        .headers()
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|host| host_allowed(host, &state.allowed_hosts));
+
+    if !allowed {
+        return Err(ApiError::new(
+            StatusCode::MISDIRECTED_REQUEST,
+            "Missing or unexpected host",
+        ));
+    }
+
+    Ok(next.run(request).await)
+}
+
+/// Checks if the given host header, with an optional port, is an IP address, `localhost` or one
+/// of the allowed host names. Pages using DNS rebinding are always on other host names.
+fn host_allowed(host: &str, allowed_hosts: &[String]) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split_once(']').map_or(rest, |(name, _)| name),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+
+    name.parse::<IpAddr>().is_ok()
+        || name.eq_ignore_ascii_case("localhost")
+        || allowed_hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(name))
+}
+
+/// Compares the provided token with the expected one in constant time to avoid leaking the
+/// token through the response times. The SHA-256 digests of the tokens are compared, so their
+/// lengths don't matter either.
+fn tokens_match(provided: &str, token: &str) -> bool {
+    let provided = Sha256::digest(provided.as_bytes());
+    let token = Sha256::digest(token.as_bytes());
+
+    provided
+        .iter()
+        .zip(token.iter())
+        .fold(0, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+async fn list_entries(State(state): State<ServerState>) -> Result<Json<Vec<Entry>>, ApiError> {
+    state.call(ProviderCall::LoadAll).await.map(Json)
+}
+
+async fn get_entry(
+    State(state): State<ServerState>,
+    Path(id): Path<u32>,
+) -> Result<Json<Entry>, ApiError> {
+    state
+        .call(ProviderCall::LoadAll)
+        .await?
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .map(Json)
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, format!("Entry {id} doesn't exist")))
+}
+
+async fn create_entry(
+    State(state): State<ServerState>,
+    Json(draft): Json<EntryDraft>,
+) -> Result<(StatusCode, Json<Entry>), ApiError> {
+    let entry = state.call(|tx| ProviderCall::Add(draft, tx)).await?;
+
+    Ok((StatusCode::CREATED, Json(entry)))
+}
+
+async fn update_entry(
+    State(state): State<ServerState>,
+    Path(id): Path<u32>,
+    Json(mut entry): Json<Entry>,
+) -> Result<Json<Entry>, ApiError> {
+    entry.id = id;
+
+    state
+        .call(|tx| ProviderCall::Update(entry, tx))
+        .await
+        .map(Json)
+}
+
+async fn delete_entry(
+    State(state): State<ServerState>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, ApiError> {
+    state.call(|tx| ProviderCall::Remove(id, tx)).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn list_trash(State(state): State<ServerState>) -> Result<Json<Vec<Entry>>, ApiError> {
+    state.call(ProviderCall::LoadTrash).await.map(Json)
+}
+
+async fn restore_entry(
+    State(state): State<ServerState>,
+    Path(id): Path<u32>,
+) -> Result<Json<Entry>, ApiError> {
+    state
+        .call(|tx| ProviderCall::Restore(id, tx))
+        .await
+        .map(Json)
+}
+
+async fn purge_entry(
+    State(state): State<ServerState>,
+    Path(id): Path<u32>,
+) -> Result<StatusCode, ApiError> {
+    state.call(|tx| ProviderCall::Purge(id, tx)).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn purge_trash(
+    State(state): State<ServerState>,
+    Json(body): Json<PurgeBody>,
+) -> Result<Json<usize>, ApiError> {
+    state
+        .call(|tx| ProviderCall::PurgeTrash(body.deleted_before, tx))
+        .await
+        .map(Json)
+}
+
+async fn export_entries(
+    State(state): State<ServerState>,
+    Json(ids): Json<Vec<u32>>,
+) -> Result<Json<EntriesDTO>, ApiError> {
+    state
+        .call(|tx| ProviderCall::Export(ids, tx))
+        .await
+        .map(Json)
+}
+
+async fn import_entries(
+    State(state): State<ServerState>,
+    Query(params): Query<ImportParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    // Transfer data is parsed from the raw body to upgrade older versions, therefore the content
+    // type must be checked here. Otherwise pages could send it as a simple cross-site request.
+    let is_json = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+    if !is_json {
+        return Err(ApiError::new(
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Transfer data must be sent as application/json",
+        ));
+    }
+
+    // Transfer data of older versions must be upgraded before importing it.
+    let (entries_dto, _) = EntriesDTO::parse_versioned(body.as_ref())
+        .map_err(|err| ApiError::new(StatusCode::BAD_REQUEST, err.to_string()))?;
+
+    match params.strategy {
+        Some(strategy) => {
+            let strategy: ImportStrategy = strategy.parse().map_err(|err: anyhow::Error| {
+                ApiError::new(StatusCode::BAD_REQUEST, err.to_string())
+            })?;
+
+            let summary = state
+                .call(|tx| ProviderCall::ImportWithStrategy(entries_dto, strategy, tx))
+                .await?;
+
+            Ok(Json(summary).into_response())
+        }
+        None => {
+            state
+                .call(|tx| ProviderCall::Import(entries_dto, tx))
+                .await?;
+
+            Ok(StatusCode::NO_CONTENT.into_response())
+        }
+    }
+}
+
+async fn assign_priority(
+    State(state): State<ServerState>,
+    Json(body): Json<PriorityBody>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .call(|tx| ProviderCall::AssignPriority(body.priority, tx))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn search(
+    State(state): State<ServerState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<(u32, Vec<MatchSpan>)>>, ApiError> {
+    state
+        .call(|tx| ProviderCall::Search(params.query, tx))
+        .await
+        .map(Json)
+}
@@ -0,0 +1,244 @@
+use std::{fmt, str::FromStr};
+
+use crate::log_line::line_time;
+use anyhow::bail;
+
+use super::*;
+
+/// Strategy to resolve imported entries which exist already, having the same UUID as an existing
+/// entry or the same day in the same notebook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImportStrategy {
+    /// Keep the existing entry and ignore the imported one.
+    Skip,
+    /// Overwrite the existing entry with the imported one.
+    Replace,
+    /// Merge the lines of the imported entry into the existing one, inserting the timestamped
+    /// lines in time order.
+    AppendLines,
+    /// Keep the contents of both entries, adding the imported content after the existing one.
+    /// Each day can have one entry only, therefore both can't be kept as separate entries.
+    #[default]
+    KeepBoth,
+}
+
+impl ImportStrategy {
+    pub const ALL: [ImportStrategy; 4] = [
+        ImportStrategy::Skip,
+        ImportStrategy::Replace,
+        ImportStrategy::AppendLines,
+        ImportStrategy::KeepBoth,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportStrategy::Skip => "skip",
+            ImportStrategy::Replace => "replace",
+            ImportStrategy::AppendLines => "append-lines",
+            ImportStrategy::KeepBoth => "keep-both",
+        }
+    }
+}
+
+impl fmt::Display for ImportStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ImportStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match ImportStrategy::ALL.into_iter().find(|st| st.as_str() == s) {
+            Some(strategy) => Ok(strategy),
+            None => bail!(
+                "Invalid import strategy '{s}'. Possible values: skip, replace, append-lines, keep-both"
+            ),
+        }
+    }
+}
+
+/// Counts of the entries affected by an import.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct ImportSummary {
+    /// Imported entries added as new entries.
+    pub created: usize,
+    /// Existing entries updated with the data of the imported ones.
+    pub merged: usize,
+    /// Imported entries ignored because their day exists already or has no new data.
+    pub skipped: usize,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} created, {} merged, {} skipped",
+            self.created, self.merged, self.skipped
+        )
+    }
+}
+
+/// Storage the imported entries are written to.
+pub(crate) trait ImportTarget {
+    async fn add(&mut self, draft: EntryDraft) -> Result<Entry, ModifyEntryError>;
+    async fn update(&mut self, entry: Entry) -> Result<Entry, ModifyEntryError>;
+}
+
+/// Writes the imported entries with the methods of the data provider, each one on its own.
+struct ProviderTarget<'a, D: DataProvider + ?Sized>(&'a D);
+
+impl<D: DataProvider + ?Sized> ImportTarget for ProviderTarget<'_, D> {
+    async fn add(&mut self, draft: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        self.0.add_entry(draft).await
+    }
+
+    async fn update(&mut self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        self.0.update_entry(entry).await
+    }
+}
+
+/// Imports the entries to the given provider, resolving the entries of days which exist already
+/// with the given strategy.
+pub(crate) async fn import_with_strategy<D: DataProvider + ?Sized>(
+    provider: &D,
+    entries_dto: EntriesDTO,
+    strategy: ImportStrategy,
+) -> anyhow::Result<ImportSummary> {
+    entries_dto.ensure_current_version()?;
+
+    let existing_entries = provider.load_all_entries().await?;
+    let mut summary = ImportSummary::default();
+
+    import_into(
+        &mut ProviderTarget(provider),
+        existing_entries,
+        entries_dto.entries,
+        strategy,
+        &mut summary,
+    )
+    .await
+    .map_err(|err| partial_import_error(err, summary))?;
+
+    Ok(summary)
+}
+
+/// Imports the drafts to the given target with the given existing entries, resolving the entries
+/// of days which exist already with the given strategy. The counts of the imported entries are
+/// added to the given summary as they are written.
+pub(crate) async fn import_into<T: ImportTarget>(
+    target: &mut T,
+    mut existing_entries: Vec<Entry>,
+    drafts: Vec<EntryDraft>,
+    strategy: ImportStrategy,
+    summary: &mut ImportSummary,
+) -> Result<(), ModifyEntryError> {
+    for draft in drafts {
+        // Entries are matched by their UUIDs first since their dates could have been changed.
+        let existing_pos = existing_entries
+            .iter()
+            .position(|entry| entry.uuid == draft.uuid)
+            .or_else(|| {
+                existing_entries.iter().position(|entry| {
+                    entry.notebook == draft.notebook
+                        && entry.date.date_naive() == draft.date.date_naive()
+                })
+            });
+        let existing = existing_pos.map(|pos| &mut existing_entries[pos]);
+
+        let existing = match (existing, strategy) {
+            (Some(existing), ImportStrategy::Skip) => {
+                log::trace!("Import: Skipping entry of existing day {}", existing.title);
+                summary.skipped += 1;
+                continue;
+            }
+            (Some(existing), _) => existing,
+            (None, _) => {
+                let entry = target.add(draft).await?;
+                existing_entries.push(entry);
+                summary.created += 1;
+                continue;
+            }
+        };
+
+        let updated = match strategy {
+            ImportStrategy::Replace => Entry {
+                uuid: existing.uuid,
+                created_at: existing.created_at,
+                // Modification time is set by the back-end if the entry has been changed.
+                updated_at: existing.updated_at,
+                ..Entry::from_draft(existing.id, draft)
+            },
+            _ => merge_entry(existing, draft, strategy),
+        };
+
+        if updated == *existing {
+            summary.skipped += 1;
+        } else {
+            *existing = target.update(updated).await?;
+            summary.merged += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// Adds the counts of the entries imported before the failure to the error, since the changes
+/// applied already are kept by the back-end.
+fn partial_import_error(err: ModifyEntryError, summary: ImportSummary) -> anyhow::Error {
+    anyhow::Error::from(err).context(format!(
+        "Import failed after applying {} entries ({summary})",
+        summary.created + summary.merged
+    ))
+}
+
+/// Merges the imported draft into the existing entry with the given strategy, returning the
+/// merged entry.
+fn merge_entry(existing: &Entry, imported: EntryDraft, strategy: ImportStrategy) -> Entry {
+    let mut merged = existing.clone();
+
+    merged.content = match strategy {
+        ImportStrategy::AppendLines => merge_log_lines(&existing.content, &imported.content),
+        _ if imported.content.is_empty() || existing.content.contains(&imported.content) => {
+            existing.content.clone()
+        }
+        _ if existing.content.is_empty() => imported.content,
+        _ => format!("{}\n{}", existing.content, imported.content),
+    };
+
+    for tag in imported.tags {
+        if !merged.tags.contains(&tag) {
+            merged.tags.push(tag);
+        }
+    }
+
+    merged.priority = existing.priority.or(imported.priority);
+
+    merged
+}
+
+/// Merges the lines of the imported content into the existing content, skipping lines which
+/// exist already. Lines starting with a `HH:MM` timestamp are inserted in time order while the
+/// other lines are appended to the end.
+pub fn merge_log_lines(existing: &str, imported: &str) -> String {
+    let mut lines: Vec<&str> = existing.lines().collect();
+
+    for line in imported.lines() {
+        if line.trim().is_empty() || lines.contains(&line) {
+            continue;
+        }
+
+        let insert_pos = line_time(line)
+            .and_then(|time| {
+                lines
+                    .iter()
+                    .position(|existing| line_time(existing).is_some_and(|t| t > time))
+            })
+            .unwrap_or(lines.len());
+
+        lines.insert(insert_pos, line);
+    }
+
+    lines.join("\n")
+}
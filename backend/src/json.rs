@@ -1,31 +1,41 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
 
-use anyhow::{Context, anyhow};
+use anyhow::anyhow;
 
 use super::*;
 
 pub struct JsonDataProvide {
     file_path: PathBuf,
+    /// Count of the rotating backup generations kept for the entries file.
+    backup_count: usize,
+    /// Warning about recovering the entries from a backup while loading them.
+    load_warning: Mutex<Option<String>>,
 }
 
 impl JsonDataProvide {
     pub fn new(file_path: PathBuf) -> Self {
-        Self { file_path }
+        Self {
+            file_path,
+            backup_count: 0,
+            load_warning: Mutex::new(None),
+        }
+    }
+
+    /// Sets the count of the backup generations to keep for the entries file.
+    #[must_use]
+    pub fn with_backup_count(mut self, backup_count: usize) -> Self {
+        self.backup_count = backup_count;
+        self
     }
 }
 
 impl DataProvider for JsonDataProvide {
     async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
-        if !self.file_path.try_exists()? {
-            return Ok(Vec::new());
-        }
-
-        let json_content = tokio::fs::read_to_string(&self.file_path).await?;
-        if json_content.is_empty() {
-            return Ok(Vec::new());
-        }
-        let entries =
-            serde_json::from_str(&json_content).context("Error while parsing entries json data")?;
+        let mut entries = self.load_stored_entries().await?;
+        entries.retain(|entry| entry.deleted_at.is_none());
 
         Ok(entries)
     }
@@ -37,11 +47,14 @@ impl DataProvider for JsonDataProvide {
             ));
         }
 
-        let mut entries = self.load_all_entries().await?;
+        let mut entries = self.load_stored_entries().await?;
 
-        entries.sort_by_key(|e| e.id);
+        ensure_unique_day(&entries, entry.date, &entry.notebook, None)?;
+        entries.retain(|e| e.deleted_at.is_none() || e.uuid != entry.uuid);
+        ensure_unique_uuid(&entries, entry.uuid, None)?;
 
-        let id: u32 = entries.last().map(|entry| entry.id + 1).unwrap_or(0);
+        // Entries in the trash keep their ids to be restored with them.
+        let id: u32 = entries.iter().map(|entry| entry.id + 1).max().unwrap_or(0);
 
         let new_entry = Entry::from_draft(id, entry);
 
@@ -55,10 +68,15 @@ impl DataProvider for JsonDataProvide {
     }
 
     async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
-        let mut entries = self.load_all_entries().await?;
+        let mut entries = self.load_stored_entries().await?;
 
-        if let Some(pos) = entries.iter().position(|e| e.id == entry_id) {
-            entries.remove(pos);
+        if let Some(entry) = entries
+            .iter_mut()
+            .find(|e| e.id == entry_id && e.deleted_at.is_none())
+        {
+            let now = Utc::now();
+            entry.deleted_at = Some(now);
+            entry.updated_at = now;
 
             self.write_entries_to_file(&entries)
                 .await
@@ -68,16 +86,24 @@ impl DataProvider for JsonDataProvide {
         Ok(())
     }
 
-    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+    async fn update_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
         if entry.title.is_empty() {
             return Err(ModifyEntryError::ValidationError(
                 "Entry title can't be empty".into(),
             ));
         }
 
-        let mut entries = self.load_all_entries().await?;
+        let mut entries = self.load_stored_entries().await?;
+
+        ensure_unique_day(&entries, entry.date, &entry.notebook, Some(entry.id))?;
+        ensure_unique_uuid(&entries, entry.uuid, Some(entry.id))?;
 
-        if let Some(entry_to_modify) = entries.iter_mut().find(|e| e.id == entry.id) {
+        if let Some(entry_to_modify) = entries
+            .iter_mut()
+            .find(|e| e.id == entry.id && e.deleted_at.is_none())
+        {
+            entry.updated_at = Utc::now();
+            entry.deleted_at = None;
             *entry_to_modify = entry.clone();
 
             self.write_entries_to_file(&entries)
@@ -92,6 +118,93 @@ impl DataProvider for JsonDataProvide {
         }
     }
 
+    async fn add_existing_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let mut entries = self.load_stored_entries().await?;
+
+        if entry.deleted_at.is_none() {
+            ensure_unique_day(&entries, entry.date, &entry.notebook, None)?;
+        }
+        ensure_unique_uuid(&entries, entry.uuid, None)?;
+
+        if entries.iter().any(|e| e.id == entry.id) {
+            entry.id = entries.iter().map(|e| e.id + 1).max().unwrap_or(0);
+        }
+
+        entries.push(entry.clone());
+
+        self.write_entries_to_file(&entries)
+            .await
+            .map_err(|err| anyhow!(err))?;
+
+        Ok(entry)
+    }
+
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        let mut entries = self.load_stored_entries().await?;
+        entries.retain(|entry| entry.deleted_at.is_some());
+
+        Ok(entries)
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        let mut entries = self.load_stored_entries().await?;
+
+        let Some(pos) = entries
+            .iter()
+            .position(|e| e.id == entry_id && e.deleted_at.is_some())
+        else {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "Entry with id {entry_id} isn't in the trash"
+            )));
+        };
+
+        let entry = &entries[pos];
+        ensure_unique_day(&entries, entry.date, &entry.notebook, Some(entry_id))?;
+
+        let entry = &mut entries[pos];
+        entry.deleted_at = None;
+        entry.updated_at = Utc::now();
+        let entry = entry.clone();
+
+        self.write_entries_to_file(&entries)
+            .await
+            .map_err(|err| anyhow!(err))?;
+
+        Ok(entry)
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let mut entries = self.load_stored_entries().await?;
+
+        if let Some(pos) = entries
+            .iter()
+            .position(|e| e.id == entry_id && e.deleted_at.is_some())
+        {
+            entries.remove(pos);
+
+            self.write_entries_to_file(&entries).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        let mut entries = self.load_stored_entries().await?;
+        let count = entries.len();
+
+        entries.retain(|e| {
+            e.deleted_at
+                .is_none_or(|deleted_at| deleted_at >= deleted_before)
+        });
+
+        let purged = count - entries.len();
+        if purged > 0 {
+            self.write_entries_to_file(&entries).await?;
+        }
+
+        Ok(purged)
+    }
+
     async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
         let entries: Vec<EntryDraft> = self
             .load_all_entries()
@@ -105,29 +218,186 @@ impl DataProvider for JsonDataProvide {
     }
 
     async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
-        let mut entries = self.load_all_entries().await?;
+        let mut entries = self.load_stored_entries().await?;
+        let now = Utc::now();
 
         entries
             .iter_mut()
-            .filter(|entry| entry.priority.is_none())
-            .for_each(|entry| entry.priority = Some(priority));
+            .filter(|entry| entry.priority.is_none() && entry.deleted_at.is_none())
+            .for_each(|entry| {
+                entry.priority = Some(priority);
+                entry.updated_at = now;
+            });
 
         self.write_entries_to_file(&entries).await?;
 
         Ok(())
     }
+
+    async fn find_recovery_backup(&self) -> anyhow::Result<Option<PathBuf>> {
+        if !self.file_path.try_exists()? {
+            return Ok(None);
+        }
+
+        let json_content = tokio::fs::read(&self.file_path).await?;
+        if json_content.is_empty() {
+            return Ok(None);
+        }
+
+        match serde_json::from_slice::<Vec<Entry>>(&json_content) {
+            Err(err) if is_corrupted(&err) => {}
+            _ => return Ok(None),
+        }
+
+        for generation in 1..=self.backup_count {
+            let backup = self.backup_path(generation);
+            if read_entries_file(&backup).await.is_some() {
+                return Ok(Some(backup));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn take_load_warning(&self) -> Option<String> {
+        self.load_warning.lock().unwrap().take()
+    }
 }
 
 impl JsonDataProvide {
+    /// Loads all the stored entries including the ones in the trash.
+    async fn load_stored_entries(&self) -> anyhow::Result<Vec<Entry>> {
+        if !self.file_path.try_exists()? {
+            return Ok(Vec::new());
+        }
+
+        // Content is read as bytes since truncated files can end within multi-byte characters.
+        let json_content = tokio::fs::read(&self.file_path).await?;
+        if json_content.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = match serde_json::from_slice(&json_content) {
+            Ok(entries) => entries,
+            Err(err) if is_corrupted(&err) => self
+                .recover_from_backups()
+                .await?
+                .ok_or_else(|| anyhow!(err).context("Error while parsing entries json data"))?,
+            Err(err) => return Err(invalid_data_error(&json_content, err)),
+        };
+
+        backfill_missing_fields(&mut entries);
+
+        Ok(entries)
+    }
+
     async fn write_entries_to_file(&self, entries: &Vec<Entry>) -> anyhow::Result<()> {
+        self.rotate_backups().await?;
+        self.write_atomic(entries).await
+    }
+
+    /// Writes the entries to a temporary file, syncing it to the disk before renaming it to the
+    /// entries file, to avoid truncating the entries file on crashes.
+    async fn write_atomic(&self, entries: &Vec<Entry>) -> anyhow::Result<()> {
         let entries_text = serde_json::to_vec(&entries)?;
-        if !self.file_path.exists() {
-            if let Some(parent) = self.file_path.parent() {
-                tokio::fs::create_dir_all(parent).await?;
+        write_file_atomic(&self.file_path, &entries_text).await
+    }
+
+    /// Copies the current entries file to the newest backup, shifting the older ones and removing
+    /// the oldest one if the backups count is exceeded.
+    async fn rotate_backups(&self) -> anyhow::Result<()> {
+        if self.backup_count == 0 || !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let oldest = self.backup_path(self.backup_count);
+        if oldest.exists() {
+            tokio::fs::remove_file(&oldest).await?;
+        }
+
+        for generation in (1..self.backup_count).rev() {
+            let backup = self.backup_path(generation);
+            if backup.exists() {
+                tokio::fs::rename(&backup, self.backup_path(generation + 1)).await?;
             }
         }
-        tokio::fs::write(&self.file_path, entries_text).await?;
+
+        tokio::fs::copy(&self.file_path, self.backup_path(1)).await?;
 
         Ok(())
     }
+
+    /// Loads the entries from the newest valid backup, restoring it as the entries file while
+    /// keeping a copy of the corrupted file.
+    /// Returns `None` if no valid backup is found.
+    async fn recover_from_backups(&self) -> anyhow::Result<Option<Vec<Entry>>> {
+        for generation in 1..=self.backup_count {
+            let backup = self.backup_path(generation);
+            let Some(entries) = read_entries_file(&backup).await else {
+                continue;
+            };
+
+            let corrupted_path = self.sibling_path("corrupted");
+            tokio::fs::copy(&self.file_path, &corrupted_path).await?;
+            self.write_atomic(&entries).await?;
+
+            log::warn!(
+                "Entries file couldn't be parsed. Entries are recovered from backup: {}",
+                backup.display()
+            );
+
+            let warning = format!(
+                "Journals file couldn't be parsed and has been restored from the backup '{}'.\nThe corrupted file is kept in '{}'",
+                backup.display(),
+                corrupted_path.display()
+            );
+            *self.load_warning.lock().unwrap() = Some(warning);
+
+            return Ok(Some(entries));
+        }
+
+        Ok(None)
+    }
+
+    fn backup_path(&self, generation: usize) -> PathBuf {
+        self.sibling_path(&format!("{generation}.bak"))
+    }
+
+    /// Gets the path of a file next to the entries file with the given suffix appended to its name.
+    fn sibling_path(&self, suffix: &str) -> PathBuf {
+        let mut file_name = self.file_path.file_name().unwrap_or_default().to_owned();
+        file_name.push(format!(".{suffix}"));
+        self.file_path.with_file_name(file_name)
+    }
+}
+
+/// Checks if the parsing error is caused by a corrupted file, like truncated writes, which can be
+/// recovered from the backups. Valid JSON data which doesn't match the entries, e.g. written by a
+/// newer version of the app, must never be replaced by the backups.
+fn is_corrupted(err: &serde_json::Error) -> bool {
+    matches!(
+        err.classify(),
+        serde_json::error::Category::Syntax | serde_json::error::Category::Eof
+    )
+}
+
+/// Creates the error of valid JSON data which doesn't match the entries, reporting the data of
+/// newer versions explicitly.
+fn invalid_data_error(json_content: &[u8], err: serde_json::Error) -> anyhow::Error {
+    let version = serde_json::from_slice::<serde_json::Value>(json_content)
+        .ok()
+        .and_then(|value| value.get("version")?.as_u64());
+
+    match version {
+        Some(version) if version > u64::from(TRANSFER_DATA_VERSION) => anyhow!(
+            "Journals data version {version} is newer than the supported version {TRANSFER_DATA_VERSION}. Please update the app to load it"
+        ),
+        _ => anyhow!(err).context("Error while parsing entries json data"),
+    }
+}
+
+/// Reads and parses the entries from the given file, returning `None` on failure.
+async fn read_entries_file(path: &Path) -> Option<Vec<Entry>> {
+    let json_content = tokio::fs::read_to_string(path).await.ok()?;
+    serde_json::from_str(&json_content).ok()
 }
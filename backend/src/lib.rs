@@ -1,23 +1,81 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
 use serde::{Deserialize, Serialize};
+pub use uuid::Uuid;
+
+mod git_versioned;
+pub use git_versioned::{GitDataProvide, GitVersion};
+
+mod search;
+pub use search::{MatchSpan, search_entries};
+
+mod transfer;
+
+mod import;
+pub use import::{ImportStrategy, ImportSummary, merge_log_lines};
+
+mod log_line;
+pub use log_line::{EntryLogLine, LogLine, append_log_line, log_lines_in_range, parse_log_lines};
+
+mod convert;
+pub use convert::{ConvertSummary, content_hash, convert_entries};
+
+mod doctor;
+pub use doctor::{EntryIssue, FixSummary, diagnose_entries, fix_entries};
+
+mod foreign_import;
+pub use foreign_import::{ForeignFormat, read_foreign_entries};
+
+mod sync;
+pub use sync::{
+    SyncAction, SyncBase, SyncConflict, SyncPlan, SyncSide, SyncSummary, apply_sync_plan,
+    merge_three_way, plan_sync,
+};
 
 #[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "json")]
 pub use json::JsonDataProvide;
 
+#[cfg(feature = "markdown_dir")]
+mod markdown_dir;
+#[cfg(feature = "markdown_dir")]
+pub use markdown_dir::MarkdownDirDataProvide;
+
 #[cfg(feature = "sqlite")]
 mod sqlite;
 #[cfg(feature = "sqlite")]
-pub use sqlite::SqliteDataProvide;
+pub use sqlite::{SqliteDataProvide, SqliteDurability};
 
-pub const TRANSFER_DATA_VERSION: u16 = 100;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::{HttpDataProvide, serve};
+
+pub const TRANSFER_DATA_VERSION: u16 = 103;
+
+/// Name of the notebook containing the entries which haven't been assigned to another notebook.
+pub const DEFAULT_NOTEBOOK: &str = "default";
+
+/// Gets the name of the default notebook, used for the data stored before notebooks have been
+/// introduced.
+pub fn default_notebook() -> String {
+    String::from(DEFAULT_NOTEBOOK)
+}
+
+/// Gets the title of the entry with the given date in the format `YYYY_MM_DD_Day`, which is the
+/// title of the journals of each day.
+pub fn day_title(date: DateTime<Utc>) -> String {
+    date.format("%Y_%m_%d_%A").to_string()
+}
 
 #[derive(Debug, thiserror::Error)]
 pub enum ModifyEntryError {
     #[error("{0}")]
     ValidationError(String),
+    /// Each day can have one entry only in each notebook.
+    #[error("A journal for the day {day} exists already")]
+    DuplicateDay { day: NaiveDate, existing_id: u32 },
     #[error("{0}")]
     DataError(#[from] anyhow::Error),
 }
@@ -25,30 +83,268 @@ pub enum ModifyEntryError {
 // The warning can be suppressed since this will be used with the code base of this app only
 #[allow(async_fn_in_trait)]
 pub trait DataProvider {
+    /// Loads all the entries apart from the ones in the trash.
     async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>>;
+    /// Adds the entry, replacing the entries in the trash with the same UUID.
     async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError>;
+    /// Moves the entry to the trash, from which it can be restored until it's purged.
     async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()>;
     async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError>;
+    /// Adds the entry of another back-end, keeping its id, timestamps and trash state where the
+    /// back-end allows it. A new id is assigned if the id of the entry is taken already.
+    async fn add_existing_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let deleted = entry.deleted_at.is_some();
+        let added = self.add_entry(EntryDraft::from_entry(entry)).await?;
+
+        if deleted {
+            self.remove_entry(added.id).await?;
+        }
+
+        Ok(added)
+    }
+    /// Loads the entries in the trash.
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>>;
+    /// Restores the entry with the given id from the trash. Fails if another entry has been
+    /// added on the same day meanwhile.
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError>;
+    /// Deletes the entry with the given id from the trash permanently.
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()>;
+    /// Deletes the entries moved to the trash before the given time permanently, returning their
+    /// count.
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        let mut count = 0;
+        for entry in self.load_trash().await? {
+            if entry
+                .deleted_at
+                .is_some_and(|deleted_at| deleted_at < deleted_before)
+            {
+                self.purge_entry(entry.id).await?;
+                count += 1;
+            }
+        }
+
+        Ok(count)
+    }
     async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO>;
+    /// Imports the entries, skipping the ones with UUIDs which exist already.
     async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
-        debug_assert_eq!(
-            TRANSFER_DATA_VERSION, entries_dto.version,
-            "Version mismatches check if there is a need to do a converting to the data"
-        );
+        entries_dto.ensure_current_version()?;
+
+        let existing_uuids: Vec<Uuid> = self
+            .load_all_entries()
+            .await?
+            .into_iter()
+            .map(|entry| entry.uuid)
+            .collect();
 
         for entry_draft in entries_dto.entries {
+            if existing_uuids.contains(&entry_draft.uuid) {
+                log::trace!("Import: Skipping existing entry {}", entry_draft.title);
+                continue;
+            }
+
             self.add_entry(entry_draft).await?;
         }
 
         Ok(())
     }
+    /// Imports the entries, resolving the entries which exist already, by UUID or by day, with the
+    /// given strategy.
+    async fn import_entries_with_strategy(
+        &self,
+        entries_dto: EntriesDTO,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
+        import::import_with_strategy(self, entries_dto, strategy).await
+    }
     /// Assigns priority to all entries that don't have a priority assigned to
     async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()>;
+    /// Searches the content of the entries for the words of the given query, returning the ids
+    /// of the matching entries with the spans of the matches, best matches first.
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        let entries = self.load_all_entries().await?;
+        search_entries(&entries, query)
+    }
+    /// Appends the log line to the content of the entry with the given id, returning the updated
+    /// entry.
+    async fn append_log_line(
+        &self,
+        entry_id: u32,
+        line: LogLine,
+    ) -> Result<Entry, ModifyEntryError> {
+        let mut entry = self
+            .load_all_entries()
+            .await?
+            .into_iter()
+            .find(|entry| entry.id == entry_id)
+            .ok_or_else(|| anyhow::anyhow!("Entry with the id {entry_id} doesn't exist"))?;
+
+        entry.content = append_log_line(&entry.content, &line);
+
+        self.update_entry(entry).await
+    }
+    /// Gets the log lines of all entries with times within the given range, with the start
+    /// included and the end excluded, ordered by time.
+    async fn query_log_lines(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> anyhow::Result<Vec<EntryLogLine>> {
+        let entries = self.load_all_entries().await?;
+        Ok(log_lines_in_range(&entries, start, end))
+    }
+    /// Loads the stored tags which refer to non-existing entries as pairs of the entry id and
+    /// the tag. Back-ends storing the tags within the entries can't have such tags.
+    async fn load_orphaned_tags(&self) -> anyhow::Result<Vec<(u32, String)>> {
+        Ok(Vec::new())
+    }
+    /// Removes the stored tags which refer to non-existing entries, returning their count.
+    async fn remove_orphaned_tags(&self) -> anyhow::Result<usize> {
+        Ok(0)
+    }
+    /// Writes the changes kept outside the back-end files, like the write-ahead log of SQLite,
+    /// into the files themselves to make them complete on their own.
+    async fn flush_to_files(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// Checks, without changing any files, if the stored entries are corrupted while one of their
+    /// backups is valid, returning the path of that backup. Loading the entries would recover
+    /// them from it.
+    async fn find_recovery_backup(&self) -> anyhow::Result<Option<std::path::PathBuf>> {
+        Ok(None)
+    }
+    /// Takes the warning raised while loading the entries if any (e.g. entries have been
+    /// recovered from a backup).
+    fn take_load_warning(&self) -> Option<String> {
+        None
+    }
+}
+
+/// Writes the contents to a temporary file next to the given path, syncing it to the disk before
+/// renaming it to the given path, to avoid leaving truncated files behind on crashes.
+#[cfg(any(feature = "json", feature = "markdown_dir"))]
+pub(crate) async fn write_file_atomic(
+    path: &std::path::Path,
+    contents: &[u8],
+) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    if let Some(parent) = path.parent() {
+        if !parent.exists() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+    }
+
+    let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+
+    let mut temp_file = tokio::fs::File::create(&temp_path).await?;
+    temp_file.write_all(contents).await?;
+    temp_file.sync_all().await?;
+    drop(temp_file);
+
+    tokio::fs::rename(&temp_path, path).await?;
+
+    // Sync the directory to persist the rename on the platforms supporting it.
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        tokio::fs::File::open(parent).await?.sync_all().await?;
+    }
+
+    Ok(())
+}
+
+/// Makes sure none of the given entries has the UUID of the given entry, apart from the one with
+/// the ignored id.
+#[cfg(any(feature = "json", feature = "markdown_dir"))]
+pub(crate) fn ensure_unique_uuid(
+    entries: &[Entry],
+    uuid: Uuid,
+    ignore_id: Option<u32>,
+) -> Result<(), ModifyEntryError> {
+    if entries
+        .iter()
+        .any(|e| Some(e.id) != ignore_id && e.uuid == uuid)
+    {
+        return Err(ModifyEntryError::ValidationError(format!(
+            "An entry with the UUID {uuid} exists already"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Fills the fields of the entries stored before these fields have been introduced, deriving
+/// their UUIDs from their ids and dates and using the entries dates as creation and modification
+/// times.
+/// The fields are filled in memory only, keeping the stored files untouched until the entries are
+/// written again. The derived UUIDs stay the same on each load until then.
+#[cfg(any(feature = "json", feature = "markdown_dir"))]
+pub(crate) fn backfill_missing_fields(entries: &mut [Entry]) {
+    for entry in entries.iter_mut() {
+        if entry.uuid.is_nil() {
+            entry.uuid = derive_uuid(entry.id, entry.date);
+        }
+
+        if entry.created_at == DateTime::<Utc>::default() {
+            entry.created_at = entry.date;
+        }
+
+        if entry.updated_at == DateTime::<Utc>::default() {
+            entry.updated_at = entry.created_at;
+        }
+    }
+}
+
+/// Derives a UUID for data stored before UUIDs have been introduced from the SHA-256 hash of the
+/// given id and date, giving the same UUID each time the same data is loaded.
+pub(crate) fn derive_uuid(id: u32, date: DateTime<Utc>) -> Uuid {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(id.to_le_bytes());
+    hasher.update(date.to_rfc3339().as_bytes());
+    let hash = hasher.finalize();
+
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&hash[..16]);
+    uuid::Builder::from_custom_bytes(bytes).into_uuid()
+}
+
+/// Makes sure none of the given entries in the given notebook, apart from the one with the
+/// ignored id and the ones in the trash, has the same day as the given date.
+#[cfg(any(feature = "json", feature = "markdown_dir"))]
+pub(crate) fn ensure_unique_day(
+    entries: &[Entry],
+    date: DateTime<Utc>,
+    notebook: &str,
+    ignore_id: Option<u32>,
+) -> Result<(), ModifyEntryError> {
+    let day = date.date_naive();
+
+    match entries.iter().find(|e| {
+        Some(e.id) != ignore_id
+            && e.deleted_at.is_none()
+            && e.notebook == notebook
+            && e.date.date_naive() == day
+    }) {
+        Some(existing) => Err(ModifyEntryError::DuplicateDay {
+            day,
+            existing_id: existing.id,
+        }),
+        None => Ok(()),
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Entry {
     pub id: u32,
+    /// Persistent identifier of the entry, which stays the same across back-ends and transfers.
+    /// Entries stored before it has been introduced get the nil UUID on loading until they are
+    /// given one.
+    #[serde(default)]
+    pub uuid: Uuid,
     pub date: DateTime<Utc>,
     pub title: String,
     pub content: String,
@@ -56,9 +352,25 @@ pub struct Entry {
     pub tags: Vec<String>,
     #[serde(default)]
     pub priority: Option<u32>,
+    /// Notebook the entry belongs to. Entries stored before notebooks have been introduced
+    /// belong to the default notebook.
+    #[serde(default = "default_notebook")]
+    pub notebook: String,
+    /// Time the entry has been created. Entries stored before it has been introduced get the
+    /// default time on loading until it's set to their date.
+    #[serde(default)]
+    pub created_at: DateTime<Utc>,
+    /// Time of the last modification on the entry, maintained by the back-ends.
+    #[serde(default)]
+    pub updated_at: DateTime<Utc>,
+    /// Time the entry has been moved to the trash, if it's in the trash.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Entry {
+    /// Creates an entry with the nil UUID, which the back-ends replace with a generated one.
+    /// Creation and modification times are set to the date of the entry.
     #[allow(dead_code)]
     pub fn new(
         id: u32,
@@ -70,33 +382,48 @@ impl Entry {
     ) -> Self {
         Self {
             id,
+            uuid: Uuid::nil(),
             date,
             title,
             content,
             tags,
             priority,
+            notebook: default_notebook(),
+            created_at: date,
+            updated_at: date,
+            deleted_at: None,
         }
     }
 
     pub fn from_draft(id: u32, draft: EntryDraft) -> Self {
         Self {
             id,
+            uuid: draft.uuid,
             date: draft.date,
             title: draft.title,
             content: draft.content,
             tags: draft.tags,
             priority: draft.priority,
+            notebook: draft.notebook,
+            created_at: draft.created_at,
+            updated_at: draft.updated_at,
+            deleted_at: None,
         }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct EntryDraft {
+    pub uuid: Uuid,
     pub date: DateTime<Utc>,
     pub title: String,
     pub content: String,
     pub tags: Vec<String>,
     pub priority: Option<u32>,
+    #[serde(default = "default_notebook")]
+    pub notebook: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 impl EntryDraft {
@@ -107,12 +434,17 @@ impl EntryDraft {
         priority: Option<u32>,
     ) -> Self {
         let content = String::new();
+        let now = Utc::now();
         Self {
+            uuid: Uuid::new_v4(),
             date,
             title,
             content,
             tags,
             priority,
+            notebook: default_notebook(),
+            created_at: now,
+            updated_at: now,
         }
     }
 
@@ -122,13 +454,23 @@ impl EntryDraft {
         self
     }
 
+    #[must_use]
+    pub fn with_notebook(mut self, notebook: String) -> Self {
+        self.notebook = notebook;
+        self
+    }
+
     pub fn from_entry(entry: Entry) -> Self {
         Self {
+            uuid: entry.uuid,
             date: entry.date,
             title: entry.title,
             content: entry.content,
             tags: entry.tags,
             priority: entry.priority,
+            notebook: entry.notebook,
+            created_at: entry.created_at,
+            updated_at: entry.updated_at,
         }
     }
 }
@@ -0,0 +1,151 @@
+use std::fmt::Display;
+
+use chrono::{NaiveDateTime, NaiveTime, Timelike};
+
+use super::*;
+
+/// Format of the timestamps at the start of the log lines.
+pub(crate) const LOG_TIME_FORMAT: &str = "%H:%M";
+
+/// Prefix marking the tags in the text of log lines.
+const TAG_PREFIX: char = '#';
+
+/// Timestamped line in the content of an entry in the form `HH:MM text`.
+/// Tags are the words in the text starting with `#`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLine {
+    pub time: NaiveTime,
+    pub text: String,
+    pub tags: Vec<String>,
+}
+
+impl LogLine {
+    /// Creates a log line with the given text, taking the tags from it. The time is truncated
+    /// to minutes since the lines have no seconds.
+    pub fn new(time: NaiveTime, text: String) -> Self {
+        let time = NaiveTime::from_hms_opt(time.hour(), time.minute(), 0).unwrap_or(time);
+        let tags = parse_tags(&text);
+        Self { time, text, tags }
+    }
+
+    /// Parses the log line from a line of the content, returning `None` if the line doesn't
+    /// start with a `HH:MM` timestamp.
+    pub fn parse(line: &str) -> Option<Self> {
+        let (time_txt, text) = line.split_once(' ').unwrap_or((line, ""));
+        let time = NaiveTime::parse_from_str(time_txt, LOG_TIME_FORMAT).ok()?;
+
+        Some(Self::new(time, text.to_owned()))
+    }
+}
+
+impl Display for LogLine {
+    /// Renders the log line as a line of the content, appending the tags which aren't in the
+    /// text already.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.time.format(LOG_TIME_FORMAT))?;
+
+        if !self.text.is_empty() {
+            write!(f, " {}", self.text)?;
+        }
+
+        let text_tags = parse_tags(&self.text);
+        for tag in self.tags.iter().filter(|tag| !text_tags.contains(tag)) {
+            write!(f, " {TAG_PREFIX}{tag}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Log line of an entry, used in the results of the queries over the log lines of all entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EntryLogLine {
+    pub entry_id: u32,
+    /// Day of the entry the line belongs to.
+    pub day: NaiveDate,
+    pub line: LogLine,
+}
+
+impl EntryLogLine {
+    pub fn new(entry_id: u32, day: NaiveDate, line: LogLine) -> Self {
+        Self {
+            entry_id,
+            day,
+            line,
+        }
+    }
+
+    /// Gets the time of the line combined with the day of its entry.
+    pub fn date_time(&self) -> NaiveDateTime {
+        self.day.and_time(self.line.time)
+    }
+}
+
+/// Parses the log lines in the given content, skipping the lines without timestamps.
+pub fn parse_log_lines(content: &str) -> Vec<LogLine> {
+    content.lines().filter_map(LogLine::parse).collect()
+}
+
+/// Appends the log line to the end of the given content.
+pub fn append_log_line(content: &str, line: &LogLine) -> String {
+    let content = content.trim_end_matches('\n');
+    if content.is_empty() {
+        line.to_string()
+    } else {
+        format!("{content}\n{line}")
+    }
+}
+
+/// Gets the log lines of the given entries with times within the given range, with the start
+/// included and the end excluded, ordered by time.
+pub fn log_lines_in_range(
+    entries: &[Entry],
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<EntryLogLine> {
+    let mut lines: Vec<EntryLogLine> = entries
+        .iter()
+        .flat_map(|entry| {
+            let day = entry.date.date_naive();
+            parse_log_lines(&entry.content)
+                .into_iter()
+                .map(move |line| EntryLogLine::new(entry.id, day, line))
+        })
+        .filter(|line| (start..end).contains(&line.date_time()))
+        .collect();
+
+    // Sorting is stable, keeping the lines with the same time in their order in the content.
+    lines.sort_by_key(EntryLogLine::date_time);
+
+    lines
+}
+
+/// Gets the time of the line if it starts with a `HH:MM` timestamp.
+pub(crate) fn line_time(line: &str) -> Option<NaiveTime> {
+    LogLine::parse(line).map(|line| line.time)
+}
+
+/// Gets the tags in the text, which are the words starting with `#`, without duplicates.
+pub(crate) fn parse_tags(text: &str) -> Vec<String> {
+    parse_prefixed_tags(text, TAG_PREFIX)
+}
+
+/// Gets the tags in the text marked with the given prefix, without duplicates.
+pub(crate) fn parse_prefixed_tags(text: &str, prefix: char) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+
+    for word in text.split_whitespace() {
+        let Some(tag) = word.strip_prefix(prefix) else {
+            continue;
+        };
+
+        let tag = tag.trim_end_matches(|c: char| !c.is_alphanumeric());
+        if tag.is_empty() || tag.starts_with(prefix) || tags.iter().any(|t| t == tag) {
+            continue;
+        }
+
+        tags.push(tag.to_owned());
+    }
+
+    tags
+}
@@ -0,0 +1,628 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use anyhow::{Context, anyhow, bail};
+
+use super::*;
+
+const FILE_EXTENSION: &str = "md";
+const FRONT_MATTER_DELIMITER: &str = "+++";
+/// Name of the sub-directory containing the files of the entries in the trash of each notebook.
+/// Its files are prefixed with the entries ids since the titles of the deleted entries can be used
+/// again.
+const TRASH_DIR_NAME: &str = ".trash";
+
+/// Data provider storing each entry in its own markdown file inside a directory.
+/// The files are named after the entries titles and start with a TOML front-matter block
+/// containing the rest of the entry attributes.
+/// The entries of the default notebook are stored in the directory itself while the entries of
+/// the other notebooks are stored in sub-directories named after them.
+pub struct MarkdownDirDataProvide {
+    dir_path: PathBuf,
+    /// Markdown files without front-matter skipped on the last load, which are reported once.
+    skipped_files: Mutex<Vec<PathBuf>>,
+    /// Warning about the skipped files while loading the entries.
+    load_warning: Mutex<Option<String>>,
+}
+
+/// Entry attributes persisted in the front-matter block of each file.
+#[derive(Debug, Serialize, Deserialize)]
+struct FrontMatter {
+    /// Files created outside the app may miss the id, which is assigned on load then.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    id: Option<u32>,
+    #[serde(default)]
+    uuid: Uuid,
+    date: DateTime<Utc>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    priority: Option<u32>,
+    #[serde(default)]
+    created_at: DateTime<Utc>,
+    #[serde(default)]
+    updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl MarkdownDirDataProvide {
+    pub fn new(dir_path: PathBuf) -> Self {
+        Self {
+            dir_path,
+            skipped_files: Mutex::new(Vec::new()),
+            load_warning: Mutex::new(None),
+        }
+    }
+}
+
+impl DataProvider for MarkdownDirDataProvide {
+    async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        let mut missing_id_files = Vec::new();
+        let mut skipped_files = Vec::new();
+        for (notebook, notebook_dir) in self.notebook_dirs().await? {
+            let mut dir = tokio::fs::read_dir(&notebook_dir).await?;
+            while let Some(dir_entry) = dir.next_entry().await? {
+                let path = dir_entry.path();
+                if !is_entry_file(&path) {
+                    continue;
+                }
+
+                let Some(entry_file) = read_entry_file(&path, &notebook).await? else {
+                    log::warn!(
+                        "Markdown file without front-matter is skipped: {}",
+                        path.display()
+                    );
+                    skipped_files.push(path);
+                    continue;
+                };
+
+                if entry_file.missing_id {
+                    missing_id_files.push((path, entry_file.entry));
+                } else {
+                    entries.push(entry_file.entry);
+                }
+            }
+        }
+
+        if !missing_id_files.is_empty() {
+            let new_entries = self.assign_missing_ids(&entries, missing_id_files).await?;
+            entries.extend(new_entries);
+        }
+
+        entries.sort_by_key(|e| e.id);
+        backfill_missing_fields(&mut entries);
+        self.report_skipped_files(skipped_files);
+
+        Ok(entries)
+    }
+
+    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        validate_title(&entry.title)?;
+        validate_notebook(&entry.notebook)?;
+
+        let entries = self.load_all_entries().await?;
+
+        if entries
+            .iter()
+            .any(|e| e.notebook == entry.notebook && e.title == entry.title)
+        {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "An entry with the title '{}' already exists",
+                entry.title
+            )));
+        }
+
+        ensure_unique_day(&entries, entry.date, &entry.notebook, None)?;
+        ensure_unique_uuid(&entries, entry.uuid, None)?;
+
+        let trash = self.load_trash().await?;
+        for trashed in trash.iter().filter(|e| e.uuid == entry.uuid) {
+            tokio::fs::remove_file(self.trash_file_path(trashed))
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+
+        // Entries in the trash keep their ids to be restored with them.
+        let id: u32 = entries
+            .iter()
+            .chain(trash.iter())
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(0);
+
+        let new_entry = Entry::from_draft(id, entry);
+
+        self.write_entry_file(&new_entry, None).await?;
+
+        Ok(new_entry)
+    }
+
+    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let entries = self.load_all_entries().await?;
+
+        if let Some(mut entry) = entries.into_iter().find(|e| e.id == entry_id) {
+            let now = Utc::now();
+            entry.deleted_at = Some(now);
+            entry.updated_at = now;
+
+            write_entry_to(&self.trash_file_path(&entry), &entry).await?;
+            tokio::fs::remove_file(self.entry_file_path(&entry)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn update_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
+        validate_title(&entry.title)?;
+        validate_notebook(&entry.notebook)?;
+
+        let entries = self.load_all_entries().await?;
+
+        if entries
+            .iter()
+            .any(|e| e.id != entry.id && e.notebook == entry.notebook && e.title == entry.title)
+        {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "An entry with the title '{}' already exists",
+                entry.title
+            )));
+        }
+
+        ensure_unique_day(&entries, entry.date, &entry.notebook, Some(entry.id))?;
+        ensure_unique_uuid(&entries, entry.uuid, Some(entry.id))?;
+
+        let Some(old_entry) = entries.iter().find(|e| e.id == entry.id) else {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "Entry with id {} doesn't exist",
+                entry.id
+            )));
+        };
+
+        entry.updated_at = Utc::now();
+
+        self.write_entry_file(&entry, Some(old_entry)).await?;
+
+        // Title or notebook changes means the file has been renamed or moved.
+        let old_path = self.entry_file_path(old_entry);
+        if old_path != self.entry_file_path(&entry) {
+            tokio::fs::remove_file(old_path)
+                .await
+                .map_err(|err| anyhow!(err))?;
+        }
+
+        Ok(entry)
+    }
+
+    async fn add_existing_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
+        validate_title(&entry.title)?;
+        validate_notebook(&entry.notebook)?;
+
+        let entries = self.load_all_entries().await?;
+        let trash = self.load_trash().await?;
+
+        if entry.deleted_at.is_none() {
+            if entries
+                .iter()
+                .any(|e| e.notebook == entry.notebook && e.title == entry.title)
+            {
+                return Err(ModifyEntryError::ValidationError(format!(
+                    "An entry with the title '{}' already exists",
+                    entry.title
+                )));
+            }
+
+            ensure_unique_day(&entries, entry.date, &entry.notebook, None)?;
+        }
+        ensure_unique_uuid(&entries, entry.uuid, None)?;
+        ensure_unique_uuid(&trash, entry.uuid, None)?;
+
+        if entries.iter().chain(trash.iter()).any(|e| e.id == entry.id) {
+            entry.id = entries
+                .iter()
+                .chain(trash.iter())
+                .map(|e| e.id + 1)
+                .max()
+                .unwrap_or(0);
+        }
+
+        if entry.deleted_at.is_some() {
+            write_entry_to(&self.trash_file_path(&entry), &entry).await?;
+        } else {
+            self.write_entry_file(&entry, None).await?;
+        }
+
+        Ok(entry)
+    }
+
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        let mut entries = Vec::new();
+        for (notebook, notebook_dir) in self.notebook_dirs().await? {
+            let trash_dir = notebook_dir.join(TRASH_DIR_NAME);
+            if !trash_dir.try_exists()? {
+                continue;
+            }
+
+            let mut dir = tokio::fs::read_dir(&trash_dir).await?;
+            while let Some(dir_entry) = dir.next_entry().await? {
+                let path = dir_entry.path();
+                if !is_entry_file(&path) {
+                    continue;
+                }
+
+                let Some(EntryFile {
+                    mut entry,
+                    missing_id,
+                }) = read_entry_file(&path, &notebook).await?
+                else {
+                    log::warn!(
+                        "Markdown file without front-matter is skipped in the trash: {}",
+                        path.display()
+                    );
+                    continue;
+                };
+
+                // Entries are moved to the trash by the app only, which always writes their ids.
+                if missing_id {
+                    log::warn!(
+                        "Markdown file without id is skipped in the trash: {}",
+                        path.display()
+                    );
+                    continue;
+                }
+
+                if let Some(title) = entry.title.strip_prefix(&format!("{}_", entry.id)) {
+                    entry.title = title.to_owned();
+                }
+
+                entries.push(entry);
+            }
+        }
+
+        entries.sort_by_key(|e| e.id);
+        backfill_missing_fields(&mut entries);
+
+        Ok(entries)
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        let Some(mut entry) = self
+            .load_trash()
+            .await?
+            .into_iter()
+            .find(|e| e.id == entry_id)
+        else {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "Entry with id {entry_id} isn't in the trash"
+            )));
+        };
+
+        let entries = self.load_all_entries().await?;
+
+        if entries
+            .iter()
+            .any(|e| e.notebook == entry.notebook && e.title == entry.title)
+        {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "An entry with the title '{}' already exists",
+                entry.title
+            )));
+        }
+
+        ensure_unique_day(&entries, entry.date, &entry.notebook, None)?;
+
+        let trash_path = self.trash_file_path(&entry);
+
+        entry.deleted_at = None;
+        entry.updated_at = Utc::now();
+
+        self.write_entry_file(&entry, None).await?;
+        tokio::fs::remove_file(trash_path)
+            .await
+            .map_err(|err| anyhow!(err))?;
+
+        Ok(entry)
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let trash = self.load_trash().await?;
+
+        if let Some(entry) = trash.iter().find(|e| e.id == entry_id) {
+            tokio::fs::remove_file(self.trash_file_path(entry)).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
+        let entries: Vec<EntryDraft> = self
+            .load_all_entries()
+            .await?
+            .into_iter()
+            .filter(|entry| entries_ids.contains(&entry.id))
+            .map(EntryDraft::from_entry)
+            .collect();
+
+        Ok(EntriesDTO::new(entries))
+    }
+
+    async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
+        let entries = self.load_all_entries().await?;
+
+        for mut entry in entries.into_iter().filter(|e| e.priority.is_none()) {
+            entry.priority = Some(priority);
+            entry.updated_at = Utc::now();
+            self.write_entry_file(&entry, Some(&entry)).await?;
+        }
+
+        Ok(())
+    }
+
+    fn take_load_warning(&self) -> Option<String> {
+        self.load_warning.lock().unwrap().take()
+    }
+}
+
+impl MarkdownDirDataProvide {
+    fn notebook_dir_path(&self, notebook: &str) -> PathBuf {
+        if notebook == DEFAULT_NOTEBOOK {
+            self.dir_path.clone()
+        } else {
+            self.dir_path.join(notebook)
+        }
+    }
+
+    fn entry_file_path(&self, entry: &Entry) -> PathBuf {
+        self.notebook_dir_path(&entry.notebook)
+            .join(format!("{}.{FILE_EXTENSION}", entry.title))
+    }
+
+    fn trash_file_path(&self, entry: &Entry) -> PathBuf {
+        self.notebook_dir_path(&entry.notebook)
+            .join(TRASH_DIR_NAME)
+            .join(format!("{}_{}.{FILE_EXTENSION}", entry.id, entry.title))
+    }
+
+    /// Writes the entry to the file named after its title. Existing files are overwritten only if
+    /// they belong to the given stored version of the entry, since other files could be markdown
+    /// files which aren't journals.
+    async fn write_entry_file(
+        &self,
+        entry: &Entry,
+        stored_entry: Option<&Entry>,
+    ) -> Result<(), ModifyEntryError> {
+        let path = self.entry_file_path(entry);
+
+        let is_stored_file =
+            stored_entry.is_some_and(|stored| self.entry_file_path(stored) == path);
+        if !is_stored_file && path.try_exists().map_err(|err| anyhow!(err))? {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "The file '{}' exists already and can't be used for the entry '{}'",
+                path.display(),
+                entry.title
+            )));
+        }
+
+        write_entry_to(&path, entry).await?;
+
+        Ok(())
+    }
+
+    /// Assigns the next free ids to the entries read from files without ids, writing the ids to
+    /// the files to keep them on the next loads. The ids of the entries in the trash are skipped
+    /// since they are restored with them.
+    async fn assign_missing_ids(
+        &self,
+        entries: &[Entry],
+        mut missing_id_files: Vec<(PathBuf, Entry)>,
+    ) -> anyhow::Result<Vec<Entry>> {
+        let trash = self.load_trash().await?;
+        let next_id = entries
+            .iter()
+            .chain(trash.iter())
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(0);
+
+        // Sort the files to assign the same ids regardless of the order of the directory listing.
+        missing_id_files.sort_by(|(path1, _), (path2, _)| path1.cmp(path2));
+
+        let mut new_entries = Vec::with_capacity(missing_id_files.len());
+        for ((path, mut entry), id) in missing_id_files.into_iter().zip(next_id..) {
+            entry.id = id;
+
+            backfill_missing_fields(std::slice::from_mut(&mut entry));
+            write_entry_to(&path, &entry).await?;
+
+            new_entries.push(entry);
+        }
+
+        Ok(new_entries)
+    }
+
+    /// Gets the notebooks with their directories, which are the entries directory for the default
+    /// notebook and its sub-directories for the others. Hidden sub-directories are ignored.
+    async fn notebook_dirs(&self) -> anyhow::Result<Vec<(String, PathBuf)>> {
+        if !self.dir_path.try_exists()? {
+            return Ok(Vec::new());
+        }
+
+        let mut notebook_dirs = vec![(default_notebook(), self.dir_path.clone())];
+        let mut dir = tokio::fs::read_dir(&self.dir_path).await?;
+        while let Some(dir_entry) = dir.next_entry().await? {
+            let path = dir_entry.path();
+            let notebook = dir_entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && !notebook.starts_with('.') && notebook != DEFAULT_NOTEBOOK {
+                notebook_dirs.push((notebook, path));
+            }
+        }
+
+        Ok(notebook_dirs)
+    }
+
+    /// Sets the load warning if the skipped files differ from the ones of the previous load, to
+    /// avoid repeating the warning on each load.
+    fn report_skipped_files(&self, mut skipped_files: Vec<PathBuf>) {
+        skipped_files.sort();
+
+        let mut reported = self.skipped_files.lock().unwrap();
+        if *reported == skipped_files {
+            return;
+        }
+
+        if !skipped_files.is_empty() {
+            let files: Vec<String> = skipped_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            let warning = format!(
+                "Markdown files without front-matter have been skipped since they aren't journals:\n{}",
+                files.join("\n")
+            );
+            *self.load_warning.lock().unwrap() = Some(warning);
+        }
+
+        *reported = skipped_files;
+    }
+}
+
+/// Writes the entry atomically to the file with the given path, creating its parent directory if
+/// needed.
+async fn write_entry_to(path: &Path, entry: &Entry) -> anyhow::Result<()> {
+    let front_matter = FrontMatter {
+        id: Some(entry.id),
+        uuid: entry.uuid,
+        date: entry.date,
+        tags: entry.tags.clone(),
+        priority: entry.priority,
+        created_at: entry.created_at,
+        updated_at: entry.updated_at,
+        deleted_at: entry.deleted_at,
+    };
+
+    let front_matter_text = toml::to_string(&front_matter)?;
+
+    let file_text = format!(
+        "{FRONT_MATTER_DELIMITER}\n{front_matter_text}{FRONT_MATTER_DELIMITER}\n{}",
+        entry.content
+    );
+
+    write_file_atomic(path, file_text.as_bytes()).await
+}
+
+fn is_entry_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(FILE_EXTENSION))
+}
+
+/// Titles are used as file names and must be valid as such.
+fn validate_title(title: &str) -> Result<(), ModifyEntryError> {
+    if title.is_empty() {
+        return Err(ModifyEntryError::ValidationError(
+            "Entry title can't be empty".into(),
+        ));
+    }
+
+    if title.contains(['/', '\\']) || title.starts_with('.') {
+        return Err(ModifyEntryError::ValidationError(format!(
+            "Entry title '{title}' can't be used as a file name"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Notebooks other than the default one are used as directory names and must be valid as such.
+fn validate_notebook(notebook: &str) -> Result<(), ModifyEntryError> {
+    if notebook.is_empty() {
+        return Err(ModifyEntryError::ValidationError(
+            "Notebook name can't be empty".into(),
+        ));
+    }
+
+    if notebook.contains(['/', '\\']) || notebook.starts_with('.') {
+        return Err(ModifyEntryError::ValidationError(format!(
+            "Notebook name '{notebook}' can't be used as a directory name"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Entry read from its file.
+struct EntryFile {
+    entry: Entry,
+    /// Whether the front-matter misses the id, which must be assigned then.
+    missing_id: bool,
+}
+
+/// Reads the entry of the given notebook from the given file, returning `None` if the file doesn't
+/// start with a front-matter block, like other markdown files which aren't journals.
+async fn read_entry_file(path: &Path, notebook: &str) -> anyhow::Result<Option<EntryFile>> {
+    let title = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .context("Entry file must have a name")?;
+
+    let file_text = tokio::fs::read_to_string(path).await?;
+
+    let Some((front_matter, content)) = split_front_matter(&file_text)
+        .with_context(|| format!("Invalid entry file: {}", path.display()))?
+    else {
+        return Ok(None);
+    };
+
+    let front_matter: FrontMatter = toml::from_str(front_matter).with_context(|| {
+        format!(
+            "Error while parsing front-matter of entry file: {}",
+            path.display()
+        )
+    })?;
+
+    let entry = Entry {
+        id: front_matter.id.unwrap_or_default(),
+        uuid: front_matter.uuid,
+        date: front_matter.date,
+        title,
+        content: content.to_owned(),
+        tags: front_matter.tags,
+        priority: front_matter.priority,
+        notebook: notebook.to_owned(),
+        created_at: front_matter.created_at,
+        updated_at: front_matter.updated_at,
+        deleted_at: front_matter.deleted_at,
+    };
+
+    Ok(Some(EntryFile {
+        entry,
+        missing_id: front_matter.id.is_none(),
+    }))
+}
+
+/// Splits the file text into front-matter and content parts, accepting both LF and CRLF line
+/// endings. Returns `None` if the text doesn't start with a front-matter block.
+fn split_front_matter(text: &str) -> anyhow::Result<Option<(&str, &str)>> {
+    let Some(rest) = text.strip_prefix(FRONT_MATTER_DELIMITER).and_then(|rest| {
+        rest.strip_prefix("\r\n")
+            .or_else(|| rest.strip_prefix('\n'))
+    }) else {
+        return Ok(None);
+    };
+
+    let mut line_start = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end_matches(['\r', '\n']) == FRONT_MATTER_DELIMITER {
+            let front_matter = &rest[..line_start];
+            let content = &rest[line_start + line.len()..];
+            return Ok(Some((front_matter, content)));
+        }
+        line_start += line.len();
+    }
+
+    bail!("Front-matter block isn't closed")
+}
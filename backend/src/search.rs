@@ -0,0 +1,56 @@
+use std::{cmp::Reverse, collections::HashSet};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use super::*;
+
+/// Range of a match in the content of an entry as byte indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatchSpan {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl MatchSpan {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+/// Searches the content of the given entries in memory for the words of the query.
+/// Entries containing all the words are returned with the spans of the matches, ranked by the
+/// count of the matches.
+pub fn search_entries(
+    entries: &[Entry],
+    query: &str,
+) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+    let words: Vec<&str> = query.split_whitespace().collect();
+    if words.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let ac = AhoCorasick::builder()
+        .ascii_case_insensitive(true)
+        .match_kind(MatchKind::LeftmostLongest)
+        .build(&words)?;
+
+    let mut results: Vec<(u32, Vec<MatchSpan>)> = entries
+        .iter()
+        .filter_map(|entry| {
+            let mut matched_words = HashSet::new();
+            let spans: Vec<MatchSpan> = ac
+                .find_iter(&entry.content)
+                .map(|mat| {
+                    matched_words.insert(mat.pattern());
+                    MatchSpan::new(mat.start(), mat.end())
+                })
+                .collect();
+
+            (matched_words.len() == words.len()).then_some((entry.id, spans))
+        })
+        .collect();
+
+    results.sort_by_key(|(_, spans)| Reverse(spans.len()));
+
+    Ok(results)
+}
@@ -0,0 +1,20 @@
+CREATE VIRTUAL TABLE IF NOT EXISTS entries_fts USING fts5(
+  content,
+  content='entries',
+  content_rowid='id'
+);
+
+INSERT INTO entries_fts(entries_fts) VALUES('rebuild');
+
+CREATE TRIGGER IF NOT EXISTS entries_fts_insert AFTER INSERT ON entries BEGIN
+  INSERT INTO entries_fts(rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_fts_delete AFTER DELETE ON entries BEGIN
+  INSERT INTO entries_fts(entries_fts, rowid, content) VALUES ('delete', old.id, old.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS entries_fts_update AFTER UPDATE OF content ON entries BEGIN
+  INSERT INTO entries_fts(entries_fts, rowid, content) VALUES ('delete', old.id, old.content);
+  INSERT INTO entries_fts(rowid, content) VALUES (new.id, new.content);
+END;
@@ -0,0 +1,36 @@
+-- Each day can have one entry only. Existing entries of the same day are merged into the
+-- first entry of that day before enforcing it.
+
+UPDATE entries
+SET content = (
+    SELECT group_concat(day_entries.content, char(10) ORDER BY day_entries.id)
+    FROM entries AS day_entries
+    WHERE date(day_entries.date) = date(entries.date)
+  ),
+  priority = COALESCE(priority, (
+    SELECT MIN(day_entries.priority)
+    FROM entries AS day_entries
+    WHERE date(day_entries.date) = date(entries.date)
+  ))
+WHERE id IN (
+  SELECT MIN(id) FROM entries
+  GROUP BY date(date)
+  HAVING COUNT(*) > 1
+);
+
+INSERT OR IGNORE INTO tags (entry_id, tag)
+SELECT first_entries.id, tags.tag
+FROM tags
+JOIN entries AS duplicates ON duplicates.id = tags.entry_id
+JOIN (
+  SELECT MIN(id) AS id, date(date) AS day FROM entries GROUP BY date(date)
+) AS first_entries ON first_entries.day = date(duplicates.date)
+WHERE duplicates.id <> first_entries.id;
+
+DELETE FROM entries
+WHERE id NOT IN (SELECT MIN(id) FROM entries GROUP BY date(date));
+
+DELETE FROM tags
+WHERE entry_id NOT IN (SELECT id FROM entries);
+
+CREATE UNIQUE INDEX IF NOT EXISTS entries_unique_day ON entries (date(date));
@@ -0,0 +1,16 @@
+-- Persistent UUIDs of the entries, stored as hyphenated text.
+-- Random version 4 UUIDs are generated for the existing entries.
+
+ALTER TABLE entries
+ADD COLUMN uuid TEXT NOT NULL DEFAULT '';
+
+UPDATE entries
+SET uuid = lower(
+  hex(randomblob(4)) || '-' ||
+  hex(randomblob(2)) || '-' ||
+  '4' || substr(hex(randomblob(2)), 2) || '-' ||
+  substr('89ab', 1 + (abs(random()) % 4), 1) || substr(hex(randomblob(2)), 2) || '-' ||
+  hex(randomblob(6))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS entries_unique_uuid ON entries (uuid);
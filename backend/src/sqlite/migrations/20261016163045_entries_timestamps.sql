@@ -0,0 +1,21 @@
+-- Creation and modification times of the entries.
+-- The dates of the existing entries are used for both of them.
+
+ALTER TABLE entries
+ADD COLUMN created_at DATE NOT NULL DEFAULT '';
+
+ALTER TABLE entries
+ADD COLUMN updated_at DATE NOT NULL DEFAULT '';
+
+UPDATE entries
+SET created_at = date,
+  updated_at = date;
+
+-- Keeps the modification time up to date on changes which don't set it explicitly.
+CREATE TRIGGER IF NOT EXISTS entries_updated_at AFTER UPDATE ON entries
+WHEN new.updated_at = old.updated_at
+BEGIN
+  UPDATE entries
+  SET updated_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now')
+  WHERE id = new.id;
+END;
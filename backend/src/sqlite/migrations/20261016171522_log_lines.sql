@@ -0,0 +1,29 @@
+-- Timestamped lines of the entries content in the form 'HH:MM text', kept in sync with the
+-- content by the back-end to query the lines without parsing the content.
+
+CREATE TABLE IF NOT EXISTS log_lines (
+  entry_id INTEGER NOT NULL,
+  line_no INTEGER NOT NULL,
+  time TEXT NOT NULL,
+  text TEXT NOT NULL,
+  PRIMARY KEY (entry_id, line_no),
+  FOREIGN KEY (entry_id) REFERENCES entries (id) ON DELETE CASCADE
+);
+
+-- Splits the content of the existing entries into lines to fill the table.
+WITH RECURSIVE split(entry_id, line_no, line, rest) AS (
+  SELECT id, -1, '', content || char(10) FROM entries
+  UNION ALL
+  SELECT entry_id,
+    line_no + 1,
+    rtrim(substr(rest, 1, instr(rest, char(10)) - 1), char(13)),
+    substr(rest, instr(rest, char(10)) + 1)
+  FROM split
+  WHERE rest <> ''
+)
+INSERT INTO log_lines (entry_id, line_no, time, text)
+SELECT entry_id, line_no, substr(line, 1, 5), substr(line, 7)
+FROM split
+WHERE line_no >= 0
+  AND (line GLOB '[0-1][0-9]:[0-5][0-9]' OR line GLOB '2[0-3]:[0-5][0-9]'
+    OR line GLOB '[0-1][0-9]:[0-5][0-9] *' OR line GLOB '2[0-3]:[0-5][0-9] *');
@@ -0,0 +1,9 @@
+-- Notebooks of the entries. The existing entries are assigned to the default notebook.
+-- Each day can have one entry in each notebook.
+
+ALTER TABLE entries
+ADD COLUMN notebook TEXT NOT NULL DEFAULT 'default';
+
+DROP INDEX IF EXISTS entries_unique_day;
+
+CREATE UNIQUE INDEX IF NOT EXISTS entries_unique_notebook_day ON entries (notebook, date(date));
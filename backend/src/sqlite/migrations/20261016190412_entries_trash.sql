@@ -0,0 +1,10 @@
+-- Time the entries have been moved to the trash. Entries in the trash don't take their days, so
+-- the unique index of the days covers the entries outside the trash only.
+
+ALTER TABLE entries
+ADD COLUMN deleted_at DATE;
+
+DROP INDEX IF EXISTS entries_unique_notebook_day;
+
+CREATE UNIQUE INDEX IF NOT EXISTS entries_unique_notebook_day ON entries (notebook, date(date))
+WHERE deleted_at IS NULL;
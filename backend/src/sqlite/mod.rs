@@ -1,24 +1,70 @@
 use std::{path::PathBuf, str::FromStr};
 
 use self::sqlite_helper::EntryIntermediate;
+use crate::{
+    import::{ImportTarget, import_into},
+    log_line::LOG_TIME_FORMAT,
+};
 
 use super::*;
 use anyhow::anyhow;
 use path_absolutize::Absolutize;
 use sqlx::{
-    Row, Sqlite, SqlitePool,
+    Row, Sqlite, SqliteConnection, SqlitePool,
     migrate::MigrateDatabase,
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteSynchronous},
 };
 
 mod sqlite_helper;
 
+/// Markers wrapping the matches in the highlighted content returned by full-text search.
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+
+/// Format of the date times compared with the days and times of the stored log lines.
+const LOG_LINES_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 pub struct SqliteDataProvide {
     pool: SqlitePool,
 }
 
+/// Durability level of the database, trading the safety of the data on crashes against the
+/// number of writes to the disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SqliteDurability {
+    /// No journal and no syncing with the disk. Fastest but a crash can corrupt the database.
+    Off,
+    /// Rollback journal with syncing on each transaction, keeping the database as a single file.
+    #[default]
+    Normal,
+    /// Write-ahead log with full syncing. Allows other instances to read the database while
+    /// it's being written.
+    WalFull,
+}
+
+impl SqliteDurability {
+    fn journal_mode(self) -> SqliteJournalMode {
+        match self {
+            SqliteDurability::Off => SqliteJournalMode::Off,
+            SqliteDurability::Normal => SqliteJournalMode::Delete,
+            SqliteDurability::WalFull => SqliteJournalMode::Wal,
+        }
+    }
+
+    fn synchronous(self) -> SqliteSynchronous {
+        match self {
+            SqliteDurability::Off => SqliteSynchronous::Off,
+            SqliteDurability::Normal | SqliteDurability::WalFull => SqliteSynchronous::Full,
+        }
+    }
+}
+
 impl SqliteDataProvide {
-    pub async fn from_file(file_path: PathBuf) -> anyhow::Result<Self> {
+    pub async fn from_file(
+        file_path: PathBuf,
+        durability: SqliteDurability,
+    ) -> anyhow::Result<Self> {
         let file_full_path = file_path.absolutize()?;
         if !file_path.exists() {
             if let Some(parent) = file_path.parent() {
@@ -28,10 +74,17 @@ impl SqliteDataProvide {
 
         let db_url = format!("sqlite://{}", file_full_path.to_string_lossy());
 
-        SqliteDataProvide::create(&db_url).await
+        SqliteDataProvide::create_with_durability(&db_url, durability).await
     }
 
     pub async fn create(db_url: &str) -> anyhow::Result<Self> {
+        SqliteDataProvide::create_with_durability(db_url, SqliteDurability::default()).await
+    }
+
+    pub async fn create_with_durability(
+        db_url: &str,
+        durability: SqliteDurability,
+    ) -> anyhow::Result<Self> {
         if !Sqlite::database_exists(db_url).await? {
             log::trace!("Creating Database with the URL '{db_url}'");
             Sqlite::create_database(db_url)
@@ -39,12 +92,9 @@ impl SqliteDataProvide {
                 .map_err(|err| anyhow!("Creating database failed. Error info: {err}"))?;
         }
 
-        // We are using the database as a normal file for one user.
-        // Journal mode will causes problems with the synchronisation in our case and it must be
-        // turned off
         let options = SqliteConnectOptions::from_str(db_url)?
-            .journal_mode(SqliteJournalMode::Off)
-            .synchronous(SqliteSynchronous::Off);
+            .journal_mode(durability.journal_mode())
+            .synchronous(durability.synchronous());
 
         let pool = SqlitePoolOptions::new().connect_with(options).await?;
 
@@ -62,138 +112,200 @@ impl SqliteDataProvide {
 
 impl DataProvider for SqliteDataProvide {
     async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
-        let entries: Vec<EntryIntermediate> = sqlx::query_as(
-            r"SELECT entries.id, entries.title, entries.date, entries.content, entries.priority, GROUP_CONCAT(tags.tag) AS tags
-            FROM entries
-            LEFT JOIN tags ON entries.id = tags.entry_id
-            GROUP BY entries.id
-            ORDER BY date DESC",
+        let mut conn = self.pool.acquire().await?;
+
+        load_active_entries(&mut conn).await
+    }
+
+    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        let mut tx = self.pool.begin().await.map_err(|err| anyhow!(err))?;
+
+        let id = insert_entry(&mut tx, &entry).await?;
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Add entry failed. Error info: {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(Entry::from_draft(id, entry))
+    }
+
+    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        let now = Utc::now();
+
+        sqlx::query(
+            r"UPDATE entries
+            SET deleted_at = $1,
+                updated_at = $1
+            WHERE id = $2 AND deleted_at IS NULL",
         )
-        .fetch_all(&self.pool)
+        .bind(now)
+        .bind(entry_id)
+        .execute(&self.pool)
         .await
         .map_err(|err| {
-            log::error!("Loading entries failed. Error Info {err}");
+            log::error!("Delete entry failed. Error info: {err}");
             anyhow!(err)
         })?;
 
-        let entries: Vec<Entry> = entries.into_iter().map(Entry::from).collect();
+        Ok(())
+    }
+
+    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let mut tx = self.pool.begin().await.map_err(|err| anyhow!(err))?;
+
+        let entry = update_entry_with(&mut tx, entry).await?;
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Update entry failed. Error info {err}");
+            anyhow!(err)
+        })?;
 
-        Ok(entries)
+        Ok(entry)
     }
 
-    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+    async fn add_existing_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
+        let mut tx = self.pool.begin().await.map_err(|err| anyhow!(err))?;
+
+        if entry.deleted_at.is_none() {
+            ensure_unique_day(&mut tx, entry.date, &entry.notebook, None).await?;
+        }
+
+        let uuid_taken: bool =
+            sqlx::query_scalar(r"SELECT EXISTS(SELECT 1 FROM entries WHERE uuid = $1)")
+                .bind(entry.uuid.hyphenated())
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|err| anyhow!(err))?;
+        if uuid_taken {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "An entry with the UUID {} exists already",
+                entry.uuid
+            )));
+        }
+
+        let id_taken: bool =
+            sqlx::query_scalar(r"SELECT EXISTS(SELECT 1 FROM entries WHERE id = $1)")
+                .bind(entry.id)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|err| anyhow!(err))?;
+
         let row = sqlx::query(
-            r"INSERT INTO entries (title, date, content, priority)
-            VALUES($1, $2, $3, $4)
+            r"INSERT INTO entries (id, title, date, content, priority, uuid, notebook, created_at, updated_at, deleted_at)
+            VALUES($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             RETURNING id",
         )
+        .bind((!id_taken).then_some(entry.id))
         .bind(&entry.title)
         .bind(entry.date)
         .bind(&entry.content)
         .bind(entry.priority)
-        .fetch_one(&self.pool)
+        .bind(entry.uuid.hyphenated())
+        .bind(&entry.notebook)
+        .bind(entry.created_at)
+        .bind(entry.updated_at)
+        .bind(entry.deleted_at)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|err| {
-            log::error!("Add entry failed. Error info: {err}");
+            log::error!("Add existing entry failed. Error info: {err}");
             anyhow!(err)
         })?;
 
-        let id = row.get::<u32, _>(0);
+        entry.id = row.get::<u32, _>(0);
 
-        for tag in entry.tags.iter() {
-            sqlx::query(
-                r"INSERT INTO tags (entry_id, tag)
-                VALUES($1, $2)",
-            )
-            .bind(id)
-            .bind(tag)
-            .execute(&self.pool)
-            .await
-            .map_err(|err| {
-                log::error!("Add entry tags failed. Error info:{err}");
-                anyhow!(err)
-            })?;
-        }
+        replace_log_lines(&mut tx, entry.id, &entry.content).await?;
+        insert_tags(&mut tx, entry.id, &entry.tags).await?;
 
-        Ok(Entry::from_draft(id, entry))
+        tx.commit().await.map_err(|err| {
+            log::error!("Add existing entry failed. Error info: {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(entry)
     }
 
-    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
-        sqlx::query(r"DELETE FROM entries WHERE id=$1")
-            .bind(entry_id)
-            .execute(&self.pool)
-            .await
-            .map_err(|err| {
-                log::error!("Delete entry failed. Error info: {err}");
-                anyhow!(err)
-            })?;
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        let entries: Vec<EntryIntermediate> = sqlx::query_as(
+            r"SELECT entries.id, entries.uuid, entries.title, entries.date, entries.content, entries.priority, entries.notebook, entries.created_at, entries.updated_at, entries.deleted_at, GROUP_CONCAT(tags.tag) AS tags
+            FROM entries
+            LEFT JOIN tags ON entries.id = tags.entry_id
+            WHERE entries.deleted_at IS NOT NULL
+            GROUP BY entries.id
+            ORDER BY deleted_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| {
+            log::error!("Loading trash failed. Error Info {err}");
+            anyhow!(err)
+        })?;
 
-        Ok(())
+        Ok(entries.into_iter().map(Entry::from).collect())
     }
 
-    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        let mut tx = self.pool.begin().await.map_err(|err| anyhow!(err))?;
+
+        let entry = load_entry(&mut tx, entry_id).await?;
+        if entry.deleted_at.is_none() {
+            return Err(ModifyEntryError::ValidationError(format!(
+                "Entry with id {entry_id} isn't in the trash"
+            )));
+        }
+
+        ensure_unique_day(&mut tx, entry.date, &entry.notebook, Some(entry_id)).await?;
+
         sqlx::query(
             r"UPDATE entries
-            Set title = $1,
-                date = $2,
-                content = $3,
-                priority = $4
-            WHERE id = $5",
+            SET deleted_at = NULL,
+                updated_at = $1
+            WHERE id = $2",
         )
-        .bind(&entry.title)
-        .bind(entry.date)
-        .bind(&entry.content)
-        .bind(entry.priority)
-        .bind(entry.id)
-        .execute(&self.pool)
+        .bind(Utc::now())
+        .bind(entry_id)
+        .execute(&mut *tx)
         .await
         .map_err(|err| {
-            log::error!("Update entry failed. Error info {err}");
+            log::error!("Restore entry failed. Error info {err}");
             anyhow!(err)
         })?;
 
-        let existing_tags: Vec<String> = sqlx::query_scalar(
-            r"SELECT tag FROM tags 
-            WHERE entry_id = $1",
-        )
-        .bind(entry.id)
-        .fetch_all(&self.pool)
-        .await
-        .map_err(|err| {
-            log::error!("Update entry tags failed. Error info {err}");
+        let entry = load_entry(&mut tx, entry_id).await?;
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Restore entry failed. Error info {err}");
             anyhow!(err)
         })?;
 
-        // Tags to remove
-        for tag_to_remove in existing_tags.iter().filter(|tag| !entry.tags.contains(tag)) {
-            sqlx::query(r"DELETE FROM tags Where entry_id = $1 AND tag = $2")
-                .bind(entry.id)
-                .bind(tag_to_remove)
-                .execute(&self.pool)
-                .await
-                .map_err(|err| {
-                    log::error!("Update entry tags failed. Error info {err}");
-                    anyhow!(err)
-                })?;
-        }
+        Ok(entry)
+    }
 
-        // Tags to insert
-        for tag_to_insert in entry.tags.iter().filter(|tag| !existing_tags.contains(tag)) {
-            sqlx::query(
-                r"INSERT INTO tags (entry_id, tag)
-                VALUES ($1, $2)",
-            )
-            .bind(entry.id)
-            .bind(tag_to_insert)
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        sqlx::query(r"DELETE FROM entries WHERE id = $1 AND deleted_at IS NOT NULL")
+            .bind(entry_id)
             .execute(&self.pool)
             .await
             .map_err(|err| {
-                log::error!("Update entry tags failed. Error info {err}");
+                log::error!("Purge entry failed. Error info: {err}");
                 anyhow!(err)
             })?;
-        }
 
-        Ok(entry)
+        Ok(())
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        let result = sqlx::query(r"DELETE FROM entries WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                log::error!("Purge trash failed. Error info: {err}");
+                anyhow!(err)
+            })?;
+
+        Ok(result.rows_affected() as usize)
     }
 
     async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
@@ -204,7 +316,7 @@ impl DataProvider for SqliteDataProvide {
             .join(", ");
 
         let sql = format!(
-            r"SELECT entries.id, entries.title, entries.date, entries.content, entries.priority, GROUP_CONCAT(tags.tag) AS tags
+            r"SELECT entries.id, entries.uuid, entries.title, entries.date, entries.content, entries.priority, entries.notebook, entries.created_at, entries.updated_at, entries.deleted_at, GROUP_CONCAT(tags.tag) AS tags
             FROM entries
             LEFT JOIN tags ON entries.id = tags.entry_id
             WHERE entries.id IN ({ids_text})
@@ -229,11 +341,180 @@ impl DataProvider for SqliteDataProvide {
         Ok(EntriesDTO::new(entry_drafts))
     }
 
+    async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
+        entries_dto.ensure_current_version()?;
+
+        // Import all the entries in one transaction so a failure doesn't leave a partial import.
+        let mut tx = self.pool.begin().await?;
+
+        for entry_draft in entries_dto.entries.iter() {
+            if uuid_exists(&mut tx, entry_draft.uuid).await? {
+                log::trace!("Import: Skipping existing entry {}", entry_draft.title);
+                continue;
+            }
+
+            insert_entry(&mut tx, entry_draft).await?;
+        }
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Import entries failed. Error info: {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(())
+    }
+
+    async fn import_entries_with_strategy(
+        &self,
+        entries_dto: EntriesDTO,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
+        entries_dto.ensure_current_version()?;
+
+        // Import all the entries in one transaction so a failure doesn't leave a partial import.
+        let mut tx = self.pool.begin().await?;
+
+        let existing_entries = load_active_entries(&mut tx).await?;
+        let mut summary = ImportSummary::default();
+        import_into(
+            &mut TransactionTarget(&mut tx),
+            existing_entries,
+            entries_dto.entries,
+            strategy,
+            &mut summary,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Import entries failed. Error info: {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(summary)
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        let fts_query = to_fts_query(query);
+        if fts_query.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows: Vec<(u32, String)> = sqlx::query_as(
+            r"SELECT rowid, highlight(entries_fts, 0, char(2), char(3))
+            FROM entries_fts
+            WHERE entries_fts MATCH $1
+                AND rowid IN (SELECT id FROM entries WHERE deleted_at IS NULL)
+            ORDER BY rank",
+        )
+        .bind(fts_query)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| {
+            log::error!("Search entries failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+        let results = rows
+            .into_iter()
+            .map(|(id, highlighted)| (id, spans_from_highlight(&highlighted)))
+            .collect();
+
+        Ok(results)
+    }
+
+    async fn append_log_line(
+        &self,
+        entry_id: u32,
+        line: LogLine,
+    ) -> Result<Entry, ModifyEntryError> {
+        let mut tx = self.pool.begin().await.map_err(|err| anyhow!(err))?;
+
+        // Entries in the trash can't be changed and are treated like missing ones.
+        let content: Option<String> =
+            sqlx::query_scalar(r"SELECT content FROM entries WHERE id = $1 AND deleted_at IS NULL")
+                .bind(entry_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(|err| {
+                    log::error!("Loading entry content failed. Error info {err}");
+                    anyhow!(err)
+                })?;
+        let content =
+            content.ok_or_else(|| anyhow!("Entry with the id {entry_id} doesn't exist"))?;
+
+        // Tags missing in the text are appended on rendering, so the stored line is parsed back.
+        let line = LogLine::parse(&line.to_string()).unwrap_or(line);
+        let content = append_log_line(&content, &line);
+        // The appended line is always the last one in the content.
+        let line_no = content.lines().count() - 1;
+
+        sqlx::query(
+            r"UPDATE entries
+            SET content = $1,
+                updated_at = $2
+            WHERE id = $3 AND deleted_at IS NULL",
+        )
+        .bind(&content)
+        .bind(Utc::now())
+        .bind(entry_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| {
+            log::error!("Append log line failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+        insert_log_line(&mut tx, entry_id, line_no, &line).await?;
+
+        let entry = load_entry(&mut tx, entry_id).await?;
+
+        tx.commit().await.map_err(|err| {
+            log::error!("Append log line failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(entry)
+    }
+
+    async fn query_log_lines(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> anyhow::Result<Vec<EntryLogLine>> {
+        let rows: Vec<(u32, NaiveDate, String, String)> = sqlx::query_as(
+            r"SELECT log_lines.entry_id, date(entries.date), log_lines.time, log_lines.text
+            FROM log_lines
+            JOIN entries ON entries.id = log_lines.entry_id
+            WHERE entries.deleted_at IS NULL
+                AND date(entries.date) || ' ' || log_lines.time || ':00' >= $1
+                AND date(entries.date) || ' ' || log_lines.time || ':00' < $2
+            ORDER BY date(entries.date), log_lines.time, log_lines.line_no",
+        )
+        .bind(start.format(LOG_LINES_TIME_FORMAT).to_string())
+        .bind(end.format(LOG_LINES_TIME_FORMAT).to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| {
+            log::error!("Query log lines failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+        let lines = rows
+            .into_iter()
+            .filter_map(|(entry_id, day, time, text)| {
+                let line = LogLine::parse(&format!("{time} {text}"))?;
+                Some(EntryLogLine::new(entry_id, day, line))
+            })
+            .collect();
+
+        Ok(lines)
+    }
+
     async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
         let sql = format!(
             r"UPDATE entries
             SET priority = '{priority}'
-            WHERE priority IS NULL;"
+            WHERE priority IS NULL AND deleted_at IS NULL;"
         );
 
         sqlx::query(sql.as_str())
@@ -247,4 +528,389 @@ impl DataProvider for SqliteDataProvide {
 
         Ok(())
     }
+
+    async fn load_orphaned_tags(&self) -> anyhow::Result<Vec<(u32, String)>> {
+        let tags: Vec<(u32, String)> = sqlx::query_as(
+            r"SELECT entry_id, tag FROM tags
+            WHERE entry_id NOT IN (SELECT id FROM entries)
+            ORDER BY entry_id, tag",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|err| {
+            log::error!("Loading orphaned tags failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+        Ok(tags)
+    }
+
+    async fn remove_orphaned_tags(&self) -> anyhow::Result<usize> {
+        let result =
+            sqlx::query(r"DELETE FROM tags WHERE entry_id NOT IN (SELECT id FROM entries)")
+                .execute(&self.pool)
+                .await
+                .map_err(|err| {
+                    log::error!("Removing orphaned tags failed. Error info {err}");
+                    anyhow!(err)
+                })?;
+
+        Ok(result.rows_affected() as usize)
+    }
+
+    async fn flush_to_files(&self) -> anyhow::Result<()> {
+        // Checkpoint has no effect if the database isn't in write-ahead log mode.
+        sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+            .execute(&self.pool)
+            .await
+            .map_err(|err| {
+                log::error!("Checkpointing the write-ahead log failed. Error info {err}");
+                anyhow!(err)
+            })?;
+
+        Ok(())
+    }
+}
+
+/// Loads all the entries apart from the ones in the trash using the given connection.
+async fn load_active_entries(conn: &mut SqliteConnection) -> anyhow::Result<Vec<Entry>> {
+    let entries: Vec<EntryIntermediate> = sqlx::query_as(
+        r"SELECT entries.id, entries.uuid, entries.title, entries.date, entries.content, entries.priority, entries.notebook, entries.created_at, entries.updated_at, entries.deleted_at, GROUP_CONCAT(tags.tag) AS tags
+        FROM entries
+        LEFT JOIN tags ON entries.id = tags.entry_id
+        WHERE entries.deleted_at IS NULL
+        GROUP BY entries.id
+        ORDER BY date DESC",
+    )
+    .fetch_all(conn)
+    .await
+    .map_err(|err| {
+        log::error!("Loading entries failed. Error Info {err}");
+        anyhow!(err)
+    })?;
+
+    let entries: Vec<Entry> = entries.into_iter().map(Entry::from).collect();
+
+    Ok(entries)
+}
+
+/// Updates the entry with its tags and log lines using the given connection.
+async fn update_entry_with(
+    conn: &mut SqliteConnection,
+    mut entry: Entry,
+) -> Result<Entry, ModifyEntryError> {
+    entry.updated_at = Utc::now();
+
+    ensure_unique_day(&mut *conn, entry.date, &entry.notebook, Some(entry.id)).await?;
+
+    let result = sqlx::query(
+        r"UPDATE entries
+        Set title = $1,
+            date = $2,
+            content = $3,
+            priority = $4,
+            uuid = $5,
+            notebook = $6,
+            updated_at = $7
+        WHERE id = $8 AND deleted_at IS NULL",
+    )
+    .bind(&entry.title)
+    .bind(entry.date)
+    .bind(&entry.content)
+    .bind(entry.priority)
+    .bind(entry.uuid.hyphenated())
+    .bind(&entry.notebook)
+    .bind(entry.updated_at)
+    .bind(entry.id)
+    .execute(&mut *conn)
+    .await
+    .map_err(|err| {
+        log::error!("Update entry failed. Error info {err}");
+        anyhow!(err)
+    })?;
+
+    // Tags and log lines must not be written for missing or deleted entries.
+    if result.rows_affected() == 0 {
+        return Err(ModifyEntryError::ValidationError(format!(
+            "Entry with id {} doesn't exist",
+            entry.id
+        )));
+    }
+
+    replace_log_lines(&mut *conn, entry.id, &entry.content).await?;
+
+    let existing_tags: Vec<String> = sqlx::query_scalar(
+        r"SELECT tag FROM tags 
+        WHERE entry_id = $1",
+    )
+    .bind(entry.id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|err| {
+        log::error!("Update entry tags failed. Error info {err}");
+        anyhow!(err)
+    })?;
+
+    // Tags to remove
+    for tag_to_remove in existing_tags.iter().filter(|tag| !entry.tags.contains(tag)) {
+        sqlx::query(r"DELETE FROM tags Where entry_id = $1 AND tag = $2")
+            .bind(entry.id)
+            .bind(tag_to_remove)
+            .execute(&mut *conn)
+            .await
+            .map_err(|err| {
+                log::error!("Update entry tags failed. Error info {err}");
+                anyhow!(err)
+            })?;
+    }
+
+    // Tags to insert
+    for tag_to_insert in entry.tags.iter().filter(|tag| !existing_tags.contains(tag)) {
+        sqlx::query(
+            r"INSERT INTO tags (entry_id, tag)
+            VALUES ($1, $2)",
+        )
+        .bind(entry.id)
+        .bind(tag_to_insert)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| {
+            log::error!("Update entry tags failed. Error info {err}");
+            anyhow!(err)
+        })?;
+    }
+
+    Ok(entry)
+}
+
+/// Writes the imported entries using the transaction of the import.
+struct TransactionTarget<'a>(&'a mut SqliteConnection);
+
+impl ImportTarget for TransactionTarget<'_> {
+    async fn add(&mut self, draft: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        let id = insert_entry(self.0, &draft).await?;
+
+        Ok(Entry::from_draft(id, draft))
+    }
+
+    async fn update(&mut self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        update_entry_with(self.0, entry).await
+    }
+}
+
+/// Inserts the entry with its tags using the given connection, returning the id of the new entry.
+async fn insert_entry(
+    conn: &mut SqliteConnection,
+    entry: &EntryDraft,
+) -> Result<u32, ModifyEntryError> {
+    ensure_unique_day(&mut *conn, entry.date, &entry.notebook, None).await?;
+
+    // Entries in the trash are replaced by the added entries with the same UUID.
+    sqlx::query(r"DELETE FROM entries WHERE uuid = $1 AND deleted_at IS NOT NULL")
+        .bind(entry.uuid.hyphenated())
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| {
+            log::error!("Removing replaced entry from trash failed. Error info: {err}");
+            anyhow!(err)
+        })?;
+
+    let row = sqlx::query(
+        r"INSERT INTO entries (title, date, content, priority, uuid, notebook, created_at, updated_at)
+        VALUES($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id",
+    )
+    .bind(&entry.title)
+    .bind(entry.date)
+    .bind(&entry.content)
+    .bind(entry.priority)
+    .bind(entry.uuid.hyphenated())
+    .bind(&entry.notebook)
+    .bind(entry.created_at)
+    .bind(entry.updated_at)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(|err| {
+        log::error!("Add entry failed. Error info: {err}");
+        anyhow!(err)
+    })?;
+
+    let id = row.get::<u32, _>(0);
+
+    replace_log_lines(&mut *conn, id, &entry.content).await?;
+    insert_tags(&mut *conn, id, &entry.tags).await?;
+
+    Ok(id)
+}
+
+/// Inserts the given tags of the entry with the given id.
+async fn insert_tags(
+    conn: &mut SqliteConnection,
+    entry_id: u32,
+    tags: &[String],
+) -> anyhow::Result<()> {
+    for tag in tags {
+        sqlx::query(
+            r"INSERT INTO tags (entry_id, tag)
+            VALUES($1, $2)",
+        )
+        .bind(entry_id)
+        .bind(tag)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| {
+            log::error!("Add entry tags failed. Error info:{err}");
+            anyhow!(err)
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Loads the entry with the given id with its tags.
+async fn load_entry(conn: &mut SqliteConnection, entry_id: u32) -> anyhow::Result<Entry> {
+    let entry: EntryIntermediate = sqlx::query_as(
+        r"SELECT entries.id, entries.uuid, entries.title, entries.date, entries.content, entries.priority, entries.notebook, entries.created_at, entries.updated_at, entries.deleted_at, GROUP_CONCAT(tags.tag) AS tags
+        FROM entries
+        LEFT JOIN tags ON entries.id = tags.entry_id
+        WHERE entries.id = $1
+        GROUP BY entries.id",
+    )
+    .bind(entry_id)
+    .fetch_one(conn)
+    .await
+    .map_err(|err| {
+        log::error!("Loading entry failed. Error Info {err}");
+        anyhow!(err)
+    })?;
+
+    Ok(Entry::from(entry))
+}
+
+/// Replaces the stored log lines of the entry with the ones parsed from the given content.
+async fn replace_log_lines(
+    conn: &mut SqliteConnection,
+    entry_id: u32,
+    content: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(r"DELETE FROM log_lines WHERE entry_id = $1")
+        .bind(entry_id)
+        .execute(&mut *conn)
+        .await
+        .map_err(|err| {
+            log::error!("Removing log lines failed. Error info {err}");
+            anyhow!(err)
+        })?;
+
+    for (line_no, line) in content.lines().enumerate() {
+        if let Some(log_line) = LogLine::parse(line) {
+            insert_log_line(&mut *conn, entry_id, line_no, &log_line).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Inserts the log line of the entry with its number in the entry content.
+async fn insert_log_line(
+    conn: &mut SqliteConnection,
+    entry_id: u32,
+    line_no: usize,
+    line: &LogLine,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r"INSERT INTO log_lines (entry_id, line_no, time, text)
+        VALUES ($1, $2, $3, $4)",
+    )
+    .bind(entry_id)
+    .bind(line_no as i64)
+    .bind(line.time.format(LOG_TIME_FORMAT).to_string())
+    .bind(&line.text)
+    .execute(conn)
+    .await
+    .map_err(|err| {
+        log::error!("Inserting log line failed. Error info {err}");
+        anyhow!(err)
+    })?;
+
+    Ok(())
+}
+
+/// Converts the words of the search query to a full-text search query matching the entries
+/// containing all of them as words prefixes.
+fn to_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Extracts the spans of the matches from the highlighted content, removing the markers.
+fn spans_from_highlight(highlighted: &str) -> Vec<MatchSpan> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    let mut match_start = None;
+
+    for ch in highlighted.chars() {
+        match ch {
+            HIGHLIGHT_START => match_start = Some(offset),
+            HIGHLIGHT_END => {
+                if let Some(start) = match_start.take() {
+                    spans.push(MatchSpan::new(start, offset));
+                }
+            }
+            ch => offset += ch.len_utf8(),
+        }
+    }
+
+    spans
+}
+
+/// Makes sure no entry in the given notebook, apart from the one with the ignored id and the ones
+/// in the trash, exists on the day of the given date.
+async fn ensure_unique_day(
+    conn: &mut SqliteConnection,
+    date: DateTime<Utc>,
+    notebook: &str,
+    ignore_id: Option<u32>,
+) -> Result<(), ModifyEntryError> {
+    let existing_id: Option<u32> = sqlx::query_scalar(
+        r"SELECT id FROM entries
+        WHERE date(date) = date($1) AND notebook = $2 AND id IS NOT $3 AND deleted_at IS NULL
+        LIMIT 1",
+    )
+    .bind(date)
+    .bind(notebook)
+    .bind(ignore_id)
+    .fetch_optional(conn)
+    .await
+    .map_err(|err| {
+        log::error!("Checking entries of the same day failed. Error info {err}");
+        anyhow!(err)
+    })?;
+
+    match existing_id {
+        Some(existing_id) => Err(ModifyEntryError::DuplicateDay {
+            day: date.date_naive(),
+            existing_id,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Checks if an entry with the given UUID exists outside the trash.
+async fn uuid_exists(conn: &mut SqliteConnection, uuid: Uuid) -> anyhow::Result<bool> {
+    let exists: bool = sqlx::query_scalar(
+        r"SELECT EXISTS(SELECT 1 FROM entries WHERE uuid = $1 AND deleted_at IS NULL)",
+    )
+    .bind(uuid.hyphenated())
+    .fetch_one(conn)
+    .await
+    .map_err(|err| {
+        log::error!("Checking entry UUID failed. Error info {err}");
+        anyhow!(err)
+    })?;
+
+    Ok(exists)
 }
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use sqlx::FromRow;
+use uuid::fmt::Hyphenated;
 
 use crate::Entry;
 
@@ -7,10 +8,15 @@ use crate::Entry;
 #[derive(FromRow)]
 pub(crate) struct EntryIntermediate {
     pub id: u32,
+    pub uuid: Hyphenated,
     pub date: DateTime<Utc>,
     pub title: String,
     pub content: String,
     pub priority: Option<u32>,
+    pub notebook: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deleted_at: Option<DateTime<Utc>>,
     /// Tags as a string with commas as separator for the tags
     pub tags: Option<String>,
 }
@@ -19,10 +25,15 @@ impl From<EntryIntermediate> for Entry {
     fn from(value: EntryIntermediate) -> Self {
         Entry {
             id: value.id,
+            uuid: value.uuid.into_uuid(),
             date: value.date,
             title: value.title,
             content: value.content,
             priority: value.priority,
+            notebook: value.notebook,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            deleted_at: value.deleted_at,
             tags: value
                 .tags
                 .map(|tags| tags.split_terminator(',').map(String::from).collect())
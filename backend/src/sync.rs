@@ -0,0 +1,446 @@
+use std::{collections::HashMap, fmt};
+
+use super::*;
+
+/// One of the two back-ends being synced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncSide {
+    /// The back-end configured in the app.
+    Local,
+    /// The back-end the local one is synced with.
+    Peer,
+}
+
+impl fmt::Display for SyncSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyncSide::Local => f.write_str("local"),
+            SyncSide::Peer => f.write_str("peer"),
+        }
+    }
+}
+
+/// State of the entries as they were on both sides after the last sync. It's the common base to
+/// detect which side has changed an entry since then. Entries of the base missing on one side are
+/// the tombstones of the entries deleted on that side.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SyncBase {
+    pub synced_at: Option<DateTime<Utc>>,
+    pub entries: Vec<EntryDraft>,
+}
+
+/// Change to apply on one of the sides to bring it in sync with the other one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncAction {
+    Add(SyncSide, EntryDraft),
+    Update(SyncSide, Entry),
+    Remove(SyncSide, Entry),
+}
+
+impl SyncAction {
+    pub fn side(&self) -> SyncSide {
+        match self {
+            SyncAction::Add(side, _)
+            | SyncAction::Update(side, _)
+            | SyncAction::Remove(side, _) => *side,
+        }
+    }
+}
+
+/// Journal changed on both sides in ways which couldn't be merged. Both sides are left untouched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncConflict {
+    pub local: Entry,
+    pub peer: Entry,
+    /// Content of both sides merged with conflict markers around the conflicting lines.
+    pub marked_content: String,
+}
+
+/// Changes needed to sync both sides with the conflicts which couldn't be resolved, and the base
+/// to persist for the next sync once the changes are applied.
+#[derive(Debug, Clone, Default)]
+pub struct SyncPlan {
+    pub actions: Vec<SyncAction>,
+    pub conflicts: Vec<SyncConflict>,
+    pub base: SyncBase,
+}
+
+/// Counts of the changes applied on one side.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SyncSummary {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+}
+
+impl fmt::Display for SyncSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} added, {} updated, {} removed",
+            self.added, self.updated, self.removed
+        )
+    }
+}
+
+impl SyncPlan {
+    /// Gets the counts of the changes on the given side.
+    pub fn summary(&self, side: SyncSide) -> SyncSummary {
+        let mut summary = SyncSummary::default();
+        for action in self.actions.iter().filter(|a| a.side() == side) {
+            match action {
+                SyncAction::Add(..) => summary.added += 1,
+                SyncAction::Update(..) => summary.updated += 1,
+                SyncAction::Remove(..) => summary.removed += 1,
+            }
+        }
+
+        summary
+    }
+}
+
+/// Compares the entries of both sides with the base of the last sync, planning the changes to
+/// propagate the new, changed and deleted entries to the other side.
+///
+/// Entries are matched by their UUIDs, then by their notebooks and days since each day has one
+/// entry only in each notebook.
+/// Entries changed on both sides are merged line by line. Contents which can't be merged are
+/// taken from the preferred side if given, otherwise they are reported as conflicts.
+pub fn plan_sync(
+    local_entries: Vec<Entry>,
+    peer_entries: Vec<Entry>,
+    base: &SyncBase,
+    prefer: Option<SyncSide>,
+) -> SyncPlan {
+    let base_entries: HashMap<Uuid, &EntryDraft> = base
+        .entries
+        .iter()
+        .map(|draft| (draft.uuid, draft))
+        .collect();
+
+    let mut peer_entries: Vec<Option<Entry>> = peer_entries.into_iter().map(Some).collect();
+    let mut plan = SyncPlan::default();
+
+    for local in local_entries {
+        let peer_pos = peer_entries
+            .iter()
+            .position(|peer| peer.as_ref().is_some_and(|p| p.uuid == local.uuid))
+            .or_else(|| {
+                peer_entries.iter().position(|peer| {
+                    peer.as_ref().is_some_and(|p| {
+                        p.notebook == local.notebook
+                            && p.date.date_naive() == local.date.date_naive()
+                    })
+                })
+            });
+        let peer = peer_pos.and_then(|pos| peer_entries[pos].take());
+
+        let base_entry = base_entries.get(&local.uuid).copied().or_else(|| {
+            peer.as_ref()
+                .and_then(|peer| base_entries.get(&peer.uuid).copied())
+        });
+
+        match peer {
+            Some(peer) => plan_pair(&mut plan, local, peer, base_entry, prefer),
+            None => plan_single(&mut plan, SyncSide::Local, local, base_entry),
+        }
+    }
+
+    for peer in peer_entries.into_iter().flatten() {
+        let base_entry = base_entries.get(&peer.uuid).copied();
+        plan_single(&mut plan, SyncSide::Peer, peer, base_entry);
+    }
+
+    plan.base.synced_at = Some(Utc::now());
+
+    plan
+}
+
+/// Plans the entry which exists on the given side only.
+fn plan_single(plan: &mut SyncPlan, side: SyncSide, entry: Entry, base: Option<&EntryDraft>) {
+    let other_side = match side {
+        SyncSide::Local => SyncSide::Peer,
+        SyncSide::Peer => SyncSide::Local,
+    };
+
+    match base {
+        // Unchanged entry deleted on the other side.
+        Some(base) if same_data(base, &EntryDraft::from_entry(entry.clone())) => {
+            plan.actions.push(SyncAction::Remove(side, entry));
+        }
+        // Entry deleted on the other side after it has been changed on this one is kept.
+        Some(_) => {
+            log::info!(
+                "Sync: Restoring journal {} on {other_side} since it has been changed on {side}",
+                entry.title
+            );
+            let draft = EntryDraft::from_entry(entry);
+            plan.base.entries.push(draft.clone());
+            plan.actions.push(SyncAction::Add(other_side, draft));
+        }
+        None => {
+            let draft = EntryDraft::from_entry(entry);
+            plan.base.entries.push(draft.clone());
+            plan.actions.push(SyncAction::Add(other_side, draft));
+        }
+    }
+}
+
+/// Plans the entry which exists on both sides.
+fn plan_pair(
+    plan: &mut SyncPlan,
+    local: Entry,
+    peer: Entry,
+    base: Option<&EntryDraft>,
+    prefer: Option<SyncSide>,
+) {
+    let local_draft = EntryDraft::from_entry(local.clone());
+    let peer_draft = EntryDraft::from_entry(peer.clone());
+
+    let merged = if same_data(&local_draft, &peer_draft) {
+        local_draft.clone()
+    } else {
+        match base {
+            Some(base) => match merge_changes(base, &local_draft, &peer_draft, prefer) {
+                Ok(merged) => merged,
+                Err(marked_content) => {
+                    plan.base.entries.push(base.clone());
+                    plan.conflicts.push(SyncConflict {
+                        local,
+                        peer,
+                        marked_content,
+                    });
+                    return;
+                }
+            },
+            None => merge_unrelated(&local_draft, &peer_draft),
+        }
+    };
+
+    if !same_data(&merged, &local_draft) || merged.uuid != local.uuid {
+        plan.actions.push(SyncAction::Update(
+            SyncSide::Local,
+            with_draft(&local, &merged),
+        ));
+    }
+
+    if !same_data(&merged, &peer_draft) || merged.uuid != peer.uuid {
+        plan.actions.push(SyncAction::Update(
+            SyncSide::Peer,
+            with_draft(&peer, &merged),
+        ));
+    }
+
+    plan.base.entries.push(merged);
+}
+
+/// Applies the data of the merged draft on the entry, keeping its id.
+fn with_draft(entry: &Entry, merged: &EntryDraft) -> Entry {
+    Entry {
+        // Modification time is set by the back-end.
+        updated_at: entry.updated_at,
+        ..Entry::from_draft(entry.id, merged.clone())
+    }
+}
+
+/// Merges the changes of both sides on the entry since the base. Fields changed on one side only
+/// are taken from that side, while fields changed on both sides are taken from the most recently
+/// modified side. Returns the content with conflict markers if the contents can't be merged.
+fn merge_changes(
+    base: &EntryDraft,
+    local: &EntryDraft,
+    peer: &EntryDraft,
+    prefer: Option<SyncSide>,
+) -> Result<EntryDraft, String> {
+    let peer_is_newer = peer.updated_at > local.updated_at;
+
+    let content = match merge_three_way(&base.content, &local.content, &peer.content) {
+        Ok(content) => content,
+        Err(_) if prefer == Some(SyncSide::Local) => local.content.clone(),
+        Err(_) if prefer == Some(SyncSide::Peer) => peer.content.clone(),
+        Err(marked) => return Err(marked),
+    };
+
+    let (date, title) = pick_changed(
+        (base.date, &base.title),
+        (local.date, &local.title),
+        (peer.date, &peer.title),
+        peer_is_newer,
+    );
+
+    let priority = pick_changed(base.priority, local.priority, peer.priority, peer_is_newer);
+    let notebook = pick_changed(
+        &base.notebook,
+        &local.notebook,
+        &peer.notebook,
+        peer_is_newer,
+    );
+
+    // Tags removed on any side are removed and the tags added on any side are added.
+    let mut tags: Vec<String> = local
+        .tags
+        .iter()
+        .filter(|tag| !base.tags.contains(tag) || peer.tags.contains(tag))
+        .cloned()
+        .collect();
+    for tag in &peer.tags {
+        if !base.tags.contains(tag) && !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
+    Ok(EntryDraft {
+        uuid: local.uuid,
+        date,
+        title: title.to_owned(),
+        content,
+        tags,
+        priority,
+        notebook: notebook.to_owned(),
+        created_at: local.created_at.min(peer.created_at),
+        updated_at: local.updated_at.max(peer.updated_at),
+    })
+}
+
+/// Merges the entries of the same day created on both sides independently, merging the lines of
+/// their contents and their tags.
+fn merge_unrelated(local: &EntryDraft, peer: &EntryDraft) -> EntryDraft {
+    let newer = if peer.updated_at > local.updated_at {
+        peer
+    } else {
+        local
+    };
+
+    let mut tags = local.tags.clone();
+    for tag in &peer.tags {
+        if !tags.contains(tag) {
+            tags.push(tag.clone());
+        }
+    }
+
+    EntryDraft {
+        uuid: local.uuid,
+        date: newer.date,
+        title: newer.title.clone(),
+        content: merge_log_lines(&local.content, &peer.content),
+        tags,
+        priority: newer.priority.or(local.priority).or(peer.priority),
+        notebook: local.notebook.clone(),
+        created_at: local.created_at.min(peer.created_at),
+        updated_at: local.updated_at.max(peer.updated_at),
+    }
+}
+
+/// Picks the value changed since the base, preferring the newer side if both have changed it.
+fn pick_changed<T: PartialEq>(base: T, local: T, peer: T, peer_is_newer: bool) -> T {
+    if local == base || (peer != base && peer_is_newer) {
+        peer
+    } else {
+        local
+    }
+}
+
+/// Merges the lines changed on both contents since their common base content.
+/// Returns the content with conflict markers around the conflicting lines on conflicts.
+pub fn merge_three_way(base: &str, local: &str, other: &str) -> Result<String, String> {
+    if local == other || other == base {
+        return Ok(local.to_owned());
+    }
+    if local == base {
+        return Ok(other.to_owned());
+    }
+
+    // Merging works on whole lines, therefore the last lines must end with a new line to be
+    // compared to the other lines.
+    let with_newline = |text: &str| {
+        if text.is_empty() || text.ends_with('\n') {
+            text.to_owned()
+        } else {
+            format!("{text}\n")
+        }
+    };
+    let keep_newline = [base, local, other].iter().any(|t| t.ends_with('\n'));
+
+    let restore_end = |mut text: String| {
+        if !keep_newline && text.ends_with('\n') {
+            text.pop();
+        }
+        text
+    };
+
+    diffy::merge(
+        &with_newline(base),
+        &with_newline(local),
+        &with_newline(other),
+    )
+    .map(restore_end)
+    .map_err(restore_end)
+}
+
+/// Checks if both entries have the same data apart from their identity and timestamps.
+fn same_data(a: &EntryDraft, b: &EntryDraft) -> bool {
+    let sorted_tags = |tags: &[String]| {
+        let mut tags = tags.to_vec();
+        tags.sort();
+        tags
+    };
+
+    a.date == b.date
+        && a.title == b.title
+        && a.content == b.content
+        && a.priority == b.priority
+        && a.notebook == b.notebook
+        && sorted_tags(&a.tags) == sorted_tags(&b.tags)
+}
+
+/// Applies the planned changes on both sides. Entries are removed first to free their days for
+/// the entries moved to them.
+pub async fn apply_sync_plan<L: DataProvider, P: DataProvider>(
+    local: &L,
+    peer: &P,
+    plan: &SyncPlan,
+) -> anyhow::Result<()> {
+    let ordered = plan
+        .actions
+        .iter()
+        .filter(|a| matches!(a, SyncAction::Remove(..)))
+        .chain(
+            plan.actions
+                .iter()
+                .filter(|a| !matches!(a, SyncAction::Remove(..))),
+        );
+
+    for action in ordered {
+        let result = match action {
+            SyncAction::Add(SyncSide::Local, draft) => {
+                local.add_entry(draft.clone()).await.map(drop)
+            }
+            SyncAction::Add(SyncSide::Peer, draft) => peer.add_entry(draft.clone()).await.map(drop),
+            SyncAction::Update(SyncSide::Local, entry) => {
+                local.update_entry(entry.clone()).await.map(drop)
+            }
+            SyncAction::Update(SyncSide::Peer, entry) => {
+                peer.update_entry(entry.clone()).await.map(drop)
+            }
+            SyncAction::Remove(SyncSide::Local, entry) => {
+                local.remove_entry(entry.id).await.map_err(Into::into)
+            }
+            SyncAction::Remove(SyncSide::Peer, entry) => {
+                peer.remove_entry(entry.id).await.map_err(Into::into)
+            }
+        };
+
+        if let Err(err) = result {
+            let title = match action {
+                SyncAction::Add(_, draft) => &draft.title,
+                SyncAction::Update(_, entry) | SyncAction::Remove(_, entry) => &entry.title,
+            };
+            return Err(anyhow::anyhow!(
+                "Syncing journal {title} on {} failed. Error info: {err}",
+                action.side()
+            ));
+        }
+    }
+
+    Ok(())
+}
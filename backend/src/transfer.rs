@@ -0,0 +1,146 @@
+use std::io::Read;
+
+use anyhow::{Context, anyhow, bail, ensure};
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Function converting transfer data in JSON from one version to the next one.
+type UpgradeFn = fn(&mut Value) -> anyhow::Result<()>;
+
+/// Chain of the upgrades for transfer data as (from version, to version, upgrade function).
+/// A new step must be added here each time [`TRANSFER_DATA_VERSION`] is increased.
+const UPGRADES: &[(u16, u16, UpgradeFn)] = &[
+    (100, 101, add_uuids),
+    (101, 102, add_timestamps),
+    (102, 103, add_notebooks),
+];
+
+impl EntriesDTO {
+    /// Parses transfer data from the given JSON reader, upgrading it to the current version if it
+    /// has been created with an older one.
+    /// Returns the parsed data with the version it had before the upgrade.
+    pub fn parse_versioned<R: Read>(reader: R) -> anyhow::Result<(Self, u16)> {
+        let mut value: Value = serde_json::from_reader(reader)
+            .map_err(|err| anyhow!("Transfer data isn't valid JSON. Error: {err}"))?;
+
+        let source_version = read_version(&value)?;
+
+        ensure!(
+            source_version <= TRANSFER_DATA_VERSION,
+            "Transfer data version {source_version} is newer than the supported version {TRANSFER_DATA_VERSION}. Please update the app to import it"
+        );
+
+        let mut version = source_version;
+        while version < TRANSFER_DATA_VERSION {
+            let (_, next_version, upgrade) = UPGRADES
+                .iter()
+                .find(|(from, _, _)| *from == version)
+                .with_context(|| format!("Transfer data version {version} isn't supported"))?;
+
+            upgrade(&mut value).with_context(|| {
+                format!("Upgrading transfer data from version {version} to {next_version} failed")
+            })?;
+
+            version = *next_version;
+            value["version"] = Value::from(version);
+        }
+
+        let dto: EntriesDTO = serde_json::from_value(value).map_err(|err| {
+            anyhow!("Transfer data doesn't match the expected format. Error: {err}")
+        })?;
+
+        Ok((dto, source_version))
+    }
+
+    /// Makes sure the data has the current version, since older versions must be upgraded via
+    /// [`EntriesDTO::parse_versioned()`] before importing them.
+    pub fn ensure_current_version(&self) -> anyhow::Result<()> {
+        ensure!(
+            self.version == TRANSFER_DATA_VERSION,
+            "Transfer data version {} doesn't match the current version {TRANSFER_DATA_VERSION}",
+            self.version
+        );
+
+        Ok(())
+    }
+}
+
+fn read_version(value: &Value) -> anyhow::Result<u16> {
+    let Some(version) = value.get("version") else {
+        bail!("Transfer data doesn't have a version");
+    };
+
+    version
+        .as_u64()
+        .and_then(|version| u16::try_from(version).ok())
+        .with_context(|| format!("Transfer data version is invalid: {version}"))
+}
+
+/// Version 101: Entries got persistent UUIDs, which are derived for the entries of older data
+/// from their positions and dates, so importing the same data again matches the same entries.
+fn add_uuids(value: &mut Value) -> anyhow::Result<()> {
+    for (pos, entry) in entries_objects(value)?.into_iter().enumerate() {
+        if entry.contains_key("uuid") {
+            continue;
+        }
+
+        let date: DateTime<Utc> = entry
+            .get("date")
+            .cloned()
+            .context("Transfer data entry doesn't have a date")
+            .and_then(|date| {
+                serde_json::from_value(date)
+                    .map_err(|err| anyhow!("Transfer data entry date is invalid. Error: {err}"))
+            })?;
+        let id = u32::try_from(pos).context("Transfer data has too many entries")?;
+
+        entry.insert(
+            String::from("uuid"),
+            Value::from(derive_uuid(id, date).to_string()),
+        );
+    }
+
+    Ok(())
+}
+
+/// Version 102: Entries got creation and modification times, which are set to the entries dates
+/// for older data.
+fn add_timestamps(value: &mut Value) -> anyhow::Result<()> {
+    for entry in entries_objects(value)? {
+        let date = entry
+            .get("date")
+            .cloned()
+            .context("Transfer data entry doesn't have a date")?;
+
+        entry.entry("created_at").or_insert_with(|| date.clone());
+        entry.entry("updated_at").or_insert(date);
+    }
+
+    Ok(())
+}
+
+/// Version 103: Entries got notebooks, with the entries of older data in the default notebook.
+fn add_notebooks(value: &mut Value) -> anyhow::Result<()> {
+    for entry in entries_objects(value)? {
+        entry
+            .entry("notebook")
+            .or_insert_with(|| Value::from(DEFAULT_NOTEBOOK));
+    }
+
+    Ok(())
+}
+
+fn entries_objects(value: &mut Value) -> anyhow::Result<Vec<&mut Map<String, Value>>> {
+    value
+        .get_mut("entries")
+        .and_then(Value::as_array_mut)
+        .context("Transfer data doesn't have entries")?
+        .iter_mut()
+        .map(|entry| {
+            entry
+                .as_object_mut()
+                .context("Transfer data entry isn't an object")
+        })
+        .collect()
+}
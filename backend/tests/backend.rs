@@ -1,4 +1,23 @@
+mod common;
+#[cfg(all(feature = "json", feature = "sqlite", feature = "markdown_dir"))]
+mod convert;
+#[cfg(all(feature = "json", feature = "sqlite"))]
+mod doctor;
+mod foreign_import;
+#[cfg(feature = "json")]
+mod git_versioned;
+#[cfg(all(feature = "http", feature = "sqlite"))]
+mod http;
+#[cfg(feature = "sqlite")]
+mod import;
 #[cfg(feature = "json")]
 mod json;
 #[cfg(feature = "sqlite")]
+mod log_line;
+#[cfg(feature = "markdown_dir")]
+mod markdown_dir;
+#[cfg(feature = "sqlite")]
 mod sqlite;
+#[cfg(feature = "sqlite")]
+mod sync;
+mod transfer;
@@ -0,0 +1,34 @@
+use std::{env, fs, path::PathBuf};
+pub struct TempDir {
+    pub dir_path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(dir_name: &str) -> Self {
+        let dir_path = env::temp_dir().join(dir_name);
+
+        let temp_dir = Self { dir_path };
+        temp_dir.clean_up();
+        fs::create_dir_all(&temp_dir.dir_path)
+            .expect("Access to create the test directory should be given");
+
+        temp_dir
+    }
+
+    pub fn clean_up(&self) {
+        if self
+            .dir_path
+            .try_exists()
+            .expect("Access to check the test directory should be given")
+        {
+            fs::remove_dir_all(&self.dir_path)
+                .expect("Access to delete the test directory should be given");
+        }
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        self.clean_up();
+    }
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use backend::*;
+use chrono::{TimeZone, Utc};
+
+use crate::common::TempDir;
+
+async fn create_json_provider(file_path: PathBuf) -> JsonDataProvide {
+    let provider = JsonDataProvide::new(file_path);
+
+    for day in 1..=3 {
+        let draft = EntryDraft::new(
+            Utc.with_ymd_and_hms(2024, 5, day, 8, 30, 0).unwrap(),
+            format!("Title {day}"),
+            vec![String::from("Tag_1"), format!("Day_{day}")],
+            Some(day),
+        )
+        .with_content(format!("Content {day}\nSecond line"));
+        provider.add_entry(draft).await.unwrap();
+    }
+
+    provider.remove_entry(1).await.unwrap();
+
+    provider
+}
+
+/// Sorts the entries by their ids with their tags, since the back-ends don't keep the order of the
+/// tags.
+fn sorted(mut entries: Vec<Entry>) -> Vec<Entry> {
+    entries.sort_by_key(|entry| entry.id);
+    entries.iter_mut().for_each(|entry| entry.tags.sort());
+    entries
+}
+
+#[tokio::test]
+async fn json_to_sqlite() {
+    let temp_dir = TempDir::new("convert_json_to_sqlite");
+    let json = create_json_provider(temp_dir.dir_path.join("entries.json")).await;
+    let sqlite = SqliteDataProvide::from_file(
+        temp_dir.dir_path.join("entries.db"),
+        SqliteDurability::default(),
+    )
+    .await
+    .unwrap();
+
+    let summary = convert_entries(&json, &sqlite).await.unwrap();
+    assert_eq!(
+        summary,
+        ConvertSummary {
+            entries: 3,
+            trashed: 1,
+            changed_ids: 0,
+        }
+    );
+
+    let source = sorted(json.load_all_entries().await.unwrap());
+    let target = sorted(sqlite.load_all_entries().await.unwrap());
+    assert_eq!(source, target);
+
+    let trash = sqlite.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 1);
+    assert_eq!(trash[0].title, "Title 2");
+}
+
+#[tokio::test]
+async fn sqlite_to_markdown_dir() {
+    let temp_dir = TempDir::new("convert_sqlite_to_markdown_dir");
+    let json = create_json_provider(temp_dir.dir_path.join("entries.json")).await;
+    let sqlite = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    convert_entries(&json, &sqlite).await.unwrap();
+
+    let markdown = MarkdownDirDataProvide::new(temp_dir.dir_path.join("entries"));
+    let summary = convert_entries(&sqlite, &markdown).await.unwrap();
+    assert_eq!(summary.entries, 3);
+    assert_eq!(summary.changed_ids, 0);
+
+    let source = sorted(sqlite.load_all_entries().await.unwrap());
+    let target = sorted(markdown.load_all_entries().await.unwrap());
+    assert_eq!(
+        source.iter().map(content_hash).collect::<Vec<_>>(),
+        target.iter().map(content_hash).collect::<Vec<_>>()
+    );
+    assert_eq!(markdown.load_trash().await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn target_not_empty() {
+    let temp_dir = TempDir::new("convert_target_not_empty");
+    let json = create_json_provider(temp_dir.dir_path.join("entries.json")).await;
+    let sqlite = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    let draft = EntryDraft::new(Utc::now(), String::from("Existing"), Vec::new(), None);
+    sqlite.add_entry(draft).await.unwrap();
+
+    assert!(convert_entries(&json, &sqlite).await.is_err());
+    assert_eq!(sqlite.load_all_entries().await.unwrap().len(), 1);
+}
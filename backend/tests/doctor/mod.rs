@@ -0,0 +1,228 @@
+use std::{fs, path::PathBuf, str::FromStr};
+
+use backend::*;
+use chrono::{NaiveDate, TimeZone, Utc};
+use sqlx::{ConnectOptions, sqlite::SqliteConnectOptions};
+
+use crate::common::TempDir;
+
+/// Writes entries with problems to the JSON file directly, since the back-end wouldn't accept
+/// them.
+fn write_broken_entries(file_path: &PathBuf) {
+    let day_1 = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let day_2 = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+
+    let entries = vec![
+        Entry::new(
+            0,
+            day_1,
+            String::from("2024_05_01_Wednesday"),
+            String::from("08:00 First"),
+            vec![String::from("Tag_1")],
+            None,
+        ),
+        Entry::new(
+            1,
+            day_1,
+            String::from("Duplicate"),
+            String::from("09:00 Second"),
+            vec![String::from("Tag_2")],
+            Some(2),
+        ),
+        Entry::new(
+            2,
+            day_2,
+            String::new(),
+            String::from("Content"),
+            vec![
+                String::from("Tag_1"),
+                String::from("Tag_3"),
+                String::from("Tag_1"),
+            ],
+            None,
+        ),
+    ];
+
+    fs::write(file_path, serde_json::to_string(&entries).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn json_diagnose_and_fix() {
+    let temp_dir = TempDir::new("doctor_json_diagnose_and_fix");
+    let file_path = temp_dir.dir_path.join("entries.json");
+    write_broken_entries(&file_path);
+    let provider = JsonDataProvide::new(file_path);
+
+    let issues = diagnose_entries(&provider).await;
+    assert_eq!(
+        issues,
+        vec![
+            EntryIssue::InvalidTitle {
+                entry_id: 1,
+                title: String::from("Duplicate"),
+                expected: String::from("2024_05_01_Wednesday"),
+            },
+            EntryIssue::EmptyTitle {
+                entry_id: 2,
+                expected: String::from("2024_05_02_Thursday"),
+            },
+            EntryIssue::DuplicateTags {
+                entry_id: 2,
+                tags: vec![String::from("Tag_1")],
+            },
+            EntryIssue::DuplicateDay {
+                day: NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                notebook: String::from(DEFAULT_NOTEBOOK),
+                entry_ids: vec![0, 1],
+            },
+        ]
+    );
+    assert!(issues.iter().all(EntryIssue::is_fixable));
+
+    let summary = fix_entries(&provider).await.unwrap();
+    assert_eq!(
+        summary,
+        FixSummary {
+            restored_backup: false,
+            renamed: 1,
+            merged: 1,
+            deduplicated_tags: 1,
+            orphaned_tags: 0,
+        }
+    );
+
+    let mut entries = provider.load_all_entries().await.unwrap();
+    entries.sort_by_key(|entry| entry.id);
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].title, "2024_05_01_Wednesday");
+    assert_eq!(entries[0].content, "08:00 First\n09:00 Second");
+    assert_eq!(entries[0].tags, vec!["Tag_1", "Tag_2"]);
+    assert_eq!(entries[0].priority, Some(2));
+
+    assert_eq!(entries[1].title, "2024_05_02_Thursday");
+    assert_eq!(entries[1].tags, vec!["Tag_1", "Tag_3"]);
+
+    // Merged duplicates are kept in the trash.
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 1);
+
+    assert!(diagnose_entries(&provider).await.is_empty());
+}
+
+#[tokio::test]
+async fn json_corrupted() {
+    let temp_dir = TempDir::new("doctor_json_corrupted");
+    let file_path = temp_dir.dir_path.join("entries.json");
+    fs::write(&file_path, "[{\"id\": 0,").unwrap();
+    let provider = JsonDataProvide::new(file_path);
+
+    let issues = diagnose_entries(&provider).await;
+    assert_eq!(issues.len(), 1);
+    assert!(matches!(issues[0], EntryIssue::LoadFailed(_)));
+    assert!(!issues[0].is_fixable());
+}
+
+#[tokio::test]
+async fn json_corrupted_with_backup() {
+    let temp_dir = TempDir::new("doctor_json_corrupted_with_backup");
+    let file_path = temp_dir.dir_path.join("entries.json");
+    let provider = JsonDataProvide::new(file_path.clone()).with_backup_count(2);
+    let draft_1 = EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+        String::from("2024_05_01_Wednesday"),
+        Vec::new(),
+        None,
+    );
+    let draft_2 = EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap(),
+        String::from("2024_05_02_Thursday"),
+        Vec::new(),
+        None,
+    );
+    provider.add_entry(draft_1).await.unwrap();
+    provider.add_entry(draft_2).await.unwrap();
+
+    fs::write(&file_path, "[{\"id\": 0,").unwrap();
+    let backup_path = temp_dir.dir_path.join("entries.json.1.bak");
+    let corrupted_path = temp_dir.dir_path.join("entries.json.corrupted");
+
+    // Diagnosing must leave the files untouched.
+    let issues = diagnose_entries(&provider).await;
+    assert_eq!(
+        issues,
+        vec![EntryIssue::CorruptedFile {
+            backup: backup_path.clone()
+        }]
+    );
+    assert!(issues[0].is_fixable());
+    assert_eq!(fs::read_to_string(&file_path).unwrap(), "[{\"id\": 0,");
+    assert!(!corrupted_path.exists());
+
+    let summary = fix_entries(&provider).await.unwrap();
+    assert!(summary.restored_backup);
+    assert!(corrupted_path.exists());
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert!(diagnose_entries(&provider).await.is_empty());
+}
+
+#[tokio::test]
+async fn sqlite_diagnose_and_fix() {
+    let temp_dir = TempDir::new("doctor_sqlite_diagnose_and_fix");
+    let file_path = temp_dir.dir_path.join("entries.db");
+    let provider = SqliteDataProvide::from_file(file_path.clone(), SqliteDurability::default())
+        .await
+        .unwrap();
+
+    let date = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::new(), vec![String::from("Tag_1")], None);
+    let entry = provider.add_entry(draft).await.unwrap();
+
+    // Tags of removed entries could be left behind in databases without enforced foreign keys.
+    let mut conn = SqliteConnectOptions::from_str(&format!("sqlite://{}", file_path.display()))
+        .unwrap()
+        .foreign_keys(false)
+        .connect()
+        .await
+        .unwrap();
+    sqlx::query("INSERT INTO tags (entry_id, tag) VALUES (99, 'Orphan')")
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+    let issues = diagnose_entries(&provider).await;
+    assert_eq!(
+        issues,
+        vec![
+            EntryIssue::EmptyTitle {
+                entry_id: entry.id,
+                expected: String::from("2024_05_01_Wednesday"),
+            },
+            EntryIssue::OrphanedTag {
+                entry_id: 99,
+                tag: String::from("Orphan"),
+            },
+        ]
+    );
+
+    let summary = fix_entries(&provider).await.unwrap();
+    assert_eq!(
+        summary,
+        FixSummary {
+            restored_backup: false,
+            renamed: 1,
+            merged: 0,
+            deduplicated_tags: 0,
+            orphaned_tags: 1,
+        }
+    );
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries[0].title, "2024_05_01_Wednesday");
+    assert_eq!(entries[0].tags, vec!["Tag_1"]);
+
+    assert!(diagnose_entries(&provider).await.is_empty());
+}
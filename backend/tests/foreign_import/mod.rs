@@ -0,0 +1,223 @@
+use std::fs;
+
+use backend::*;
+use chrono::{TimeZone, Utc};
+
+use crate::common::TempDir;
+
+#[test]
+fn parse_format() {
+    for format in ForeignFormat::ALL {
+        assert_eq!(format.as_str().parse::<ForeignFormat>().unwrap(), format);
+    }
+    assert!("word".parse::<ForeignFormat>().is_err());
+}
+
+#[test]
+fn jrnl_text() {
+    let temp_dir = TempDir::new("foreign_import_jrnl_text");
+    let file_path = temp_dir.dir_path.join("journal.txt");
+    fs::write(
+        &file_path,
+        "[2024-05-01 09:15:00 PM] Evening walk @health\n\
+         Went around the #park.\n\
+         \n\
+         [2024-05-01 08:00:00 AM] Breakfast\n\
+         \n\
+         [2024-05-02 10:30:00 AM] Meeting with @work team\n",
+    )
+    .unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::Jrnl, &file_path).unwrap();
+    assert_eq!(drafts.len(), 2);
+
+    assert_eq!(
+        drafts[0].date,
+        Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap()
+    );
+    assert_eq!(drafts[0].title, "2024_05_01_Wednesday");
+    assert_eq!(
+        drafts[0].content,
+        "08:00 Breakfast\n21:15 Evening walk @health\nWent around the #park."
+    );
+    assert_eq!(drafts[0].tags, vec!["health", "park"]);
+
+    assert_eq!(drafts[1].title, "2024_05_02_Thursday");
+    assert_eq!(drafts[1].content, "10:30 Meeting with @work team");
+    assert_eq!(drafts[1].tags, vec!["work"]);
+}
+
+#[test]
+fn jrnl_json() {
+    let temp_dir = TempDir::new("foreign_import_jrnl_json");
+    let file_path = temp_dir.dir_path.join("journal.json");
+    fs::write(
+        &file_path,
+        r#"{
+            "tags": {"@work": 1},
+            "entries": [
+                {
+                    "title": "Planning",
+                    "body": "Sprint goals",
+                    "date": "2024-05-03",
+                    "time": "14:05",
+                    "tags": ["@work"],
+                    "starred": false
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::Jrnl, &file_path).unwrap();
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0].title, "2024_05_03_Friday");
+    assert_eq!(drafts[0].content, "14:05 Planning\nSprint goals");
+    assert_eq!(drafts[0].tags, vec!["work"]);
+}
+
+#[test]
+fn jrnl_invalid() {
+    let temp_dir = TempDir::new("foreign_import_jrnl_invalid");
+    let file_path = temp_dir.dir_path.join("journal.txt");
+    fs::write(&file_path, "Text without any entry heading").unwrap();
+
+    assert!(read_foreign_entries(ForeignFormat::Jrnl, &file_path).is_err());
+}
+
+#[test]
+fn day_one() {
+    let temp_dir = TempDir::new("foreign_import_day_one");
+    let file_path = temp_dir.dir_path.join("Journal.json");
+    // Days and times of the entries follow their time zones.
+    fs::write(
+        &file_path,
+        r#"{
+            "metadata": {"version": "1.0"},
+            "entries": [
+                {
+                    "uuid": "A1",
+                    "creationDate": "2024-05-04T23:30:00Z",
+                    "timeZone": "Asia/Tokyo",
+                    "text": "Trip to the lake\\. Great \\#fun",
+                    "tags": ["Travel"]
+                },
+                {
+                    "uuid": "A2",
+                    "creationDate": "2024-05-05T02:00:00Z",
+                    "timeZone": "America/New_York",
+                    "text": "Quiet day"
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::DayOne, &file_path).unwrap();
+    assert_eq!(drafts.len(), 2);
+    assert_eq!(drafts[0].title, "2024_05_04_Saturday");
+    assert_eq!(drafts[0].content, "22:00 Quiet day");
+    assert!(drafts[0].tags.is_empty());
+
+    assert_eq!(drafts[1].title, "2024_05_05_Sunday");
+    assert_eq!(drafts[1].content, "08:30 Trip to the lake. Great #fun");
+    assert_eq!(drafts[1].tags, vec!["Travel", "fun"]);
+}
+
+#[test]
+fn day_one_invalid_time_zone() {
+    let temp_dir = TempDir::new("foreign_import_day_one_invalid_time_zone");
+    let file_path = temp_dir.dir_path.join("Journal.json");
+    fs::write(
+        &file_path,
+        r#"{"entries": [{"creationDate": "2024-05-04T12:00:00Z", "timeZone": "Mars/Base"}]}"#,
+    )
+    .unwrap();
+
+    assert!(read_foreign_entries(ForeignFormat::DayOne, &file_path).is_err());
+}
+
+#[test]
+fn obsidian_daily_notes() {
+    let temp_dir = TempDir::new("foreign_import_obsidian");
+    let dir_path = &temp_dir.dir_path;
+    fs::create_dir_all(dir_path.join("journals")).unwrap();
+    fs::create_dir_all(dir_path.join(".trash")).unwrap();
+
+    fs::write(
+        dir_path.join("2024-05-06.md"),
+        "---\ntags:\n  - daily\n  - \"#mood\"\n---\nFeeling good #happy\n",
+    )
+    .unwrap();
+    fs::write(
+        dir_path.join("journals").join("2024_05_07.md"),
+        "---\ntags: [work, review]\n---\n- Reviewed PRs\n",
+    )
+    .unwrap();
+    fs::write(dir_path.join(".trash").join("2024-05-08.md"), "Deleted").unwrap();
+    fs::write(dir_path.join("Ideas.md"), "Not a daily note").unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::Obsidian, dir_path).unwrap();
+    assert_eq!(drafts.len(), 2);
+
+    assert_eq!(drafts[0].title, "2024_05_06_Monday");
+    assert_eq!(drafts[0].content, "Feeling good #happy");
+    assert_eq!(drafts[0].tags, vec!["daily", "mood", "happy"]);
+
+    assert_eq!(drafts[1].title, "2024_05_07_Tuesday");
+    assert_eq!(drafts[1].content, "- Reviewed PRs");
+    assert_eq!(drafts[1].tags, vec!["work", "review"]);
+
+    let file_path = dir_path.join("2024-05-06.md");
+    assert!(read_foreign_entries(ForeignFormat::Obsidian, &file_path).is_err());
+}
+
+#[test]
+fn obsidian_daily_notes_crlf() {
+    let temp_dir = TempDir::new("foreign_import_obsidian_crlf");
+    let dir_path = &temp_dir.dir_path;
+
+    fs::write(
+        dir_path.join("2024-05-06.md"),
+        "---\r\ntags:\r\n  - daily\r\n---\r\nFeeling good #happy\r\n",
+    )
+    .unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::Obsidian, dir_path).unwrap();
+    assert_eq!(drafts.len(), 1);
+
+    assert_eq!(drafts[0].content, "Feeling good #happy");
+    assert_eq!(drafts[0].tags, vec!["daily", "happy"]);
+}
+
+#[test]
+fn plain_text() {
+    let temp_dir = TempDir::new("foreign_import_plain");
+    let file_path = temp_dir.dir_path.join("diary.txt");
+    fs::write(
+        &file_path,
+        "My diary\n\
+         \n\
+         2024-05-09 (Thursday)\n\
+         ==========\n\
+         Started the #garden\n\
+         \n\
+         ## 2024-05-10\n\
+         Rain all day\n\
+         2024-05-10 was a text line in the wrong place\n",
+    )
+    .unwrap();
+
+    let drafts = read_foreign_entries(ForeignFormat::Plain, &file_path).unwrap();
+    assert_eq!(drafts.len(), 2);
+
+    assert_eq!(drafts[0].title, "2024_05_09_Thursday");
+    assert_eq!(drafts[0].content, "Started the #garden");
+    assert_eq!(drafts[0].tags, vec!["garden"]);
+
+    assert_eq!(drafts[1].title, "2024_05_10_Friday");
+    assert_eq!(
+        drafts[1].content,
+        "Rain all day\n2024-05-10 was a text line in the wrong place"
+    );
+}
@@ -0,0 +1,208 @@
+use std::{fs, path::PathBuf};
+
+use backend::*;
+use chrono::{TimeZone, Utc};
+use git2::Repository;
+
+use crate::common::TempDir;
+
+fn create_provider(file_path: PathBuf) -> GitDataProvide<JsonDataProvide> {
+    GitDataProvide::new(JsonDataProvide::new(file_path.clone()), file_path).unwrap()
+}
+
+fn create_draft(day: u32) -> EntryDraft {
+    EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        format!("2024_05_{day:02}"),
+        Vec::new(),
+        None,
+    )
+    .with_content(format!("Content {day}"))
+}
+
+#[tokio::test]
+async fn commit_per_change() {
+    let temp_dir = TempDir::new("git_versioned_commit_per_change");
+    let file_path = temp_dir.dir_path.join("entries.json");
+    let provider = create_provider(file_path);
+
+    assert!(temp_dir.dir_path.join(".git").exists());
+
+    let entry = provider.add_entry(create_draft(3)).await.unwrap();
+    provider.add_entry(create_draft(4)).await.unwrap();
+
+    let mut updated = entry.clone();
+    updated.content.push_str(" updated");
+    provider.update_entry(updated).await.unwrap();
+    provider.remove_entry(entry.id).await.unwrap();
+
+    let messages: Vec<String> = provider
+        .versions()
+        .unwrap()
+        .into_iter()
+        .map(|v| v.message)
+        .collect();
+
+    assert_eq!(
+        messages,
+        vec![
+            "remove 2024_05_03",
+            "update 2024_05_03",
+            "add 2024_05_04",
+            "add 2024_05_03"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn checkout_version() {
+    let temp_dir = TempDir::new("git_versioned_checkout_version");
+    let file_path = temp_dir.dir_path.join("entries.json");
+    let provider = create_provider(file_path);
+
+    provider.add_entry(create_draft(3)).await.unwrap();
+    provider.add_entry(create_draft(4)).await.unwrap();
+    provider
+        .import_entries(EntriesDTO::new(vec![create_draft(5)]))
+        .await
+        .unwrap();
+
+    let versions = provider.versions().unwrap();
+    assert_eq!(versions.len(), 3);
+    assert_eq!(versions[0].message, "import 1 entries");
+
+    let checkout_path = temp_dir.dir_path.join("checkout").join("entries.json");
+    let oldest = &versions.last().unwrap().commit_id;
+    assert!(provider.checkout_version(oldest, &checkout_path).unwrap());
+
+    let old_entries = JsonDataProvide::new(checkout_path)
+        .load_all_entries()
+        .await
+        .unwrap();
+
+    assert_eq!(old_entries.len(), 1);
+    assert_eq!(old_entries[0].content, String::from("Content 3"));
+}
+
+#[tokio::test]
+async fn staged_changes_not_committed() {
+    let temp_dir = TempDir::new("git_versioned_staged_changes");
+    let repo = Repository::init(&temp_dir.dir_path).unwrap();
+
+    fs::write(temp_dir.dir_path.join("notes.txt"), "Staged by the user").unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path("notes.txt".as_ref()).unwrap();
+    index.write().unwrap();
+
+    let provider = create_provider(temp_dir.dir_path.join("entries.json"));
+    provider.add_entry(create_draft(3)).await.unwrap();
+
+    let head_tree = repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(head_tree.get_name("entries.json").is_some());
+    assert!(head_tree.get_name("notes.txt").is_none());
+
+    let mut index = repo.index().unwrap();
+    index.read(true).unwrap();
+    assert!(index.get_path("notes.txt".as_ref(), 0).is_some());
+    assert_eq!(
+        index.get_path("entries.json".as_ref(), 0).unwrap().id,
+        head_tree.get_name("entries.json").unwrap().id()
+    );
+}
+
+#[tokio::test]
+async fn user_commit_keeps_changes() {
+    let temp_dir = TempDir::new("git_versioned_user_commit");
+    let repo = Repository::init(&temp_dir.dir_path).unwrap();
+    let file_path = temp_dir.dir_path.join("entries.json");
+    let provider = create_provider(file_path.clone());
+
+    provider.add_entry(create_draft(3)).await.unwrap();
+    provider.add_entry(create_draft(4)).await.unwrap();
+
+    // Commit the index like a plain `git commit` of the user would do.
+    let mut index = repo.index().unwrap();
+    index.read(true).unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    let signature = git2::Signature::now("user", "user@localhost").unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "user commit",
+        &tree,
+        &[&parent],
+    )
+    .unwrap();
+
+    let head_tree = repo.head().unwrap().peel_to_tree().unwrap();
+    let blob = repo
+        .find_blob(head_tree.get_name("entries.json").unwrap().id())
+        .unwrap();
+    assert_eq!(blob.content(), fs::read(&file_path).unwrap());
+
+    let statuses = repo.statuses(None).unwrap();
+    assert!(statuses.is_empty());
+}
+
+#[tokio::test]
+async fn repo_in_parent_dir_used() {
+    let temp_dir = TempDir::new("git_versioned_repo_in_parent_dir");
+    let repo = Repository::init(&temp_dir.dir_path).unwrap();
+    let file_path = temp_dir.dir_path.join("journal").join("entries.json");
+    fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+
+    let provider = create_provider(file_path);
+    provider.add_entry(create_draft(3)).await.unwrap();
+
+    assert!(!temp_dir.dir_path.join("journal").join(".git").exists());
+    let head_tree = repo.head().unwrap().peel_to_tree().unwrap();
+    assert!(head_tree.get_path("journal/entries.json".as_ref()).is_ok());
+}
+
+#[tokio::test]
+async fn repo_further_up_not_used() {
+    let temp_dir = TempDir::new("git_versioned_repo_further_up");
+    let repo = Repository::init(&temp_dir.dir_path).unwrap();
+    let journal_dir = temp_dir.dir_path.join("notes").join("journal");
+    fs::create_dir_all(&journal_dir).unwrap();
+
+    let provider = create_provider(journal_dir.join("entries.json"));
+    provider.add_entry(create_draft(3)).await.unwrap();
+
+    assert!(journal_dir.join(".git").exists());
+    assert!(repo.head().is_err());
+    assert_eq!(provider.versions().unwrap().len(), 1);
+}
+
+#[cfg(feature = "sqlite")]
+#[tokio::test]
+async fn sqlite_wal_changes_committed() {
+    let temp_dir = TempDir::new("git_versioned_sqlite_wal");
+    let file_path = temp_dir.dir_path.join("entries.db");
+    let sqlite = SqliteDataProvide::from_file(file_path.clone(), SqliteDurability::WalFull)
+        .await
+        .unwrap();
+    let provider = GitDataProvide::new(sqlite, file_path).unwrap();
+
+    provider.add_entry(create_draft(3)).await.unwrap();
+
+    let versions = provider.versions().unwrap();
+    let checkout_path = temp_dir.dir_path.join("checkout").join("entries.db");
+    assert!(
+        provider
+            .checkout_version(&versions[0].commit_id, &checkout_path)
+            .unwrap()
+    );
+
+    let committed = SqliteDataProvide::from_file(checkout_path, SqliteDurability::default())
+        .await
+        .unwrap()
+        .load_all_entries()
+        .await
+        .unwrap();
+
+    assert_eq!(committed.len(), 1);
+    assert_eq!(committed[0].content, String::from("Content 3"));
+}
@@ -0,0 +1,221 @@
+use std::future::Future;
+
+use backend::*;
+use chrono::{TimeZone, Utc};
+use tokio::net::TcpListener;
+
+const TOKEN: &str = "secret-token";
+
+fn create_draft(day: u32, content: &str) -> EntryDraft {
+    EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        format!("2024_05_{day:02}"),
+        vec![String::from("Tag_1")],
+        None,
+    )
+    .with_content(String::from(content))
+}
+
+/// Runs the test with an HTTP provider connected to a server serving an in-memory SQLite
+/// provider, which is stopped once the test is done. The test gets the URL of the server too.
+async fn run_with_server<F, Fut>(client_token: Option<&str>, test: F)
+where
+    F: FnOnce(HttpDataProvide, String) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/", listener.local_addr().unwrap());
+
+    let client = HttpDataProvide::new(url.clone()).with_token(client_token.map(String::from));
+    let allowed_hosts = vec![String::from("journal.lan")];
+
+    tokio::select! {
+        result = serve(provider, listener, String::from(TOKEN), allowed_hosts) => {
+            panic!("Server stopped before the test is done. Result: {result:?}");
+        }
+        () = test(client, url) => {}
+    }
+}
+
+#[tokio::test]
+async fn add_update_remove() {
+    run_with_server(Some(TOKEN), |client, _| async move {
+        let added = client
+            .add_entry(create_draft(1, "08:00 first"))
+            .await
+            .unwrap();
+        client
+            .add_entry(create_draft(2, "09:00 second"))
+            .await
+            .unwrap();
+
+        let mut entries = client.load_all_entries().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert!(entries.contains(&added));
+
+        let mut entry = entries.remove(0);
+        entry.content.push_str("\n10:00 updated");
+        let updated = client.update_entry(entry.clone()).await.unwrap();
+        assert_eq!(updated.content, entry.content);
+        assert!(updated.updated_at >= entry.updated_at);
+
+        client.remove_entry(updated.id).await.unwrap();
+
+        let entries = client.load_all_entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_ne!(entries[0].id, updated.id);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn duplicate_day() {
+    run_with_server(Some(TOKEN), |client, _| async move {
+        let existing = client
+            .add_entry(create_draft(1, "08:00 first"))
+            .await
+            .unwrap();
+
+        let err = client
+            .add_entry(create_draft(1, "09:00 same day"))
+            .await
+            .unwrap_err();
+
+        match err {
+            ModifyEntryError::DuplicateDay { day, existing_id } => {
+                assert_eq!(day, existing.date.date_naive());
+                assert_eq!(existing_id, existing.id);
+            }
+            err => panic!("Expected duplicate day error. Found: {err}"),
+        }
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn unauthorized() {
+    run_with_server(Some("wrong"), |client, _| async move {
+        assert!(client.load_all_entries().await.is_err());
+        assert!(client.add_entry(create_draft(1, "first")).await.is_err());
+    })
+    .await;
+
+    run_with_server(None, |client, _| async move {
+        assert!(client.load_all_entries().await.is_err());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn export_import() {
+    run_with_server(Some(TOKEN), |client, _| async move {
+        let first = client
+            .add_entry(create_draft(1, "08:00 first"))
+            .await
+            .unwrap();
+        client
+            .add_entry(create_draft(2, "09:00 second"))
+            .await
+            .unwrap();
+
+        let dto = client.get_export_object(&[first.id]).await.unwrap();
+        assert_eq!(dto.entries.len(), 1);
+        assert_eq!(dto.entries[0].content, first.content);
+
+        let summary = client
+            .import_entries_with_strategy(
+                EntriesDTO::new(vec![
+                    create_draft(1, "10:00 appended"),
+                    create_draft(3, "07:00 new day"),
+                ]),
+                ImportStrategy::AppendLines,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(summary.created, 1);
+        assert_eq!(summary.merged, 1);
+        assert_eq!(summary.skipped, 0);
+
+        client
+            .import_entries(EntriesDTO::new(vec![create_draft(4, "06:00 fourth")]))
+            .await
+            .unwrap();
+
+        let entries = client.load_all_entries().await.unwrap();
+        assert_eq!(entries.len(), 4);
+        let merged = entries.iter().find(|e| e.id == first.id).unwrap();
+        assert_eq!(merged.content, "08:00 first\n10:00 appended");
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn search_and_priority() {
+    run_with_server(Some(TOKEN), |client, _| async move {
+        let first = client
+            .add_entry(create_draft(1, "08:00 coffee"))
+            .await
+            .unwrap();
+        client
+            .add_entry(create_draft(2, "09:00 tea"))
+            .await
+            .unwrap();
+
+        let results = client.search("coffee").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, first.id);
+        assert!(!results[0].1.is_empty());
+
+        client.assign_priority_to_entries(2).await.unwrap();
+
+        let entries = client.load_all_entries().await.unwrap();
+        assert!(entries.iter().all(|e| e.priority == Some(2)));
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn import_requires_json() {
+    run_with_server(Some(TOKEN), |_, url| async move {
+        let response = reqwest::Client::new()
+            .post(format!("{url}import"))
+            .bearer_auth(TOKEN)
+            .header(reqwest::header::CONTENT_TYPE, "text/plain")
+            .body(serde_json::to_string(&EntriesDTO::new(vec![create_draft(1, "first")])).unwrap())
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.status(),
+            reqwest::StatusCode::UNSUPPORTED_MEDIA_TYPE
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn reject_unexpected_host() {
+    run_with_server(Some(TOKEN), |client, url| async move {
+        let get_with_host = |host: &'static str| {
+            reqwest::Client::new()
+                .get(format!("{url}entries"))
+                .bearer_auth(TOKEN)
+                .header(reqwest::header::HOST, host)
+                .send()
+        };
+
+        let response = get_with_host("attacker.example:7373").await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::MISDIRECTED_REQUEST);
+
+        for host in ["localhost:7373", "[::1]:7373", "journal.lan"] {
+            let response = get_with_host(host).await.unwrap();
+            assert!(response.status().is_success(), "Host: {host}");
+        }
+
+        assert!(client.load_all_entries().await.is_ok());
+    })
+    .await;
+}
@@ -0,0 +1,358 @@
+use backend::*;
+use chrono::{TimeZone, Utc};
+
+#[cfg(feature = "json")]
+use crate::common::TempDir;
+
+fn create_draft(day: u32, content: &str, tags: &[&str]) -> EntryDraft {
+    EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        format!("2024_05_{day:02}"),
+        tags.iter().map(|tag| String::from(*tag)).collect(),
+        None,
+    )
+    .with_content(String::from(content))
+}
+
+async fn create_provider() -> SqliteDataProvide {
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+
+    provider
+        .add_entry(create_draft(1, "08:00 first\n12:00 third", &["Tag_1"]))
+        .await
+        .unwrap();
+    provider
+        .add_entry(create_draft(2, "09:00 other day", &[]))
+        .await
+        .unwrap();
+
+    provider
+}
+
+fn create_import_dto() -> EntriesDTO {
+    EntriesDTO::new(vec![
+        create_draft(1, "10:00 second\n08:00 first", &["Tag_1", "Tag_2"]),
+        create_draft(2, "09:00 other day", &[]),
+        create_draft(3, "07:00 new day", &[]),
+    ])
+}
+
+async fn get_content(provider: &SqliteDataProvide, title: &str) -> Vec<String> {
+    provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|e| e.title == title)
+        .map(|e| e.content)
+        .collect()
+}
+
+#[tokio::test]
+async fn import_skip() {
+    let provider = create_provider().await;
+
+    let summary = provider
+        .import_entries_with_strategy(create_import_dto(), ImportStrategy::Skip)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 1,
+            merged: 0,
+            skipped: 2
+        }
+    );
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 3);
+    assert_eq!(
+        get_content(&provider, "2024_05_01").await,
+        vec![String::from("08:00 first\n12:00 third")]
+    );
+}
+
+#[tokio::test]
+async fn import_replace() {
+    let provider = create_provider().await;
+
+    let summary = provider
+        .import_entries_with_strategy(create_import_dto(), ImportStrategy::Replace)
+        .await
+        .unwrap();
+
+    // Second day is identical and doesn't need to be replaced.
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 1,
+            merged: 1,
+            skipped: 1
+        }
+    );
+    assert_eq!(
+        get_content(&provider, "2024_05_01").await,
+        vec![String::from("10:00 second\n08:00 first")]
+    );
+}
+
+#[tokio::test]
+async fn import_append_lines() {
+    let provider = create_provider().await;
+
+    let summary = provider
+        .import_entries_with_strategy(create_import_dto(), ImportStrategy::AppendLines)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 1,
+            merged: 1,
+            skipped: 1
+        }
+    );
+
+    let entry = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == "2024_05_01")
+        .unwrap();
+    assert_eq!(entry.content, "08:00 first\n10:00 second\n12:00 third");
+    assert_eq!(entry.tags.len(), 2);
+}
+
+#[tokio::test]
+async fn import_keep_both() {
+    let provider = create_provider().await;
+
+    let summary = provider
+        .import_entries_with_strategy(create_import_dto(), ImportStrategy::KeepBoth)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 1,
+            merged: 1,
+            skipped: 1
+        }
+    );
+    assert_eq!(
+        get_content(&provider, "2024_05_01").await,
+        vec![String::from(
+            "08:00 first\n12:00 third\n10:00 second\n08:00 first"
+        )]
+    );
+}
+
+#[tokio::test]
+async fn import_into_other_notebook() {
+    let provider = create_provider().await;
+    let drafts = create_import_dto()
+        .entries
+        .into_iter()
+        .map(|draft| draft.with_notebook(String::from("work")))
+        .collect();
+
+    let summary = provider
+        .import_entries_with_strategy(EntriesDTO::new(drafts), ImportStrategy::Skip)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 3,
+            merged: 0,
+            skipped: 0
+        }
+    );
+    assert_eq!(
+        get_content(&provider, "2024_05_01").await,
+        vec![
+            String::from("08:00 first\n12:00 third"),
+            String::from("10:00 second\n08:00 first")
+        ]
+    );
+}
+
+#[test]
+fn merge_log_lines_in_time_order() {
+    let existing = "Header\n08:00 first\n12:00 third";
+    let imported = "Header\n13:00 fourth\n10:00 second\n\nFooter";
+
+    assert_eq!(
+        merge_log_lines(existing, imported),
+        "Header\n08:00 first\n10:00 second\n12:00 third\n13:00 fourth\nFooter"
+    );
+}
+
+#[test]
+fn parse_strategy() {
+    for strategy in ImportStrategy::ALL {
+        assert_eq!(
+            strategy.to_string().parse::<ImportStrategy>().unwrap(),
+            strategy
+        );
+    }
+
+    assert!("merge".parse::<ImportStrategy>().is_err());
+}
+
+#[tokio::test]
+async fn import_match_uuid() {
+    let provider = create_provider().await;
+
+    // Move the first entry to another day, which must still be matched by its UUID.
+    let mut draft = provider
+        .get_export_object(&[1])
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    draft.date = Utc.with_ymd_and_hms(2024, 5, 4, 0, 0, 0).unwrap();
+    draft.content = String::from("08:00 moved");
+
+    let summary = provider
+        .import_entries_with_strategy(
+            EntriesDTO::new(vec![draft.clone()]),
+            ImportStrategy::Replace,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 0,
+            merged: 1,
+            skipped: 0,
+        }
+    );
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    let moved = entries.iter().find(|e| e.id == 1).unwrap();
+    assert_eq!(moved.uuid, draft.uuid);
+    assert_eq!(moved.date, draft.date);
+    assert_eq!(moved.content, draft.content);
+}
+
+/// Creates an import moving the first entry of the provider to the day of the second one after a
+/// new day has been created, which fails on the second entry.
+async fn create_failing_import<D: DataProvider>(provider: &D) -> EntriesDTO {
+    let first_id = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == "2024_05_01")
+        .unwrap()
+        .id;
+    let mut draft = provider
+        .get_export_object(&[first_id])
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    draft.date = Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap();
+
+    EntriesDTO::new(vec![create_draft(3, "07:00 new day", &[]), draft])
+}
+
+#[tokio::test]
+async fn import_failure_rolled_back() {
+    let provider = create_provider().await;
+    let entries_dto = create_failing_import(&provider).await;
+
+    let err = provider
+        .import_entries_with_strategy(entries_dto, ImportStrategy::Replace)
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        err.downcast_ref::<ModifyEntryError>(),
+        Some(ModifyEntryError::DuplicateDay { .. })
+    ));
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+    assert!(get_content(&provider, "2024_05_03").await.is_empty());
+}
+
+#[cfg(feature = "json")]
+#[tokio::test]
+async fn import_failure_reports_applied_entries() {
+    let temp_dir = TempDir::new("import_failure_reports_applied_entries");
+    let provider = JsonDataProvide::new(temp_dir.dir_path.join("entries.json"));
+    provider
+        .add_entry(create_draft(1, "08:00 first", &[]))
+        .await
+        .unwrap();
+    provider
+        .add_entry(create_draft(2, "09:00 other day", &[]))
+        .await
+        .unwrap();
+    let entries_dto = create_failing_import(&provider).await;
+
+    let err = provider
+        .import_entries_with_strategy(entries_dto, ImportStrategy::Replace)
+        .await
+        .unwrap_err();
+
+    // Back-ends without transactions keep the entries imported before the failure.
+    assert_eq!(
+        err.to_string(),
+        "Import failed after applying 1 entries (1 created, 0 merged, 0 skipped)"
+    );
+    assert!(matches!(
+        err.downcast_ref::<ModifyEntryError>(),
+        Some(ModifyEntryError::DuplicateDay { .. })
+    ));
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn import_version_100_twice() {
+    let provider = create_provider().await;
+    let json = r#"{"version": 100, "entries": [
+        {"date": "2024-05-03T00:00:00Z", "title": "2024_05_03", "content": "07:00 old", "tags": [], "priority": null}
+    ]}"#;
+
+    let (dto, _) = EntriesDTO::parse_versioned(json.as_bytes()).unwrap();
+    let summary = provider
+        .import_entries_with_strategy(dto, ImportStrategy::Skip)
+        .await
+        .unwrap();
+    assert_eq!(summary.created, 1);
+
+    // Move the imported entry to another day, so it can be matched by its UUID only.
+    let mut imported = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == "2024_05_03")
+        .unwrap();
+    imported.date = Utc.with_ymd_and_hms(2024, 5, 4, 0, 0, 0).unwrap();
+    provider.update_entry(imported).await.unwrap();
+
+    let (dto, _) = EntriesDTO::parse_versioned(json.as_bytes()).unwrap();
+    let summary = provider
+        .import_entries_with_strategy(dto, ImportStrategy::Skip)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        summary,
+        ImportSummary {
+            created: 0,
+            merged: 0,
+            skipped: 1,
+        }
+    );
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 3);
+}
@@ -52,7 +52,7 @@ async fn add_entry() {
     let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
 
     let mut entry_draft = EntryDraft::new(
-        Utc.with_ymd_and_hms(2023, 3, 23, 1, 1, 1).unwrap(),
+        Utc.with_ymd_and_hms(2023, 3, 24, 1, 1, 1).unwrap(),
         String::from("Title added"),
         vec![String::from("Tag_1"), String::from("Tag_3")],
         Some(1),
@@ -74,6 +74,56 @@ async fn add_entry() {
     );
 }
 
+#[tokio::test]
+async fn duplicate_day() {
+    let temp_file = TempFile::new("json_duplicate_day");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title added"), Vec::new(), None);
+    let err = provider.add_entry(draft).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 1, .. }
+    ));
+
+    let mut entry = provider.load_all_entries().await.unwrap().remove(0);
+    entry.date = date;
+    let err = provider.update_entry(entry).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 1, .. }
+    ));
+
+    // Updating the entry on its own day is allowed
+    let mut entry = provider.load_all_entries().await.unwrap().remove(1);
+    entry.date = date;
+    assert!(provider.update_entry(entry).await.is_ok());
+}
+
+#[tokio::test]
+async fn same_day_in_other_notebook() {
+    let temp_file = TempFile::new("json_same_day_in_other_notebook");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title work"), Vec::new(), None)
+        .with_notebook(String::from("work"));
+    let entry = provider.add_entry(draft).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries.last().unwrap().notebook, "work");
+    assert_eq!(entries[0].notebook, DEFAULT_NOTEBOOK);
+
+    let mut entry = entry;
+    entry.notebook = String::from(DEFAULT_NOTEBOOK);
+    assert!(matches!(
+        provider.update_entry(entry).await.unwrap_err(),
+        ModifyEntryError::DuplicateDay { existing_id: 1, .. }
+    ));
+}
+
 #[tokio::test]
 async fn remove_entry() {
     let temp_file = TempFile::new("json_remove_entry");
@@ -86,6 +136,70 @@ async fn remove_entry() {
     assert_eq!(entries[0].id, 0);
 }
 
+#[tokio::test]
+async fn trash_restore() {
+    let temp_file = TempFile::new("json_trash_restore");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 1);
+    assert!(trash[0].deleted_at.is_some());
+
+    let restored = provider.restore_entry(1).await.unwrap();
+    assert_eq!(restored.title, "Title 2");
+    assert!(restored.deleted_at.is_none());
+
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn trash_restore_duplicate_day() {
+    let temp_file = TempFile::new("json_trash_restore_duplicate_day");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title new"), Vec::new(), None);
+    let new_entry = provider.add_entry(draft).await.unwrap();
+    assert_ne!(new_entry.id, 1);
+
+    assert!(matches!(
+        provider.restore_entry(1).await.unwrap_err(),
+        ModifyEntryError::DuplicateDay { .. }
+    ));
+}
+
+#[tokio::test]
+async fn trash_purge() {
+    let temp_file = TempFile::new("json_trash_purge");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+    provider.remove_entry(0).await.unwrap();
+
+    provider.purge_entry(1).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 0);
+
+    let purged = provider
+        .purge_trash(Utc::now() - chrono::Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = provider.purge_trash(Utc::now()).await.unwrap();
+    assert_eq!(purged, 1);
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert!(provider.load_all_entries().await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn update_entry() {
     let temp_file = TempFile::new("json_update_entry");
@@ -141,6 +255,19 @@ async fn export_import() {
     assert_eq!(dto_source, dto_dist);
 }
 
+#[tokio::test]
+async fn update_timestamps() {
+    let temp_file = TempFile::new("json_update_timestamps");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    let entry = provider.load_all_entries().await.unwrap().remove(0);
+    let updated = provider.update_entry(entry.clone()).await.unwrap();
+
+    assert_eq!(updated.created_at, entry.created_at);
+    assert!(updated.updated_at > entry.updated_at);
+    assert_eq!(provider.load_all_entries().await.unwrap()[0], updated);
+}
+
 #[tokio::test]
 async fn assign_priority() {
     let temp_file = TempFile::new("json_assign_priority");
@@ -153,3 +280,210 @@ async fn assign_priority() {
     assert_eq!(entries[0].priority, Some(3));
     assert_eq!(entries[1].priority, Some(1));
 }
+
+#[tokio::test]
+async fn search() {
+    let temp_file = TempFile::new("json_search");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    let mut entry = provider.load_all_entries().await.unwrap().pop().unwrap();
+    entry.content = String::from("Entry 2 entry content");
+    provider.update_entry(entry).await.unwrap();
+
+    let results = provider.search("ENTRY").await.unwrap();
+    assert_eq!(results.len(), 2);
+    // Entries with more matches come first
+    assert_eq!(results[0].0, 1);
+    assert_eq!(
+        results[0].1,
+        vec![MatchSpan::new(0, 5), MatchSpan::new(8, 13)]
+    );
+    assert_eq!(results[1].0, 0);
+    assert_eq!(results[1].1, vec![MatchSpan::new(8, 13)]);
+
+    let results = provider.search("entry 1").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, 0);
+
+    assert!(provider.search("missing").await.unwrap().is_empty());
+    assert!(provider.search("  ").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn backfill_missing_fields() {
+    let temp_file = TempFile::new("json_backfill_missing_fields");
+    let suffixes = ["1.bak", "2.bak"];
+    remove_siblings(&temp_file, &suffixes);
+
+    let legacy_text = r#"[{"id":0,"date":"2024-05-03T00:00:00Z","title":"2024_05_03_Friday","content":"","tags":[],"priority":null}]"#;
+    std::fs::write(&temp_file.file_path, legacy_text).unwrap();
+    let provider = JsonDataProvide::new(temp_file.file_path.clone()).with_backup_count(2);
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert!(!entries[0].uuid.is_nil());
+    assert_eq!(entries[0].created_at, entries[0].date);
+    assert_eq!(entries[0].updated_at, entries[0].date);
+
+    // Loading must leave the file and its backups untouched while the backfilled fields stay
+    // the same on each load.
+    let reloaded = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries, reloaded);
+    assert_eq!(
+        std::fs::read_to_string(&temp_file.file_path).unwrap(),
+        legacy_text
+    );
+    let backup_path =
+        |suffix: &str| PathBuf::from(format!("{}.{suffix}", temp_file.file_path.display()));
+    assert!(!backup_path("1.bak").exists());
+
+    // Backfilled fields are persisted with the next write.
+    let mut entry = entries[0].clone();
+    entry.content = String::from("Changed");
+    provider.update_entry(entry).await.unwrap();
+
+    let stored: Vec<Entry> =
+        serde_json::from_str(&std::fs::read_to_string(&temp_file.file_path).unwrap()).unwrap();
+    assert_eq!(stored[0].uuid, entries[0].uuid);
+    assert_eq!(
+        std::fs::read_to_string(backup_path("1.bak")).unwrap(),
+        legacy_text
+    );
+
+    remove_siblings(&temp_file, &suffixes);
+}
+
+#[tokio::test]
+async fn import_existing_uuid() {
+    let temp_file = TempFile::new("json_import_existing_uuid");
+    let provider = create_provide_with_two_entries(temp_file.file_path.clone()).await;
+
+    let dto = provider.get_export_object(&[0]).await.unwrap();
+    provider.import_entries(dto).await.unwrap();
+
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+}
+
+fn remove_siblings(temp_file: &TempFile, suffixes: &[&str]) {
+    for suffix in suffixes {
+        let path = PathBuf::from(format!("{}.{suffix}", temp_file.file_path.display()));
+        if path.exists() {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn rotating_backups() {
+    let temp_file = TempFile::new("json_rotating_backups");
+    let suffixes = ["1.bak", "2.bak", "3.bak"];
+    remove_siblings(&temp_file, &suffixes);
+
+    let provider = JsonDataProvide::new(temp_file.file_path.clone()).with_backup_count(2);
+    for idx in 0..4 {
+        let date = Utc.with_ymd_and_hms(2024, 5, idx + 1, 0, 0, 0).unwrap();
+        let draft = EntryDraft::new(date, format!("Title {idx}"), Vec::new(), None);
+        provider.add_entry(draft).await.unwrap();
+    }
+
+    let backup_path = |suffix: &str| format!("{}.{suffix}", temp_file.file_path.display());
+    let newest: Vec<Entry> =
+        serde_json::from_str(&std::fs::read_to_string(backup_path("1.bak")).unwrap()).unwrap();
+    let oldest: Vec<Entry> =
+        serde_json::from_str(&std::fs::read_to_string(backup_path("2.bak")).unwrap()).unwrap();
+
+    assert_eq!(newest.len(), 3);
+    assert_eq!(oldest.len(), 2);
+    assert!(!PathBuf::from(backup_path("3.bak")).exists());
+    assert!(!PathBuf::from(backup_path("tmp")).exists());
+
+    remove_siblings(&temp_file, &suffixes);
+}
+
+#[tokio::test]
+async fn recover_from_backup() {
+    let temp_file = TempFile::new("json_recover_from_backup");
+    let suffixes = ["1.bak", "2.bak", "corrupted"];
+    remove_siblings(&temp_file, &suffixes);
+
+    let provider = JsonDataProvide::new(temp_file.file_path.clone()).with_backup_count(2);
+    let draft_1 = EntryDraft::new(Utc::now(), String::from("Title 1"), Vec::new(), None);
+    let date_2 = Utc.with_ymd_and_hms(2023, 3, 23, 1, 1, 1).unwrap();
+    let draft_2 = EntryDraft::new(date_2, String::from("Title 2"), Vec::new(), None);
+    provider.add_entry(draft_1).await.unwrap();
+    provider.add_entry(draft_2).await.unwrap();
+    assert!(provider.take_load_warning().is_none());
+
+    // Simulate a truncated write
+    std::fs::write(&temp_file.file_path, "[{\"id\":0,").unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].title, String::from("Title 1"));
+    assert!(provider.take_load_warning().is_some());
+    assert!(provider.take_load_warning().is_none());
+
+    // Main file is restored and the corrupted one is kept.
+    let entries = JsonDataProvide::new(temp_file.file_path.clone())
+        .load_all_entries()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    let corrupted_path = format!("{}.corrupted", temp_file.file_path.display());
+    assert_eq!(
+        std::fs::read_to_string(corrupted_path).unwrap(),
+        "[{\"id\":0,"
+    );
+
+    remove_siblings(&temp_file, &suffixes);
+}
+
+#[tokio::test]
+async fn corrupted_without_backups() {
+    let temp_file = TempFile::new("json_corrupted_without_backups");
+    std::fs::write(&temp_file.file_path, "[{\"id\":0,").unwrap();
+
+    let provider = JsonDataProvide::new(temp_file.file_path.clone());
+
+    assert!(provider.load_all_entries().await.is_err());
+}
+
+#[tokio::test]
+async fn newer_data_not_recovered() {
+    let temp_file = TempFile::new("json_newer_data_not_recovered");
+    let suffixes = ["1.bak", "corrupted"];
+    remove_siblings(&temp_file, &suffixes);
+
+    let provider = JsonDataProvide::new(temp_file.file_path.clone()).with_backup_count(1);
+    // Add two entries to have a valid backup.
+    for idx in 0..2 {
+        let date = Utc.with_ymd_and_hms(2024, 5, idx + 1, 0, 0, 0).unwrap();
+        let draft = EntryDraft::new(date, format!("Title {idx}"), Vec::new(), None);
+        provider.add_entry(draft).await.unwrap();
+    }
+
+    // Data written by a newer version must be reported without restoring the backup.
+    let newer_data = format!(
+        r#"{{"version": {}, "entries": []}}"#,
+        TRANSFER_DATA_VERSION + 1
+    );
+    std::fs::write(&temp_file.file_path, &newer_data).unwrap();
+
+    let err = provider.load_all_entries().await.unwrap_err();
+    assert!(err.to_string().contains("newer than the supported version"));
+    assert!(provider.find_recovery_backup().await.unwrap().is_none());
+
+    // Valid JSON which doesn't match the entries isn't recovered either.
+    std::fs::write(&temp_file.file_path, r#"[{"id": "first"}]"#).unwrap();
+    assert!(provider.load_all_entries().await.is_err());
+    assert!(provider.find_recovery_backup().await.unwrap().is_none());
+
+    assert_eq!(
+        std::fs::read_to_string(&temp_file.file_path).unwrap(),
+        r#"[{"id": "first"}]"#
+    );
+    assert!(provider.take_load_warning().is_none());
+    assert!(!PathBuf::from(format!("{}.corrupted", temp_file.file_path.display())).exists());
+
+    remove_siblings(&temp_file, &suffixes);
+}
@@ -0,0 +1,164 @@
+use backend::*;
+use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+
+fn create_draft(day: u32, content: &str) -> EntryDraft {
+    EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        format!("2024_05_{day:02}"),
+        Vec::new(),
+        None,
+    )
+    .with_content(String::from(content))
+}
+
+fn time(hour: u32, min: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, min, 0).unwrap()
+}
+
+fn date_time(day: u32, hour: u32, min: u32) -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2024, 5, day)
+        .unwrap()
+        .and_time(time(hour, min))
+}
+
+fn texts(lines: &[EntryLogLine]) -> Vec<&str> {
+    lines.iter().map(|l| l.line.text.as_str()).collect()
+}
+
+#[test]
+fn parse_and_render() {
+    let line = LogLine::parse("08:30 Coffee with #friends and #work, again #friends").unwrap();
+
+    assert_eq!(line.time, time(8, 30));
+    assert_eq!(line.text, "Coffee with #friends and #work, again #friends");
+    assert_eq!(
+        line.tags,
+        vec![String::from("friends"), String::from("work")]
+    );
+    assert_eq!(
+        line.to_string(),
+        "08:30 Coffee with #friends and #work, again #friends"
+    );
+
+    assert!(LogLine::parse("Not a log line").is_none());
+    assert!(LogLine::parse("# 08:30 heading").is_none());
+    assert_eq!(LogLine::parse("09:00").unwrap().text, "");
+
+    let mut line = LogLine::new(NaiveTime::from_hms_opt(10, 15, 42).unwrap(), "Run".into());
+    line.tags.push(String::from("sport"));
+    assert_eq!(line.time, time(10, 15));
+    assert_eq!(line.to_string(), "10:15 Run #sport");
+
+    let content = "Notes\n08:00 first\nmore notes\n09:00 second #tag\n";
+    let lines = parse_log_lines(content);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1].tags, vec![String::from("tag")]);
+    assert_eq!(
+        append_log_line(content, &LogLine::new(time(10, 0), "third".into())),
+        "Notes\n08:00 first\nmore notes\n09:00 second #tag\n10:00 third"
+    );
+    assert_eq!(
+        append_log_line("", &LogLine::new(time(10, 0), "first".into())),
+        "10:00 first"
+    );
+}
+
+#[tokio::test]
+async fn append_and_query() {
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    let first = provider
+        .add_entry(create_draft(1, "Notes\n08:00 first\n12:00 second"))
+        .await
+        .unwrap();
+    provider
+        .add_entry(create_draft(2, "07:00 third\n"))
+        .await
+        .unwrap();
+
+    let entry = provider
+        .append_log_line(first.id, LogLine::new(time(18, 0), "evening #home".into()))
+        .await
+        .unwrap();
+    assert_eq!(
+        entry.content,
+        "Notes\n08:00 first\n12:00 second\n18:00 evening #home"
+    );
+    assert!(entry.updated_at >= first.updated_at);
+    assert_eq!(provider.load_all_entries().await.unwrap()[1], entry);
+
+    let lines = provider
+        .query_log_lines(date_time(1, 12, 0), date_time(2, 7, 0))
+        .await
+        .unwrap();
+    assert_eq!(texts(&lines), vec!["second", "evening #home"]);
+    assert_eq!(lines[1].entry_id, first.id);
+    assert_eq!(lines[1].line.tags, vec![String::from("home")]);
+    assert_eq!(lines[1].date_time(), date_time(1, 18, 0));
+
+    // The queried lines must match the ones parsed from the content.
+    let entries = provider.load_all_entries().await.unwrap();
+    let all_lines = provider
+        .query_log_lines(date_time(1, 0, 0), date_time(3, 0, 0))
+        .await
+        .unwrap();
+    assert_eq!(
+        all_lines,
+        log_lines_in_range(&entries, date_time(1, 0, 0), date_time(3, 0, 0))
+    );
+    assert_eq!(all_lines.len(), 4);
+
+    assert!(
+        provider
+            .append_log_line(100, LogLine::new(time(8, 0), "missing".into()))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn append_to_trashed_entry() {
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    let entry = provider
+        .add_entry(create_draft(1, "08:00 first"))
+        .await
+        .unwrap();
+    provider.remove_entry(entry.id).await.unwrap();
+
+    let err = provider
+        .append_log_line(entry.id, LogLine::new(time(9, 0), "trashed".into()))
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("Entry with the id {} doesn't exist", entry.id)
+    );
+
+    // The entry in the trash must stay unchanged.
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash[0].content, "08:00 first");
+    let lines = provider
+        .query_log_lines(date_time(1, 0, 0), date_time(2, 0, 0))
+        .await
+        .unwrap();
+    assert!(lines.is_empty());
+}
+
+#[tokio::test]
+async fn lines_follow_content_changes() {
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    let mut entry = provider
+        .add_entry(create_draft(1, "08:00 first\n09:00 second"))
+        .await
+        .unwrap();
+
+    entry.content = String::from("10:00 changed");
+    provider.update_entry(entry.clone()).await.unwrap();
+
+    let range = (date_time(1, 0, 0), date_time(2, 0, 0));
+    let lines = provider.query_log_lines(range.0, range.1).await.unwrap();
+    assert_eq!(texts(&lines), vec!["changed"]);
+
+    provider.remove_entry(entry.id).await.unwrap();
+    let lines = provider.query_log_lines(range.0, range.1).await.unwrap();
+    assert!(lines.is_empty());
+}
@@ -0,0 +1,463 @@
+use std::path::PathBuf;
+
+use backend::*;
+use chrono::{TimeZone, Utc};
+
+use crate::common::TempDir;
+
+async fn create_provide_with_two_entries(dir_path: PathBuf) -> MarkdownDirDataProvide {
+    let md_provide = MarkdownDirDataProvide::new(dir_path);
+    let mut entry_draft_1 = EntryDraft::new(
+        Utc::now(),
+        String::from("Title 1"),
+        vec![String::from("Tag_1"), String::from("Tag_2")],
+        None,
+    );
+    entry_draft_1.content.push_str("Content entry 1");
+    let mut entry_draft_2 = EntryDraft::new(
+        Utc.with_ymd_and_hms(2023, 3, 23, 1, 1, 1).unwrap(),
+        String::from("Title 2"),
+        Vec::new(),
+        Some(1),
+    );
+    entry_draft_2.content.push_str("Content entry 2");
+
+    md_provide.add_entry(entry_draft_1).await.unwrap();
+    md_provide.add_entry(entry_draft_2).await.unwrap();
+
+    md_provide
+}
+
+#[tokio::test]
+async fn create_provider_with_default_entries() {
+    let temp_dir = TempDir::new("markdown_dir_create_default");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].id, 0);
+    assert_eq!(entries[1].id, 1);
+    assert_eq!(entries[0].title, String::from("Title 1"));
+    assert_eq!(entries[1].title, String::from("Title 2"));
+    assert_eq!(entries[0].priority, None);
+    assert_eq!(entries[1].priority, Some(1));
+}
+
+#[tokio::test]
+async fn add_entry() {
+    let temp_dir = TempDir::new("markdown_dir_add_entry");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let mut entry_draft = EntryDraft::new(
+        Utc.with_ymd_and_hms(2023, 3, 24, 1, 1, 1).unwrap(),
+        String::from("Title added"),
+        vec![String::from("Tag_1"), String::from("Tag_3")],
+        Some(1),
+    );
+    entry_draft.content.push_str("Content entry added");
+
+    provider.add_entry(entry_draft).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 3);
+    assert_eq!(entries[2].id, 2);
+    assert_eq!(entries[2].title, String::from("Title added"));
+    assert_eq!(entries[2].content, String::from("Content entry added"));
+    assert_eq!(entries[2].priority, Some(1));
+    assert_eq!(
+        entries[2].tags,
+        vec![String::from("Tag_1"), String::from("Tag_3")]
+    );
+}
+
+#[tokio::test]
+async fn duplicate_day() {
+    let temp_dir = TempDir::new("markdown_dir_duplicate_day");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title added"), Vec::new(), None);
+    let err = provider.add_entry(draft).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 1, .. }
+    ));
+
+    let mut entry = provider.load_all_entries().await.unwrap().remove(0);
+    entry.date = date;
+    let err = provider.update_entry(entry).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 1, .. }
+    ));
+}
+
+#[tokio::test]
+async fn same_day_in_other_notebook() {
+    let temp_dir = TempDir::new("markdown_dir_same_day_in_other_notebook");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    // Entries of the same day in different notebooks share their titles too.
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title 2"), Vec::new(), None)
+        .with_notebook(String::from("work"));
+    let entry = provider.add_entry(draft).await.unwrap();
+    assert_eq!(entry.notebook, "work");
+    assert!(temp_dir.dir_path.join("Title 2.md").exists());
+    assert!(temp_dir.dir_path.join("work").join("Title 2.md").exists());
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 3);
+    let added = entries.iter().find(|e| e.id == entry.id).unwrap();
+    assert_eq!(added.notebook, "work");
+    assert!(
+        entries
+            .iter()
+            .filter(|e| e.id != entry.id)
+            .all(|e| e.notebook == DEFAULT_NOTEBOOK)
+    );
+
+    // Trash is kept per notebook.
+    provider.remove_entry(entry.id).await.unwrap();
+    assert!(
+        temp_dir
+            .dir_path
+            .join("work")
+            .join(".trash")
+            .join(format!("{}_Title 2.md", entry.id))
+            .exists()
+    );
+    let restored = provider.restore_entry(entry.id).await.unwrap();
+    assert_eq!(restored.notebook, "work");
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 3);
+
+    // Moving the entry to the default notebook conflicts with the entry of the same day
+    let mut moved = restored.clone();
+    moved.notebook = String::from(DEFAULT_NOTEBOOK);
+    assert!(provider.update_entry(moved).await.is_err());
+    assert!(temp_dir.dir_path.join("work").join("Title 2.md").exists());
+
+    // Moving the entry to another notebook moves its file.
+    let mut moved = restored;
+    moved.notebook = String::from("home");
+    provider.update_entry(moved).await.unwrap();
+    assert!(!temp_dir.dir_path.join("work").join("Title 2.md").exists());
+    assert!(temp_dir.dir_path.join("home").join("Title 2.md").exists());
+
+    let draft = EntryDraft::new(date, String::from("Title 2"), Vec::new(), None)
+        .with_notebook(String::from(".hidden"));
+    assert!(provider.add_entry(draft).await.is_err());
+}
+
+#[tokio::test]
+async fn remove_entry() {
+    let temp_dir = TempDir::new("markdown_dir_remove_entry");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, 0);
+}
+
+#[tokio::test]
+async fn trash_restore() {
+    let temp_dir = TempDir::new("markdown_dir_trash_restore");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 1);
+    assert!(trash[0].deleted_at.is_some());
+
+    let restored = provider.restore_entry(1).await.unwrap();
+    assert_eq!(restored.title, "Title 2");
+    assert!(restored.deleted_at.is_none());
+
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn trash_restore_duplicate_day() {
+    let temp_dir = TempDir::new("markdown_dir_trash_restore_duplicate_day");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title new"), Vec::new(), None);
+    let new_entry = provider.add_entry(draft).await.unwrap();
+    assert_ne!(new_entry.id, 1);
+
+    assert!(matches!(
+        provider.restore_entry(1).await.unwrap_err(),
+        ModifyEntryError::DuplicateDay { .. }
+    ));
+}
+
+#[tokio::test]
+async fn trash_purge() {
+    let temp_dir = TempDir::new("markdown_dir_trash_purge");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    provider.remove_entry(1).await.unwrap();
+    provider.remove_entry(0).await.unwrap();
+
+    provider.purge_entry(1).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 0);
+
+    let purged = provider
+        .purge_trash(Utc::now() - chrono::Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = provider.purge_trash(Utc::now()).await.unwrap();
+    assert_eq!(purged, 1);
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert!(provider.load_all_entries().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn update_entry() {
+    let temp_dir = TempDir::new("markdown_dir_update_entry");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let mut entries = provider.load_all_entries().await.unwrap();
+
+    entries[0].content = String::from("Updated Content");
+    entries[0].tags.pop().unwrap();
+    entries[0].priority = Some(2);
+    entries[1].title = String::from("Updated Title");
+    entries[1].tags.push(String::from("Tag_4"));
+    entries[1].priority = None;
+
+    provider.update_entry(entries.pop().unwrap()).await.unwrap();
+    provider.update_entry(entries.pop().unwrap()).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].content, String::from("Updated Content"));
+    assert_eq!(entries[0].tags.len(), 1);
+    assert_eq!(entries[0].priority, Some(2));
+    assert_eq!(entries[1].title, String::from("Updated Title"));
+    assert!(entries[1].tags.contains(&String::from("Tag_4")));
+    assert_eq!(entries[1].priority, None);
+}
+
+#[tokio::test]
+async fn export_import() {
+    let temp_dir_source = TempDir::new("markdown_dir_export_source");
+    let provider_source = create_provide_with_two_entries(temp_dir_source.dir_path.clone()).await;
+
+    let created_ids = [0, 1];
+
+    let dto_source = provider_source
+        .get_export_object(&created_ids)
+        .await
+        .unwrap();
+
+    assert_eq!(dto_source.entries.len(), created_ids.len());
+
+    let temp_dir_dist = TempDir::new("markdown_dir_export_dist");
+    let provider_dist = MarkdownDirDataProvide::new(temp_dir_dist.dir_path.clone());
+
+    provider_dist
+        .import_entries(dto_source.clone())
+        .await
+        .unwrap();
+
+    let dto_dist = provider_dist.get_export_object(&created_ids).await.unwrap();
+
+    assert_eq!(dto_source, dto_dist);
+}
+
+#[tokio::test]
+async fn assign_priority() {
+    let temp_dir = TempDir::new("markdown_dir_assign_priority");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    provider.assign_priority_to_entries(3).await.unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries[0].priority, Some(3));
+    assert_eq!(entries[1].priority, Some(1));
+}
+
+#[tokio::test]
+async fn files_follow_titles() {
+    let temp_dir = TempDir::new("markdown_dir_files_follow_titles");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    assert!(temp_dir.dir_path.join("Title 1.md").exists());
+    assert!(temp_dir.dir_path.join("Title 2.md").exists());
+
+    let mut entries = provider.load_all_entries().await.unwrap();
+    let mut entry = entries.remove(1);
+    entry.title = String::from("2024_05_03_Friday");
+    provider.update_entry(entry).await.unwrap();
+
+    assert!(!temp_dir.dir_path.join("Title 2.md").exists());
+    let file_text =
+        std::fs::read_to_string(temp_dir.dir_path.join("2024_05_03_Friday.md")).unwrap();
+    assert!(file_text.starts_with("+++\n"));
+    assert!(file_text.ends_with("+++\nContent entry 2"));
+
+    let draft = EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, 3, 0, 0, 0).unwrap(),
+        String::from("2024_05_03_Friday"),
+        Vec::new(),
+        None,
+    );
+    assert!(provider.add_entry(draft).await.is_err());
+}
+
+#[tokio::test]
+async fn skip_files_without_front_matter() {
+    let temp_dir = TempDir::new("markdown_dir_skip_files_without_front_matter");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let readme_path = temp_dir.dir_path.join("README.md");
+    std::fs::write(&readme_path, "# Journals\nNotes about this folder").unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+
+    let warning = provider.take_load_warning().unwrap();
+    assert!(warning.contains("README.md"));
+
+    // Skipped files are reported once only.
+    provider.load_all_entries().await.unwrap();
+    assert!(provider.take_load_warning().is_none());
+    assert!(readme_path.exists());
+}
+
+#[tokio::test]
+async fn crlf_front_matter() {
+    let temp_dir = TempDir::new("markdown_dir_crlf_front_matter");
+    std::fs::write(
+        temp_dir.dir_path.join("2024_05_01_Wednesday.md"),
+        "+++\r\nid = 3\r\ndate = \"2024-05-01T00:00:00Z\"\r\ntags = [\"Tag_1\"]\r\n+++\r\nLine 1\r\nLine 2",
+    )
+    .unwrap();
+
+    let provider = MarkdownDirDataProvide::new(temp_dir.dir_path.clone());
+    let entries = provider.load_all_entries().await.unwrap();
+
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].id, 3);
+    assert_eq!(entries[0].title, "2024_05_01_Wednesday");
+    assert_eq!(entries[0].tags, vec!["Tag_1"]);
+    assert_eq!(entries[0].content, "Line 1\r\nLine 2");
+    assert!(provider.take_load_warning().is_none());
+}
+
+#[tokio::test]
+async fn atomic_writes_leave_no_temp_files() {
+    let temp_dir = TempDir::new("markdown_dir_atomic_writes");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let mut entry = provider.load_all_entries().await.unwrap().remove(0);
+    entry.content = String::from("Changed content");
+    provider.update_entry(entry).await.unwrap();
+
+    let file_names: Vec<String> = std::fs::read_dir(&temp_dir.dir_path)
+        .unwrap()
+        .map(|dir_entry| dir_entry.unwrap().file_name().to_string_lossy().to_string())
+        .collect();
+    assert!(file_names.iter().all(|name| !name.ends_with(".tmp")));
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries[0].content, "Changed content");
+}
+
+#[tokio::test]
+async fn backfill_missing_fields() {
+    let temp_dir = TempDir::new("markdown_dir_backfill_missing_fields");
+    let file_path = temp_dir.dir_path.join("2024_05_03_Friday.md");
+    let legacy_text = "+++\nid = 0\ndate = \"2024-05-03T00:00:00Z\"\n+++\nContent";
+    std::fs::write(&file_path, legacy_text).unwrap();
+
+    let provider = MarkdownDirDataProvide::new(temp_dir.dir_path.clone());
+    let entries = provider.load_all_entries().await.unwrap();
+    assert!(!entries[0].uuid.is_nil());
+    assert_eq!(entries[0].created_at, entries[0].date);
+    assert_eq!(entries[0].updated_at, entries[0].date);
+
+    // Loading must leave the file untouched while the backfilled fields stay the same.
+    let reloaded = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries, reloaded);
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), legacy_text);
+}
+
+#[tokio::test]
+async fn keep_files_without_front_matter() {
+    let temp_dir = TempDir::new("markdown_dir_keep_files_without_front_matter");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    let notes_path = temp_dir.dir_path.join("Notes.md");
+    let notes_text = "# Notes\nNot a journal";
+    std::fs::write(&notes_path, notes_text).unwrap();
+
+    // Adding an entry with the name of the skipped file must be refused.
+    let draft = EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap(),
+        String::from("Notes"),
+        Vec::new(),
+        None,
+    );
+    let err = provider.add_entry(draft).await.unwrap_err();
+    assert!(matches!(err, ModifyEntryError::ValidationError(_)));
+
+    // Renaming an entry to the name of the skipped file must be refused too.
+    let mut entry = provider.load_all_entries().await.unwrap().remove(0);
+    entry.title = String::from("Notes");
+    let err = provider.update_entry(entry).await.unwrap_err();
+    assert!(matches!(err, ModifyEntryError::ValidationError(_)));
+
+    assert_eq!(std::fs::read_to_string(&notes_path).unwrap(), notes_text);
+    assert!(temp_dir.dir_path.join("Title 1.md").exists());
+}
+
+#[tokio::test]
+async fn assign_missing_ids() {
+    let temp_dir = TempDir::new("markdown_dir_assign_missing_ids");
+    let provider = create_provide_with_two_entries(temp_dir.dir_path.clone()).await;
+
+    // Move the second entry to the trash to make sure its id isn't reused.
+    provider.remove_entry(1).await.unwrap();
+
+    let file_path = temp_dir.dir_path.join("2024_05_03_Friday.md");
+    std::fs::write(
+        &file_path,
+        "+++\ndate = \"2024-05-03T00:00:00Z\"\n+++\nContent",
+    )
+    .unwrap();
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[1].id, 2);
+    assert_eq!(entries[1].title, "2024_05_03_Friday");
+    assert_eq!(entries[1].content, "Content");
+    assert!(provider.take_load_warning().is_none());
+
+    // Assigned ids are kept in the files.
+    assert!(
+        std::fs::read_to_string(&file_path)
+            .unwrap()
+            .contains("id = 2")
+    );
+    let reloaded = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries, reloaded);
+}
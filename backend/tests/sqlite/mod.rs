@@ -50,7 +50,7 @@ async fn add_entry() {
     let provider = create_provider_with_two_entries().await;
 
     let mut entry_draft = EntryDraft::new(
-        Utc.with_ymd_and_hms(2023, 3, 23, 1, 1, 1).unwrap(),
+        Utc.with_ymd_and_hms(2023, 3, 22, 1, 1, 1).unwrap(),
         String::from("Title added"),
         vec![String::from("Tag_1"), String::from("Tag_3")],
         Some(1),
@@ -72,6 +72,107 @@ async fn add_entry() {
     );
 }
 
+#[tokio::test]
+async fn duplicate_day() {
+    let provider = create_provider_with_two_entries().await;
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title added"), Vec::new(), None);
+    let err = provider.add_entry(draft).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 2, .. }
+    ));
+
+    let mut entry = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.id == 1)
+        .unwrap();
+    entry.date = date;
+    let err = provider.update_entry(entry).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 2, .. }
+    ));
+
+    // Updating the entry on its own day is allowed
+    let mut entry = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.id == 2)
+        .unwrap();
+    entry.date = date;
+    assert!(provider.update_entry(entry).await.is_ok());
+}
+
+#[tokio::test]
+async fn same_day_in_other_notebook() {
+    let provider = create_provider_with_two_entries().await;
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title work"), Vec::new(), None)
+        .with_notebook(String::from("work"));
+    let entry = provider.add_entry(draft).await.unwrap();
+    assert_eq!(entry.notebook, "work");
+
+    let entries = provider.load_all_entries().await.unwrap();
+    let added = entries.iter().find(|e| e.id == entry.id).unwrap();
+    assert_eq!(added.notebook, "work");
+    assert!(
+        entries
+            .iter()
+            .filter(|e| e.id != entry.id)
+            .all(|e| e.notebook == DEFAULT_NOTEBOOK)
+    );
+
+    // Moving the entry to the default notebook brings back the duplicate day
+    let mut entry = entry;
+    entry.notebook = String::from(DEFAULT_NOTEBOOK);
+    let err = provider.update_entry(entry).await.unwrap_err();
+    assert!(matches!(
+        err,
+        ModifyEntryError::DuplicateDay { existing_id: 2, .. }
+    ));
+}
+
+#[tokio::test]
+async fn import_existing_uuid() {
+    let provider = create_provider_with_two_entries().await;
+
+    let dto = provider.get_export_object(&[1, 2]).await.unwrap();
+    provider.import_entries(dto.clone()).await.unwrap();
+
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+    assert_eq!(provider.get_export_object(&[1, 2]).await.unwrap(), dto);
+}
+
+#[tokio::test]
+async fn update_timestamps() {
+    let provider = create_provider_with_two_entries().await;
+
+    let entry = provider.load_all_entries().await.unwrap().remove(0);
+    assert_eq!(entry.created_at, entry.updated_at);
+
+    let updated = provider.update_entry(entry.clone()).await.unwrap();
+    assert_eq!(updated.created_at, entry.created_at);
+    assert!(updated.updated_at > entry.updated_at);
+
+    let loaded = provider.load_all_entries().await.unwrap().remove(0);
+    assert_eq!(loaded, updated);
+
+    // Modification time of changes without explicit time is set by a trigger, which has
+    // milliseconds precision only.
+    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+    provider.assign_priority_to_entries(3).await.unwrap();
+    let loaded = provider.load_all_entries().await.unwrap().remove(0);
+    assert!(loaded.updated_at > updated.updated_at);
+}
+
 #[tokio::test]
 async fn remove_entry() {
     let provider = create_provider_with_two_entries().await;
@@ -83,6 +184,67 @@ async fn remove_entry() {
     assert_eq!(entries[0].id, 2);
 }
 
+#[tokio::test]
+async fn trash_restore() {
+    let provider = create_provider_with_two_entries().await;
+
+    provider.remove_entry(2).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 2);
+    assert!(trash[0].deleted_at.is_some());
+
+    let restored = provider.restore_entry(2).await.unwrap();
+    assert_eq!(restored.title, "Title 2");
+    assert!(restored.deleted_at.is_none());
+
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert_eq!(provider.load_all_entries().await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn trash_restore_duplicate_day() {
+    let provider = create_provider_with_two_entries().await;
+
+    provider.remove_entry(2).await.unwrap();
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 20, 0, 0).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title new"), Vec::new(), None);
+    let new_entry = provider.add_entry(draft).await.unwrap();
+    assert_ne!(new_entry.id, 2);
+
+    assert!(matches!(
+        provider.restore_entry(2).await.unwrap_err(),
+        ModifyEntryError::DuplicateDay { .. }
+    ));
+}
+
+#[tokio::test]
+async fn trash_purge() {
+    let provider = create_provider_with_two_entries().await;
+
+    provider.remove_entry(2).await.unwrap();
+    provider.remove_entry(1).await.unwrap();
+
+    provider.purge_entry(2).await.unwrap();
+
+    let trash = provider.load_trash().await.unwrap();
+    assert_eq!(trash.len(), 1);
+    assert_eq!(trash[0].id, 1);
+
+    let purged = provider
+        .purge_trash(Utc::now() - chrono::Duration::days(1))
+        .await
+        .unwrap();
+    assert_eq!(purged, 0);
+
+    let purged = provider.purge_trash(Utc::now()).await.unwrap();
+    assert_eq!(purged, 1);
+    assert!(provider.load_trash().await.unwrap().is_empty());
+    assert!(provider.load_all_entries().await.unwrap().is_empty());
+}
+
 #[tokio::test]
 async fn update_entry() {
     let provider = create_provider_with_two_entries().await;
@@ -146,3 +308,129 @@ async fn assign_priority() {
     assert_eq!(entries[0].priority, Some(3));
     assert_eq!(entries[1].priority, Some(1));
 }
+
+#[tokio::test]
+async fn search() {
+    let provider = create_provider_with_two_entries().await;
+
+    let mut entry = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.id == 2)
+        .unwrap();
+    entry.content = String::from("Entry 2 entries content");
+    provider.update_entry(entry).await.unwrap();
+
+    let results = provider.search("ENTR").await.unwrap();
+    assert_eq!(results.len(), 2);
+    let (_, spans) = results.iter().find(|(id, _)| *id == 2).unwrap();
+    assert_eq!(spans, &vec![MatchSpan::new(0, 5), MatchSpan::new(8, 15)]);
+    let (_, spans) = results.iter().find(|(id, _)| *id == 1).unwrap();
+    assert_eq!(spans, &vec![MatchSpan::new(8, 13)]);
+
+    let results = provider.search("entry 1").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, 1);
+
+    provider.remove_entry(1).await.unwrap();
+    assert!(provider.search("entry 1").await.unwrap().is_empty());
+
+    assert!(provider.search("\"missing").await.unwrap().is_empty());
+    assert!(provider.search("  ").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn add_entry_is_atomic() {
+    let provider = create_provider_with_two_entries().await;
+
+    // Duplicated tags violate the primary key of the tags table.
+    let entry_draft = EntryDraft::new(
+        Utc.with_ymd_and_hms(2023, 3, 25, 1, 1, 1).unwrap(),
+        String::from("Title failed"),
+        vec![String::from("Tag_1"), String::from("Tag_1")],
+        None,
+    );
+
+    assert!(provider.add_entry(entry_draft).await.is_err());
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 2);
+    assert!(!entries.iter().any(|e| e.title == "Title failed"));
+}
+
+#[tokio::test]
+async fn update_entry_is_atomic() {
+    let provider = create_provider_with_two_entries().await;
+
+    let mut entry = provider.load_all_entries().await.unwrap().pop().unwrap();
+    let original = entry.clone();
+    entry.title = String::from("Title failed");
+    entry.tags = vec![String::from("Tag_3"), String::from("Tag_3")];
+
+    assert!(provider.update_entry(entry).await.is_err());
+
+    let entries = provider.load_all_entries().await.unwrap();
+    assert!(entries.contains(&original));
+}
+
+#[tokio::test]
+async fn update_missing_entry() {
+    let provider = create_provider_with_two_entries().await;
+
+    let mut entry = provider.load_all_entries().await.unwrap().pop().unwrap();
+    entry.tags = vec![String::from("Tag_3")];
+    entry.content = String::from("09:00 Log line");
+
+    let mut missing = entry.clone();
+    missing.id = 10;
+    assert!(provider.update_entry(missing).await.is_err());
+
+    provider.remove_entry(entry.id).await.unwrap();
+    assert!(provider.update_entry(entry).await.is_err());
+
+    assert_eq!(provider.remove_orphaned_tags().await.unwrap(), 0);
+    let trash = provider.load_trash().await.unwrap();
+    assert!(trash[0].tags.iter().all(|tag| tag != "Tag_3"));
+
+    let log_lines = provider
+        .query_log_lines(
+            Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0)
+                .unwrap()
+                .naive_utc(),
+            Utc.with_ymd_and_hms(2100, 1, 1, 0, 0, 0)
+                .unwrap()
+                .naive_utc(),
+        )
+        .await
+        .unwrap();
+    assert!(log_lines.is_empty());
+}
+
+#[tokio::test]
+async fn wal_shared_between_instances() {
+    let dir = std::env::temp_dir().join(format!("tjournal-sqlite-wal-{}", std::process::id()));
+    let file_path = dir.join("entries.db");
+
+    let writer = SqliteDataProvide::from_file(file_path.clone(), SqliteDurability::WalFull)
+        .await
+        .unwrap();
+    let reader = SqliteDataProvide::from_file(file_path, SqliteDurability::WalFull)
+        .await
+        .unwrap();
+
+    let draft = EntryDraft::new(Utc::now(), String::from("Title 1"), Vec::new(), None);
+    writer.add_entry(draft).await.unwrap();
+
+    let entries = reader.load_all_entries().await.unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].title, String::from("Title 1"));
+
+    let date = Utc.with_ymd_and_hms(2023, 3, 23, 1, 1, 1).unwrap();
+    let draft = EntryDraft::new(date, String::from("Title 2"), Vec::new(), None);
+    reader.add_entry(draft).await.unwrap();
+    assert_eq!(writer.load_all_entries().await.unwrap().len(), 2);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
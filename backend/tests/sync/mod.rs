@@ -0,0 +1,243 @@
+use backend::*;
+use chrono::{TimeZone, Utc};
+
+fn create_draft(day: u32, content: &str) -> EntryDraft {
+    EntryDraft::new(
+        Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap(),
+        format!("2024_05_{day:02}"),
+        vec![String::from("Tag_1")],
+        None,
+    )
+    .with_content(String::from(content))
+}
+
+async fn create_provider(drafts: Vec<EntryDraft>) -> SqliteDataProvide {
+    let provider = SqliteDataProvide::create("sqlite::memory:").await.unwrap();
+    for draft in drafts {
+        provider.add_entry(draft).await.unwrap();
+    }
+
+    provider
+}
+
+async fn sync(
+    local: &SqliteDataProvide,
+    peer: &SqliteDataProvide,
+    base: &SyncBase,
+    prefer: Option<SyncSide>,
+) -> SyncPlan {
+    let plan = plan_sync(
+        local.load_all_entries().await.unwrap(),
+        peer.load_all_entries().await.unwrap(),
+        base,
+        prefer,
+    );
+    apply_sync_plan(local, peer, &plan).await.unwrap();
+
+    plan
+}
+
+async fn get_content(provider: &SqliteDataProvide, title: &str) -> String {
+    provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == title)
+        .map(|e| e.content)
+        .unwrap()
+}
+
+async fn update_content(provider: &SqliteDataProvide, title: &str, content: &str) {
+    let mut entry = provider
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == title)
+        .unwrap();
+    entry.content = String::from(content);
+    provider.update_entry(entry).await.unwrap();
+}
+
+/// Asserts that both sides have the same entries with the same UUIDs.
+async fn assert_in_sync(local: &SqliteDataProvide, peer: &SqliteDataProvide) {
+    let drafts = |entries: Vec<Entry>| {
+        let mut drafts: Vec<_> = entries
+            .into_iter()
+            .map(|e| (e.uuid, e.title, e.content, e.tags, e.priority))
+            .collect();
+        drafts.sort_by(|a, b| a.1.cmp(&b.1));
+        drafts
+    };
+
+    assert_eq!(
+        drafts(local.load_all_entries().await.unwrap()),
+        drafts(peer.load_all_entries().await.unwrap())
+    );
+}
+
+#[tokio::test]
+async fn first_sync() {
+    let local = create_provider(vec![create_draft(1, "08:00 local")]).await;
+    let peer = create_provider(vec![create_draft(2, "09:00 peer")]).await;
+
+    let plan = sync(&local, &peer, &SyncBase::default(), None).await;
+
+    assert_eq!(plan.summary(SyncSide::Local).added, 1);
+    assert_eq!(plan.summary(SyncSide::Peer).added, 1);
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(plan.base.entries.len(), 2);
+    assert_in_sync(&local, &peer).await;
+
+    let plan = sync(&local, &peer, &plan.base, None).await;
+    assert!(plan.actions.is_empty());
+}
+
+#[tokio::test]
+async fn propagate_changes_and_deletions() {
+    let local = create_provider(vec![
+        create_draft(1, "08:00 first"),
+        create_draft(2, "09:00 second"),
+    ])
+    .await;
+    let peer = create_provider(Vec::new()).await;
+    let base = sync(&local, &peer, &SyncBase::default(), None).await.base;
+
+    update_content(&local, "2024_05_01", "08:00 first changed").await;
+    let removed_id = peer
+        .load_all_entries()
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|e| e.title == "2024_05_02")
+        .unwrap()
+        .id;
+    peer.remove_entry(removed_id).await.unwrap();
+
+    let plan = sync(&local, &peer, &base, None).await;
+
+    assert_eq!(plan.summary(SyncSide::Peer).updated, 1);
+    assert_eq!(plan.summary(SyncSide::Local).removed, 1);
+    assert_eq!(
+        get_content(&peer, "2024_05_01").await,
+        "08:00 first changed"
+    );
+    assert_eq!(local.load_all_entries().await.unwrap().len(), 1);
+    assert_in_sync(&local, &peer).await;
+}
+
+#[tokio::test]
+async fn keep_changed_over_deletion() {
+    let local = create_provider(vec![create_draft(1, "08:00 first")]).await;
+    let peer = create_provider(Vec::new()).await;
+    let base = sync(&local, &peer, &SyncBase::default(), None).await.base;
+
+    update_content(&local, "2024_05_01", "08:00 first changed").await;
+    let peer_id = peer.load_all_entries().await.unwrap()[0].id;
+    peer.remove_entry(peer_id).await.unwrap();
+
+    let plan = sync(&local, &peer, &base, None).await;
+
+    assert_eq!(plan.summary(SyncSide::Peer).added, 1);
+    assert_in_sync(&local, &peer).await;
+}
+
+#[tokio::test]
+async fn merge_changes_on_both_sides() {
+    let local = create_provider(vec![create_draft(
+        1,
+        "08:00 first\n09:00 second\n10:00 third",
+    )])
+    .await;
+    let peer = create_provider(Vec::new()).await;
+    let base = sync(&local, &peer, &SyncBase::default(), None).await.base;
+
+    update_content(
+        &local,
+        "2024_05_01",
+        "08:00 first local\n09:00 second\n10:00 third",
+    )
+    .await;
+    update_content(
+        &peer,
+        "2024_05_01",
+        "08:00 first\n09:00 second\n10:00 third peer",
+    )
+    .await;
+
+    let plan = sync(&local, &peer, &base, None).await;
+
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(
+        get_content(&local, "2024_05_01").await,
+        "08:00 first local\n09:00 second\n10:00 third peer"
+    );
+    assert_in_sync(&local, &peer).await;
+}
+
+#[tokio::test]
+async fn conflicts() {
+    let local = create_provider(vec![create_draft(1, "08:00 first")]).await;
+    let peer = create_provider(Vec::new()).await;
+    let base = sync(&local, &peer, &SyncBase::default(), None).await.base;
+
+    update_content(&local, "2024_05_01", "08:00 first local").await;
+    update_content(&peer, "2024_05_01", "08:00 first peer").await;
+
+    let plan = sync(&local, &peer, &base, None).await;
+
+    assert!(plan.actions.is_empty());
+    assert_eq!(plan.conflicts.len(), 1);
+    assert!(
+        plan.conflicts[0]
+            .marked_content
+            .contains("08:00 first local")
+    );
+    assert!(
+        plan.conflicts[0]
+            .marked_content
+            .contains("08:00 first peer")
+    );
+    assert_eq!(get_content(&local, "2024_05_01").await, "08:00 first local");
+    assert_eq!(get_content(&peer, "2024_05_01").await, "08:00 first peer");
+    // Conflicting entries keep their old base to be detected again on the next sync.
+    assert_eq!(
+        plan.base,
+        SyncBase {
+            synced_at: plan.base.synced_at,
+            ..base.clone()
+        }
+    );
+
+    let plan = sync(&local, &peer, &plan.base, Some(SyncSide::Peer)).await;
+
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(get_content(&local, "2024_05_01").await, "08:00 first peer");
+    assert_in_sync(&local, &peer).await;
+}
+
+#[tokio::test]
+async fn same_day_created_on_both_sides() {
+    let local = create_provider(vec![create_draft(1, "08:00 first\n12:00 third")]).await;
+    let peer = create_provider(vec![create_draft(1, "10:00 second")]).await;
+
+    let plan = sync(&local, &peer, &SyncBase::default(), None).await;
+
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(
+        get_content(&peer, "2024_05_01").await,
+        "08:00 first\n10:00 second\n12:00 third"
+    );
+    assert_in_sync(&local, &peer).await;
+}
+
+#[test]
+fn three_way_merge() {
+    assert_eq!(
+        merge_three_way("a\nb", "a\nb\nc", "z\na\nb").unwrap(),
+        "z\na\nb\nc"
+    );
+    assert_eq!(merge_three_way("a\n", "b\n", "a\n").unwrap(), "b\n");
+    assert!(merge_three_way("a", "b", "c").is_err());
+}
@@ -0,0 +1,93 @@
+use backend::*;
+use chrono::Utc;
+
+#[test]
+fn parse_current_version() {
+    let draft = EntryDraft::new(Utc::now(), String::from("Title 1"), Vec::new(), Some(1));
+    let dto = EntriesDTO::new(vec![draft]);
+    let json = serde_json::to_string(&dto).unwrap();
+
+    let (parsed, source_version) = EntriesDTO::parse_versioned(json.as_bytes()).unwrap();
+
+    assert_eq!(source_version, TRANSFER_DATA_VERSION);
+    assert_eq!(parsed, dto);
+    assert!(parsed.ensure_current_version().is_ok());
+}
+
+#[test]
+fn reject_newer_version() {
+    let json = format!(
+        r#"{{"version": {}, "entries": []}}"#,
+        TRANSFER_DATA_VERSION + 1
+    );
+
+    let err = EntriesDTO::parse_versioned(json.as_bytes()).unwrap_err();
+
+    assert!(err.to_string().contains("newer than the supported version"));
+}
+
+#[test]
+fn reject_unknown_older_version() {
+    let json = r#"{"version": 1, "entries": []}"#;
+
+    assert!(EntriesDTO::parse_versioned(json.as_bytes()).is_err());
+}
+
+#[test]
+fn reject_invalid_data() {
+    let missing_version = r#"{"entries": []}"#;
+    assert!(EntriesDTO::parse_versioned(missing_version.as_bytes()).is_err());
+
+    let invalid_version = r#"{"version": "100", "entries": []}"#;
+    assert!(EntriesDTO::parse_versioned(invalid_version.as_bytes()).is_err());
+
+    let invalid_entries =
+        format!(r#"{{"version": {TRANSFER_DATA_VERSION}, "entries": [{{"title": "Title 1"}}]}}"#);
+    assert!(EntriesDTO::parse_versioned(invalid_entries.as_bytes()).is_err());
+
+    assert!(EntriesDTO::parse_versioned("not json".as_bytes()).is_err());
+}
+
+#[test]
+fn ensure_current_version() {
+    let mut dto = EntriesDTO::new(Vec::new());
+    dto.version = TRANSFER_DATA_VERSION + 1;
+
+    assert!(dto.ensure_current_version().is_err());
+}
+
+#[test]
+fn upgrade_from_version_100() {
+    let json = r#"{"version": 100, "entries": [
+        {"date": "2024-05-03T00:00:00Z", "title": "2024_05_03_Friday", "content": "", "tags": [], "priority": null},
+        {"date": "2024-05-04T00:00:00Z", "title": "2024_05_04_Saturday", "content": "", "tags": [], "priority": null}
+    ]}"#;
+
+    let (parsed, source_version) = EntriesDTO::parse_versioned(json.as_bytes()).unwrap();
+
+    assert_eq!(source_version, 100);
+    assert_eq!(parsed.version, TRANSFER_DATA_VERSION);
+    assert_eq!(parsed.entries.len(), 2);
+    assert!(parsed.entries.iter().all(|e| !e.uuid.is_nil()));
+    assert_ne!(parsed.entries[0].uuid, parsed.entries[1].uuid);
+    assert!(
+        parsed
+            .entries
+            .iter()
+            .all(|e| e.created_at == e.date && e.updated_at == e.date)
+    );
+}
+
+#[test]
+fn upgrade_from_version_102() {
+    let json = r#"{"version": 102, "entries": [
+        {"date": "2024-05-03T00:00:00Z", "title": "2024_05_03_Friday", "content": "", "tags": [], "priority": null,
+         "uuid": "0b0a9f4e-4d5c-4b8e-9a55-2f1a8f0c7d11", "created_at": "2024-05-03T00:00:00Z", "updated_at": "2024-05-03T00:00:00Z"}
+    ]}"#;
+
+    let (parsed, source_version) = EntriesDTO::parse_versioned(json.as_bytes()).unwrap();
+
+    assert_eq!(source_version, 102);
+    assert_eq!(parsed.version, TRANSFER_DATA_VERSION);
+    assert_eq!(parsed.entries[0].notebook, DEFAULT_NOTEBOOK);
+}
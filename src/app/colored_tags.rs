@@ -1,64 +1,77 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 use ratatui::style::Color;
 
-/// Hard coded colors for the tags.
-/// Note: the order to pick the colors is from bottom to top because we are popping the colors from
-/// the end of the stack.
-const TAG_COLORS: &[TagColors] = &[
-    TagColors::new(Color::Black, Color::LightMagenta),
-    TagColors::new(Color::Red, Color::Cyan),
-    TagColors::new(Color::Yellow, Color::Blue),
-    TagColors::new(Color::Reset, Color::Red),
-    TagColors::new(Color::Black, Color::LightYellow),
-    TagColors::new(Color::Reset, Color::DarkGray),
-    TagColors::new(Color::Black, Color::LightGreen),
-    TagColors::new(Color::Black, Color::LightRed),
-    TagColors::new(Color::Black, Color::LightCyan),
+use super::{tag_tree::parent_path, ui::themes::PinnedTagColors};
+
+/// Minimum contrast ratio between the foreground and background colors of a tag, which matches
+/// the WCAG recommendation for normal text.
+const MIN_CONTRAST_RATIO: f64 = 4.5;
+
+/// RGB values of the 16 basic terminal colors as defined in xterm, which are used to estimate
+/// the contrast of the named and indexed colors.
+const BASIC_COLORS_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 0, 0),
+    (0, 205, 0),
+    (205, 205, 0),
+    (0, 0, 238),
+    (205, 0, 205),
+    (0, 205, 205),
+    (229, 229, 229),
+    (127, 127, 127),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (92, 92, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
 ];
 
+/// Levels of each channel in the 6x6x6 color cube of the 256 terminal colors.
+const COLOR_CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
 #[derive(Debug, Clone)]
 /// Manages assigning colors to the tags, keeping track on the assigned colors and providing
 /// functions to updating them.
+///
+/// Tags get their colors pinned in the themes or otherwise a color from the generated palette
+/// picked by the hash of their names, so each tag keeps its color between sessions regardless of
+/// the other existing tags.
 pub struct ColoredTagsManager {
     tag_colors_map: HashMap<String, TagColors>,
-    available_colors: Vec<TagColors>,
+    pinned_colors: BTreeMap<String, PinnedTagColors>,
+    palette: Vec<TagColors>,
 }
 
 impl ColoredTagsManager {
     pub fn new() -> Self {
-        let available_colors = TAG_COLORS.to_vec();
-
         Self {
             tag_colors_map: HashMap::new(),
-            available_colors,
+            pinned_colors: BTreeMap::new(),
+            palette: generate_palette(),
         }
     }
 
+    /// Sets the colors pinned to the tags, overriding the generated ones.
+    pub fn with_pinned_colors(mut self, pinned_colors: BTreeMap<String, PinnedTagColors>) -> Self {
+        self.pinned_colors = pinned_colors;
+        self
+    }
+
     /// Updates the tag_color map with the provided tags, removing the not existing tags and
     /// assigning colors to the newly added ones.
     pub fn update_tags(&mut self, current_tags: Vec<String>) {
         // First: Clear the non-existing anymore tags.
-        let tags_to_remove: Vec<_> = self
-            .tag_colors_map
-            .keys()
-            .filter(|t| !current_tags.contains(t))
-            .cloned()
-            .collect();
-
-        for tag in tags_to_remove {
-            let color = self.tag_colors_map.remove(&tag).unwrap();
-            self.available_colors.push(color)
-        }
+        self.tag_colors_map
+            .retain(|tag, _| current_tags.contains(tag));
 
         // Second: Add the new tags to the map
         for tag in current_tags {
-            match self.tag_colors_map.entry(tag) {
-                std::collections::hash_map::Entry::Occupied(_) => {}
-                std::collections::hash_map::Entry::Vacant(vacant_entry) => {
-                    let color = self.available_colors.pop().unwrap_or_default();
-                    vacant_entry.insert(color);
-                }
+            if !self.tag_colors_map.contains_key(&tag) {
+                let color = self.resolve_tag_color(&tag);
+                self.tag_colors_map.insert(tag, color);
             }
         }
     }
@@ -67,6 +80,53 @@ impl ColoredTagsManager {
     pub fn get_tag_color(&self, tag: &str) -> Option<TagColors> {
         self.tag_colors_map.get(tag).copied()
     }
+
+    /// Gets the colors pinned to the tag or its nearest ancestor in the tags hierarchy.
+    fn get_pinned_color(&self, tag: &str) -> Option<PinnedTagColors> {
+        let mut path = Some(tag);
+        while let Some(current) = path {
+            if let Some(pinned) = self.pinned_colors.get(current) {
+                return Some(*pinned);
+            }
+            path = parent_path(current);
+        }
+
+        None
+    }
+
+    fn resolve_tag_color(&self, tag: &str) -> TagColors {
+        let palette_idx = (fnv1a_hash(tag) % self.palette.len() as u64) as usize;
+        let generated = self.palette[palette_idx];
+
+        let Some(pinned) = self.get_pinned_color(tag) else {
+            return generated;
+        };
+
+        match (pinned.fg, pinned.bg) {
+            (Some(foreground), Some(background)) => {
+                if is_readable(foreground, background) {
+                    TagColors::new(foreground, background)
+                } else {
+                    log::warn!(
+                        "Pinned colors for tag '{tag}' have low contrast. Readable foreground color will be used instead"
+                    );
+                    TagColors::new(readable_foreground(background), background)
+                }
+            }
+            (None, Some(background)) => TagColors::new(readable_foreground(background), background),
+            // Look for the first readable background in the palette starting from the tag hash.
+            (Some(foreground), None) => self
+                .palette
+                .iter()
+                .cycle()
+                .skip(palette_idx)
+                .take(self.palette.len())
+                .find(|colors| is_readable(foreground, colors.background))
+                .map(|colors| TagColors::new(foreground, colors.background))
+                .unwrap_or(generated),
+            (None, None) => generated,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -85,6 +145,117 @@ impl TagColors {
     }
 }
 
+/// Generates the palette for the tags from the colors of the 256 terminal colors cube, skipping
+/// the grayish ones since they are hard to tell apart.
+fn generate_palette() -> Vec<TagColors> {
+    let mut palette = Vec::new();
+    for red in 0..6u8 {
+        for green in 0..6u8 {
+            for blue in 0..6u8 {
+                let max = red.max(green).max(blue);
+                let min = red.min(green).min(blue);
+                if max - min < 2 {
+                    continue;
+                }
+
+                let background = Color::Indexed(16 + 36 * red + 6 * green + blue);
+                palette.push(TagColors::new(readable_foreground(background), background));
+            }
+        }
+    }
+
+    palette
+}
+
+/// FNV-1a hash, used because its values are stable between sessions and versions unlike the
+/// hasher of the standard library.
+fn fnv1a_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Gets the RGB values of the given color if known. The values of the default terminal colors
+/// can't be known.
+fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let basic_idx = match color {
+        Color::Reset => return None,
+        Color::Rgb(r, g, b) => return Some((r, g, b)),
+        Color::Indexed(idx @ 16..=231) => {
+            let idx = (idx - 16) as usize;
+            return Some((
+                COLOR_CUBE_LEVELS[idx / 36],
+                COLOR_CUBE_LEVELS[(idx / 6) % 6],
+                COLOR_CUBE_LEVELS[idx % 6],
+            ));
+        }
+        Color::Indexed(idx @ 232..=255) => {
+            let level = 8 + 10 * (idx - 232);
+            return Some((level, level, level));
+        }
+        Color::Indexed(idx) => idx as usize,
+        Color::Black => 0,
+        Color::Red => 1,
+        Color::Green => 2,
+        Color::Yellow => 3,
+        Color::Blue => 4,
+        Color::Magenta => 5,
+        Color::Cyan => 6,
+        Color::Gray => 7,
+        Color::DarkGray => 8,
+        Color::LightRed => 9,
+        Color::LightGreen => 10,
+        Color::LightYellow => 11,
+        Color::LightBlue => 12,
+        Color::LightMagenta => 13,
+        Color::LightCyan => 14,
+        Color::White => 15,
+    };
+
+    Some(BASIC_COLORS_RGB[basic_idx])
+}
+
+/// Calculates the relative luminance of the color as defined in WCAG.
+fn relative_luminance((red, green, blue): (u8, u8, u8)) -> f64 {
+    let channel = |value: u8| {
+        let value = value as f64 / 255.0;
+        if value <= 0.03928 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.2126 * channel(red) + 0.7152 * channel(green) + 0.0722 * channel(blue)
+}
+
+/// Calculates the contrast ratio between the two colors if their values are known.
+fn contrast_ratio(first: Color, second: Color) -> Option<f64> {
+    let first = relative_luminance(color_to_rgb(first)?);
+    let second = relative_luminance(color_to_rgb(second)?);
+
+    Some((first.max(second) + 0.05) / (first.min(second) + 0.05))
+}
+
+/// Checks if text with the foreground color is readable on the background color. Colors with
+/// unknown values are assumed to be readable since they are chosen by the terminal.
+fn is_readable(foreground: Color, background: Color) -> bool {
+    contrast_ratio(foreground, background).is_none_or(|ratio| ratio >= MIN_CONTRAST_RATIO)
+}
+
+/// Picks black or white as foreground depending on which one has more contrast with the given
+/// background.
+fn readable_foreground(background: Color) -> Color {
+    match (
+        contrast_ratio(Color::Black, background),
+        contrast_ratio(Color::White, background),
+    ) {
+        (Some(black), Some(white)) if white > black => Color::White,
+        (Some(_), Some(_)) => Color::Black,
+        _ => Color::Reset,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -115,7 +286,6 @@ mod tests {
         assert!(manager.get_tag_color("Non Existing Tag").is_none());
 
         // Keep track on colors before updating.
-        let tag_one_color = manager.get_tag_color(TAG_ONE).unwrap();
         let tag_two_color = manager.get_tag_color(TAG_TWO).unwrap();
 
         // Remove Tag one with changing the order of the tags.
@@ -136,7 +306,79 @@ mod tests {
         // Tag two color must remain the same after update.
         assert_eq!(manager.get_tag_color(TAG_TWO).unwrap(), tag_two_color);
 
-        // Added tag should take the color of tag one because we removed it then added the new tag.
-        assert_eq!(manager.get_tag_color(ADDED_TAG).unwrap(), tag_one_color);
+        // Colors must be the same in new sessions regardless of the other tags and their order.
+        let mut other_manager = ColoredTagsManager::new();
+        other_manager.update_tags(vec![ADDED_TAG.into(), TAG_TWO.into()]);
+        assert_eq!(other_manager.get_tag_color(TAG_TWO).unwrap(), tag_two_color);
+        assert_eq!(
+            other_manager.get_tag_color(ADDED_TAG),
+            manager.get_tag_color(ADDED_TAG)
+        );
+    }
+
+    #[test]
+    fn pinned_colors() {
+        let pinned = BTreeMap::from([
+            (
+                String::from("work"),
+                PinnedTagColors {
+                    fg: Some(Color::Black),
+                    bg: Some(Color::LightGreen),
+                },
+            ),
+            (
+                String::from("home"),
+                PinnedTagColors {
+                    fg: None,
+                    bg: Some(Color::Blue),
+                },
+            ),
+            (
+                String::from("unreadable"),
+                PinnedTagColors {
+                    fg: Some(Color::Yellow),
+                    bg: Some(Color::LightYellow),
+                },
+            ),
+        ]);
+
+        let mut manager = ColoredTagsManager::new().with_pinned_colors(pinned);
+        manager.update_tags(vec![
+            String::from("work"),
+            String::from("work/meetings"),
+            String::from("home"),
+            String::from("unreadable"),
+        ]);
+
+        let work_colors = TagColors::new(Color::Black, Color::LightGreen);
+        assert_eq!(manager.get_tag_color("work"), Some(work_colors));
+        // Descendants use the pinned colors of their parents.
+        assert_eq!(manager.get_tag_color("work/meetings"), Some(work_colors));
+
+        // Missing foreground is picked to be readable.
+        assert_eq!(
+            manager.get_tag_color("home"),
+            Some(TagColors::new(Color::White, Color::Blue))
+        );
+
+        // Foreground with low contrast is replaced.
+        assert_eq!(
+            manager.get_tag_color("unreadable"),
+            Some(TagColors::new(Color::Black, Color::LightYellow))
+        );
+    }
+
+    #[test]
+    fn palette_readable() {
+        let palette = generate_palette();
+        assert!(palette.len() > 100);
+
+        for colors in palette {
+            let ratio = contrast_ratio(colors.foreground, colors.background).unwrap();
+            assert!(
+                ratio >= MIN_CONTRAST_RATIO,
+                "Colors {colors:?} have contrast ratio {ratio}"
+            );
+        }
     }
 }
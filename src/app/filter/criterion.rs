@@ -1,5 +1,8 @@
 use aho_corasick::AhoCorasick;
 use backend::Entry;
+use chrono::NaiveDate;
+
+use crate::app::tag_tree::is_tag_or_descendant;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FilterCriterion {
@@ -7,6 +10,10 @@ pub enum FilterCriterion {
     Title(String),
     Content(String),
     Priority(u32),
+    /// Journals on or after the given day.
+    FromDate(NaiveDate),
+    /// Journals on or before the given day.
+    ToDate(NaiveDate),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,7 +26,11 @@ impl FilterCriterion {
     /// Checks if the entry meets the criterion
     pub fn check_entry(&self, entry: &Entry) -> bool {
         match self {
-            FilterCriterion::Tag(TagFilterOption::Tag(tag)) => entry.tags.contains(tag),
+            // Parent tags match all their descendants in the tags hierarchy.
+            FilterCriterion::Tag(TagFilterOption::Tag(tag)) => entry
+                .tags
+                .iter()
+                .any(|entry_tag| is_tag_or_descendant(entry_tag, tag)),
             FilterCriterion::Tag(TagFilterOption::NoTags) => entry.tags.is_empty(),
             FilterCriterion::Title(search) => {
                 // Use simple smart-case search for title
@@ -52,6 +63,8 @@ impl FilterCriterion {
                 }
             }
             FilterCriterion::Priority(prio) => entry.priority.is_some_and(|pr| pr == *prio),
+            FilterCriterion::FromDate(day) => entry.date.date_naive() >= *day,
+            FilterCriterion::ToDate(day) => entry.date.date_naive() <= *day,
         }
     }
 }
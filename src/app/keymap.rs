@@ -232,6 +232,14 @@ pub fn get_entries_list_keymaps() -> Vec<Keymap> {
             Input::new(KeyCode::Char('o'), KeyModifiers::NONE),
             UICommand::ShowSortOptions,
         ),
+        Keymap::new(
+            Input::new(KeyCode::Char('b'), KeyModifiers::NONE),
+            UICommand::ShowNotebooks,
+        ),
+        Keymap::new(
+            Input::new(KeyCode::Char('t'), KeyModifiers::NONE),
+            UICommand::ShowTrash,
+        ),
         Keymap::new(
             Input::new(KeyCode::Home, KeyModifiers::NONE),
             UICommand::GoToTopEntry,
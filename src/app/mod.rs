@@ -2,32 +2,39 @@ use self::{
     filter::{Filter, FilterCriterion},
     sorter::{SortCriteria, SortOrder, Sorter},
     state::AppState,
+    tag_tree::{TagTree, is_tag_or_descendant, parent_path},
+    ui::themes::PinnedTagColors,
 };
 use crate::settings::Settings;
 use anyhow::{Context, anyhow, bail};
-use backend::{DataProvider, EntriesDTO, Entry, EntryDraft};
-use chrono::{DateTime, Utc};
+use backend::{
+    DEFAULT_NOTEBOOK, DataProvider, EntriesDTO, Entry, EntryDraft, ImportStrategy, ImportSummary,
+    MatchSpan, ModifyEntryError,
+};
+use chrono::{DateTime, Duration, Utc};
 use colored_tags::ColoredTagsManager;
 use filter::criterion::TagFilterOption;
 use history::{Change, HistoryManager, HistoryStack};
 use rayon::prelude::*;
 use std::{
-    collections::{BTreeSet, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fs::File,
     path::PathBuf,
 };
 
 mod colored_tags;
 mod external_editor;
-mod filter;
+pub mod filter;
 mod history;
 mod keymap;
 mod runner;
-mod sorter;
+pub mod sorter;
 pub mod state;
+mod tag_tree;
 #[cfg(test)]
-mod test;
+pub(crate) mod test;
 pub mod ui;
+mod watcher;
 
 pub use runner::HandleInputReturnType;
 pub use runner::run;
@@ -44,7 +51,7 @@ where
     pub current_entry_id: Option<u32>,
     /// Selected entries' IDs in multi-select mode
     pub selected_entries: HashSet<u32>,
-    /// Inactive entries' IDs due to not meeting the filter criteria
+    /// Inactive entries' IDs due to not meeting the filter criteria or being in another notebook
     pub filtered_out_entries: HashSet<u32>,
     pub settings: Settings,
     pub redraw_after_restore: bool,
@@ -81,7 +88,18 @@ where
         }
     }
 
-    /// Get entries that meet the filter criteria if any otherwise it returns all entries
+    /// Sets the colors pinned to tags in the themes, used when colored tags are enabled.
+    pub fn with_pinned_tag_colors(
+        mut self,
+        pinned_colors: BTreeMap<String, PinnedTagColors>,
+    ) -> Self {
+        self.colored_tags = self
+            .colored_tags
+            .map(|colored_tags| colored_tags.with_pinned_colors(pinned_colors));
+        self
+    }
+
+    /// Get the entries of the active notebook that meet the filter criteria if any
     pub fn get_active_entries(&self) -> impl DoubleEndedIterator<Item = &Entry> {
         self.entries
             .iter()
@@ -122,20 +140,93 @@ where
             .and_then(|id| self.get_active_entries().find(|entry| entry.id == id))
     }
 
+    /// Gets the name of the notebook which the shown entries belong to.
+    pub fn active_notebook(&self) -> &str {
+        self.state.notebook.as_deref().unwrap_or(DEFAULT_NOTEBOOK)
+    }
+
+    /// Gets the names of all notebooks sorted, including the default and the active notebooks
+    /// even if they have no entries.
+    pub fn get_notebooks(&self) -> Vec<String> {
+        let mut notebooks: BTreeSet<&str> = self
+            .entries
+            .iter()
+            .map(|entry| entry.notebook.as_str())
+            .collect();
+        notebooks.insert(DEFAULT_NOTEBOOK);
+        notebooks.insert(self.active_notebook());
+
+        notebooks.into_iter().map(String::from).collect()
+    }
+
+    /// Switches to the given notebook, showing its entries only.
+    pub fn set_active_notebook(&mut self, notebook: String) {
+        log::trace!("Switching to notebook {notebook}");
+
+        self.state.notebook = Some(notebook);
+        self.selected_entries.clear();
+
+        self.update_filter();
+        self.update_filtered_out_entries();
+        self.update_colored_tags();
+    }
+
     pub async fn load_entries(&mut self) -> anyhow::Result<()> {
         log::trace!("Loading entries");
 
-        self.entries = self.data_provide.load_all_entries().await?;
+        let entries = self.data_provide.load_all_entries().await?;
+        self.set_entries(entries);
+
+        Ok(())
+    }
+
+    fn set_entries(&mut self, entries: Vec<Entry>) {
+        self.entries = entries;
 
         self.sort_entries();
 
         self.update_filtered_out_entries();
 
         self.update_colored_tags();
+    }
 
-        Ok(())
+    /// Loads the entries again if they have been changed outside the app, returning the
+    /// entries as they were before loading them.
+    pub async fn reload_external_changes(&mut self) -> anyhow::Result<Option<Vec<Entry>>> {
+        let mut loaded = self.data_provide.load_all_entries().await?;
+        loaded.sort_by_key(|entry| entry.id);
+
+        let mut current = self.entries.clone();
+        current.sort_by_key(|entry| entry.id);
+
+        if loaded == current {
+            return Ok(None);
+        }
+
+        log::trace!("Reloading entries changed outside the app");
+
+        self.set_entries(loaded);
+
+        Ok(Some(current))
     }
 
+    /// Adds the entry again after it has been removed outside the app, returning its new id.
+    pub async fn restore_removed_entry(&mut self, entry: Entry) -> anyhow::Result<u32> {
+        let added = self
+            .data_provide
+            .add_entry(EntryDraft::from_entry(entry))
+            .await?;
+
+        let id = added.id;
+        let mut entries = std::mem::take(&mut self.entries);
+        entries.push(added);
+        self.set_entries(entries);
+
+        Ok(id)
+    }
+
+    /// Adds a new entry returning its id. If the day has an entry already, the id of the existing
+    /// entry will be returned instead since each day can have one entry only.
     pub async fn add_entry(
         &mut self,
         title: String,
@@ -143,12 +234,34 @@ where
         tags: Vec<String>,
         priority: Option<u32>,
     ) -> anyhow::Result<u32> {
-        self.add_entry_intern(title, date, tags, priority, None, HistoryStack::Undo)
-            .await
+        let notebook = self.active_notebook().to_owned();
+        let result = self
+            .add_entry_intern(
+                title,
+                date,
+                tags,
+                priority,
+                None,
+                notebook,
+                HistoryStack::Undo,
+            )
+            .await;
+
+        match result {
+            Err(err) => match err.downcast_ref::<ModifyEntryError>() {
+                Some(ModifyEntryError::DuplicateDay { day, existing_id }) => {
+                    log::trace!("Journal of {day} exists already. Selecting the existing one");
+                    Ok(*existing_id)
+                }
+                _ => Err(err),
+            },
+            ok => ok,
+        }
     }
 
     /// Creates an [`Entry`] from the given arguments, registering the change to the provided
     /// [`HistoryStack`].
+    #[allow(clippy::too_many_arguments)]
     async fn add_entry_intern(
         &mut self,
         title: String,
@@ -156,11 +269,12 @@ where
         tags: Vec<String>,
         priority: Option<u32>,
         content: Option<String>,
+        notebook: String,
         history_target: HistoryStack,
     ) -> anyhow::Result<u32> {
         log::trace!("Adding entry");
 
-        let mut draft = EntryDraft::new(date, title, tags, priority);
+        let mut draft = EntryDraft::new(date, title, tags, priority).with_notebook(notebook);
         if let Some(content) = content {
             draft = draft.with_content(content);
         }
@@ -227,7 +341,8 @@ where
 
         let clone = entry.clone();
 
-        self.data_provide.update_entry(clone).await?;
+        let updated = self.data_provide.update_entry(clone).await?;
+        self.apply_updated_entry(updated);
 
         self.sort_entries();
 
@@ -268,13 +383,22 @@ where
 
         let clone = entry.clone();
 
-        self.data_provide.update_entry(clone).await?;
+        let updated = self.data_provide.update_entry(clone).await?;
+        self.apply_updated_entry(updated);
 
         self.update_filtered_out_entries();
 
         Ok(())
     }
 
+    /// Replaces the stored entry with its state returned from the data provider after updating it,
+    /// which includes the attributes maintained by the back-end like the modification time.
+    fn apply_updated_entry(&mut self, updated: Entry) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.id == updated.id) {
+            *entry = updated;
+        }
+    }
+
     pub async fn delete_entry(&mut self, entry_id: u32) -> anyhow::Result<()> {
         self.delete_entry_intern(entry_id, HistoryStack::Undo).await
     }
@@ -304,6 +428,62 @@ where
         Ok(())
     }
 
+    /// Loads the entries of the active notebook in the trash, the most recently deleted first.
+    pub async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        let mut trash = self.data_provide.load_trash().await?;
+        trash.retain(|entry| entry.notebook == self.active_notebook());
+        trash.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+
+        Ok(trash)
+    }
+
+    /// Restores the entry from the trash, returning its id.
+    pub async fn restore_from_trash(&mut self, entry_id: u32) -> anyhow::Result<u32> {
+        self.restore_entry_intern(entry_id, HistoryStack::Undo)
+            .await
+    }
+
+    /// Restores the entry from the trash, registering it as added to the given [`HistoryStack`]
+    async fn restore_entry_intern(
+        &mut self,
+        entry_id: u32,
+        history_target: HistoryStack,
+    ) -> anyhow::Result<u32> {
+        log::trace!("Restoring entry with id: {entry_id}");
+
+        let entry = self.data_provide.restore_entry(entry_id).await?;
+        let entry_id = entry.id;
+
+        self.history.register_add(history_target, &entry);
+
+        self.entries.push(entry);
+
+        self.sort_entries();
+        self.update_filtered_out_entries();
+        self.update_colored_tags();
+
+        Ok(entry_id)
+    }
+
+    /// Deletes the entry in the trash permanently.
+    pub async fn purge_from_trash(&mut self, entry_id: u32) -> anyhow::Result<()> {
+        log::trace!("Purging entry with id: {entry_id}");
+
+        self.data_provide.purge_entry(entry_id).await
+    }
+
+    /// Deletes the entries which have been in the trash longer than the retention days in the
+    /// settings permanently, returning their count.
+    pub async fn purge_expired_trash(&self) -> anyhow::Result<usize> {
+        if self.settings.trash_retention_days == 0 {
+            return Ok(0);
+        }
+
+        let deleted_before = Utc::now() - Duration::days(self.settings.trash_retention_days.into());
+
+        self.data_provide.purge_trash(deleted_before).await
+    }
+
     async fn export_entry_content(&self, entry_id: u32, path: PathBuf) -> anyhow::Result<()> {
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await?;
@@ -331,7 +511,11 @@ where
         Ok(())
     }
 
-    async fn import_entries(&self, file_path: PathBuf) -> anyhow::Result<()> {
+    async fn import_entries(
+        &self,
+        file_path: PathBuf,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
         if !file_path.exists() {
             bail!("Import file doesn't exist: path {}", file_path.display())
         }
@@ -339,21 +523,38 @@ where
         let file = File::open(file_path)
             .map_err(|err| anyhow!("Error while opening import file: Error: {err}"))?;
 
-        let entries_dto: EntriesDTO = serde_json::from_reader(&file)
+        let (entries_dto, _) = EntriesDTO::parse_versioned(&file)
             .map_err(|err| anyhow!("Error while parsing import file. Error: {err}"))?;
 
-        self.data_provide
-            .import_entries(entries_dto)
+        let summary = self
+            .data_provide
+            .import_entries_with_strategy(entries_dto, strategy)
             .await
             .map_err(|err| anyhow!("Error while importing the entry. Error: {err}"))?;
 
-        Ok(())
+        Ok(summary)
+    }
+
+    /// Searches the content of the active entries using the data provider, returning the ids of
+    /// the matching entries with the spans of the matches, best matches first.
+    pub async fn search_content(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        let results = self.data_provide.search(query).await?;
+
+        Ok(results
+            .into_iter()
+            .filter(|(id, _)| self.get_entry(*id).is_some())
+            .collect())
     }
 
     pub fn get_all_tags(&self) -> Vec<String> {
         let mut tags = BTreeSet::new();
 
-        for tag in self.entries.iter().flat_map(|entry| &entry.tags) {
+        for tag in self
+            .entries
+            .iter()
+            .filter(|entry| entry.notebook == self.active_notebook())
+            .flat_map(|entry| &entry.tags)
+        {
             tags.insert(tag);
         }
 
@@ -373,11 +574,14 @@ where
             let filter = self.filter.as_mut().unwrap();
 
             filter.criteria.retain(|cr| match cr {
-                FilterCriterion::Tag(TagFilterOption::Tag(tag)) => all_tags.contains(tag),
+                FilterCriterion::Tag(TagFilterOption::Tag(tag)) => all_tags
+                    .iter()
+                    .any(|existing| is_tag_or_descendant(existing, tag)),
                 FilterCriterion::Tag(TagFilterOption::NoTags) => !all_tags.is_empty(),
                 FilterCriterion::Title(_) => true,
                 FilterCriterion::Content(_) => true,
                 FilterCriterion::Priority(_) => true,
+                FilterCriterion::FromDate(_) | FilterCriterion::ToDate(_) => true,
             });
 
             if filter.criteria.is_empty() {
@@ -387,17 +591,21 @@ where
     }
 
     /// Applies filter on the entries and filter out the ones who don't meet the filter's criteria
+    /// or don't belong to the active notebook.
     fn update_filtered_out_entries(&mut self) {
-        if let Some(filter) = self.filter.as_ref() {
-            self.filtered_out_entries = self
-                .entries
-                .par_iter()
-                .filter(|entry| !filter.check_entry(entry))
-                .map(|entry| entry.id)
-                .collect();
-        } else {
-            self.filtered_out_entries.clear();
-        }
+        let notebook = self.active_notebook();
+        let filter = self.filter.as_ref();
+
+        let filtered_out_entries = self
+            .entries
+            .par_iter()
+            .filter(|entry| {
+                entry.notebook != notebook || filter.is_some_and(|f| !f.check_entry(entry))
+            })
+            .map(|entry| entry.id)
+            .collect();
+
+        self.filtered_out_entries = filtered_out_entries;
     }
 
     /// Updates the colors tags mapping, assigning colors to new one and removing the non existing
@@ -425,11 +633,22 @@ where
         if all_tags.is_empty() {
             return;
         }
-        let all_tags_criteria: Vec<_> = all_tags
-            .into_iter()
-            .map(TagFilterOption::Tag)
-            .chain(std::iter::once(TagFilterOption::NoTags))
-            .collect();
+
+        // Tags are cycled within the level of the current tag in the tags hierarchy, with the
+        // option for entries without tags on the top level.
+        let tag_tree = TagTree::new(&all_tags);
+        let level_criteria = |parent: Option<&str>| -> Vec<TagFilterOption> {
+            let level_tags = tag_tree
+                .children(parent)
+                .map(|node| TagFilterOption::Tag(node.path.clone()));
+            match parent {
+                Some(_) => level_tags.collect(),
+                None => level_tags
+                    .chain(std::iter::once(TagFilterOption::NoTags))
+                    .collect(),
+            }
+        };
+        let all_tags_criteria = level_criteria(None);
 
         if let Some(mut filter) = self.filter.take() {
             let applied_tags_criteria: Vec<_> = filter
@@ -461,13 +680,22 @@ where
                         })
                         .expect("Criteria checked for having one Tag only");
 
-                    let tag_pos = all_tags_criteria
+                    let parent = match current_tag_criteria {
+                        TagFilterOption::Tag(tag) => parent_path(tag).map(String::from),
+                        TagFilterOption::NoTags => None,
+                    };
+                    let mut current_level = level_criteria(parent.as_deref());
+                    if current_level.is_empty() {
+                        current_level = all_tags_criteria;
+                    }
+
+                    let tag_pos = current_level
                         .iter()
                         .position(|t| t == current_tag_criteria)
                         .unwrap_or_default();
 
-                    let next_index = (tag_pos + 1) % all_tags_criteria.len();
-                    *current_tag_criteria = all_tags_criteria.into_iter().nth(next_index).unwrap();
+                    let next_index = (tag_pos + 1) % current_level.len();
+                    *current_tag_criteria = current_level.into_iter().nth(next_index).unwrap();
                 }
                 // Many tags exist => Clean them and apply the first one.
                 _ => {
@@ -530,6 +758,11 @@ where
         };
 
         self.state = state;
+
+        // Notebook set from the command line overrides the last active one.
+        if let Some(notebook) = self.settings.notebook.clone() {
+            self.state.notebook = Some(notebook);
+        }
     }
 
     pub fn persist_state(&self) -> anyhow::Result<()> {
@@ -567,6 +800,19 @@ where
             }
             Change::RemoveEntry(entry) => {
                 log::trace!("History Apply: Remove Entry: {entry:?}");
+
+                // Removed entries are restored from the trash unless they have been purged.
+                let in_trash = self
+                    .data_provide
+                    .load_trash()
+                    .await?
+                    .iter()
+                    .any(|trashed| trashed.id == entry.id);
+                if in_trash {
+                    let id = self.restore_entry_intern(entry.id, history_target).await?;
+                    return Ok(Some(id));
+                }
+
                 let id = self
                     .add_entry_intern(
                         entry.title,
@@ -574,6 +820,7 @@ where
                         entry.tags,
                         entry.priority,
                         Some(entry.content),
+                        entry.notebook,
                         history_target,
                     )
                     .await?;
@@ -1,21 +1,33 @@
+use std::{path::PathBuf, time::Duration};
+
 use anyhow::{Context, Result};
 use crossterm::event::{Event, EventStream, KeyEventKind};
 use ratatui::{Terminal, backend::Backend};
+use tokio::time::{Instant, sleep_until};
 
 use crate::app::{App, UIComponents};
 use crate::cli::PendingCliCommand;
 use crate::settings::{BackendType, Settings};
 use futures_util::StreamExt;
 
-use backend::DataProvider;
+#[cfg(feature = "http")]
+use backend::HttpDataProvide;
 #[cfg(feature = "json")]
 use backend::JsonDataProvide;
+#[cfg(feature = "markdown_dir")]
+use backend::MarkdownDirDataProvide;
 #[cfg(feature = "sqlite")]
 use backend::SqliteDataProvide;
+use backend::{DataProvider, GitDataProvide};
 
 use super::keymap::Input;
-use super::ui::Styles;
 use super::ui::ui_functions::render_message_centered;
+use super::ui::{Styles, UICommand};
+use super::watcher::BackendWatcher;
+
+/// Time to wait for more changes on the back-end files before reloading them, since writing them
+/// fires many events.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(300);
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum HandleInputReturnType {
@@ -39,8 +51,9 @@ pub async fn run<B: Backend>(
             } else {
                 crate::settings::json_backend::get_default_json_path()?
             };
-            let data_provider = JsonDataProvide::new(path);
-            run_intern(terminal, data_provider, settings, styles, pending_cmd).await
+            let data_provider = JsonDataProvide::new(path.clone())
+                .with_backup_count(settings.json_backend.backup_count);
+            run_with_provider(terminal, data_provider, path, settings, styles, pending_cmd).await
         }
         #[cfg(not(feature = "json"))]
         BackendType::Json => {
@@ -55,8 +68,10 @@ pub async fn run<B: Backend>(
             } else {
                 crate::settings::sqlite_backend::get_default_sqlite_path()?
             };
-            let data_provider = SqliteDataProvide::from_file(path).await?;
-            run_intern(terminal, data_provider, settings, styles, pending_cmd).await
+            let data_provider =
+                SqliteDataProvide::from_file(path.clone(), settings.sqlite_backend.durability)
+                    .await?;
+            run_with_provider(terminal, data_provider, path, settings, styles, pending_cmd).await
         }
         #[cfg(not(feature = "sqlite"))]
         BackendType::Sqlite => {
@@ -64,12 +79,87 @@ pub async fn run<B: Backend>(
                 "Feature 'sqlite' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'sqlite' feature"
             )
         }
+        #[cfg(feature = "markdown_dir")]
+        BackendType::MarkdownDir => {
+            let path = if let Some(path) = &settings.markdown_dir_backend.dir_path {
+                path.clone()
+            } else {
+                crate::settings::markdown_dir_backend::get_default_markdown_dir_path()?
+            };
+            let data_provider = MarkdownDirDataProvide::new(path.clone());
+            run_with_provider(terminal, data_provider, path, settings, styles, pending_cmd).await
+        }
+        #[cfg(not(feature = "markdown_dir"))]
+        BackendType::MarkdownDir => {
+            anyhow::bail!(
+                "Feature 'markdown_dir' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'markdown_dir' feature"
+            )
+        }
+        #[cfg(feature = "http")]
+        BackendType::Http => {
+            let url = settings
+                .http_backend
+                .url
+                .clone()
+                .unwrap_or_else(crate::settings::http_backend::get_default_http_url);
+            let data_provider =
+                HttpDataProvide::new(url).with_token(settings.http_backend.token.clone());
+            if settings.git_versioning {
+                log::warn!("Git versioning is ignored with the HTTP back-end");
+            }
+            run_intern(terminal, data_provider, None, settings, styles, pending_cmd).await
+        }
+        #[cfg(not(feature = "http"))]
+        BackendType::Http => {
+            anyhow::bail!(
+                "Feature 'http' is not installed. Please check your configs and set your backend to an installed feature, or reinstall the program with 'http' feature"
+            )
+        }
+    }
+}
+
+/// Runs the app with the given data provider, wrapping it with git versioning if enabled.
+/// The back-end path is watched for changes made outside the app.
+async fn run_with_provider<B, D>(
+    terminal: &mut Terminal<B>,
+    data_provider: D,
+    backend_path: PathBuf,
+    settings: Settings,
+    styles: Styles,
+    pending_cmd: Option<PendingCliCommand>,
+) -> anyhow::Result<()>
+where
+    B: Backend,
+    D: DataProvider,
+{
+    if settings.git_versioning {
+        let data_provider = GitDataProvide::new(data_provider, backend_path.clone())?;
+        run_intern(
+            terminal,
+            data_provider,
+            Some(backend_path),
+            settings,
+            styles,
+            pending_cmd,
+        )
+        .await
+    } else {
+        run_intern(
+            terminal,
+            data_provider,
+            Some(backend_path),
+            settings,
+            styles,
+            pending_cmd,
+        )
+        .await
     }
 }
 
 async fn run_intern<B, D>(
     terminal: &mut Terminal<B>,
     data_provider: D,
+    watch_path: Option<PathBuf>,
     settings: Settings,
     styles: Styles,
     pending_cmd: Option<PendingCliCommand>,
@@ -78,11 +168,13 @@ where
     B: Backend,
     D: DataProvider,
 {
+    let mut app = App::new(data_provider, settings).with_pinned_tag_colors(styles.tags.clone());
     let mut ui_components = UIComponents::new(styles);
-    let mut app = App::new(data_provider, settings);
     if let Some(cmd) = pending_cmd {
-        if let Err(err) = exec_pending_cmd(terminal, &app, cmd).await {
-            ui_components.show_err_msg(err.to_string());
+        match exec_pending_cmd(terminal, &app, cmd).await {
+            Ok(Some(msg)) => ui_components.show_info_msg(msg),
+            Ok(None) => {}
+            Err(err) => ui_components.show_err_msg(err.to_string()),
         }
     }
 
@@ -92,64 +184,122 @@ where
         ui_components.show_err_msg(err.to_string());
     }
 
+    if let Some(warning) = app.data_provide.take_load_warning() {
+        ui_components.show_warning_msg(warning);
+    }
+
+    match app.purge_expired_trash().await {
+        Ok(0) => {}
+        Ok(count) => log::info!("Purged {count} journals from the trash"),
+        Err(err) => log::warn!("Purging the trash failed. Error info: {err}"),
+    }
+
     ui_components.set_current_entry(app.entries.first().map(|entry| entry.id), &mut app);
 
     draw_ui(terminal, &mut app, &mut ui_components)?;
 
+    let mut watcher = watch_path.and_then(|path| match BackendWatcher::new(&path) {
+        Ok(watcher) => Some(watcher),
+        Err(err) => {
+            log::warn!("Changes outside the app can't be detected. Error info: {err:?}");
+            None
+        }
+    });
+    let mut reload_deadline: Option<Instant> = None;
+    let mut reload_pending = false;
+
     let mut input_stream = EventStream::new();
-    while let Some(event) = input_stream.next().await {
-        let event = event.context("Error getting input stream")?;
-        match handle_input(event, &mut app, &mut ui_components).await {
-            Ok(result) => {
-                match result {
-                    HandleInputReturnType::Handled => {
-                        ui_components.update_current_entry(&mut app);
-                        draw_ui(terminal, &mut app, &mut ui_components)?;
+    loop {
+        tokio::select! {
+            event = input_stream.next() => {
+                let Some(event) = event else {
+                    break;
+                };
+                let event = event.context("Error getting input stream")?;
+                match handle_input(event, &mut app, &mut ui_components).await {
+                    Ok(result) => {
+                        match result {
+                            HandleInputReturnType::Handled => {
+                                ui_components.update_current_entry(&mut app);
+                                draw_ui(terminal, &mut app, &mut ui_components)?;
+                            }
+                            HandleInputReturnType::NotFound => {
+                                // UI should be drawn even if the input isn't handled in the app
+                                // logic to catch events like resize, Font resize, Mouse
+                                // activation...
+                                draw_ui(terminal, &mut app, &mut ui_components)?;
+                            }
+                            HandleInputReturnType::ExitApp => {
+                                // Logging persisting errors by closing the app is enough
+                                if let Err(err) = app.persist_state() {
+                                    log::error!("Persisting app state failed: Error info {err}");
+                                }
+
+                                return Ok(());
+                            }
+                            HandleInputReturnType::Ignore => {}
+                        };
                     }
-                    HandleInputReturnType::NotFound => {
-                        // UI should be drawn even if the input isn't handled in the app logic to
-                        // catch events like resize, Font resize, Mouse activation...
+                    Err(err) => {
+                        ui_components.show_err_msg(err.to_string());
                         draw_ui(terminal, &mut app, &mut ui_components)?;
                     }
-                    HandleInputReturnType::ExitApp => {
-                        // Logging persisting errors by closing the app is enough
-                        if let Err(err) = app.persist_state() {
-                            log::error!("Persisting app state failed: Error info {err}");
-                        }
-
-                        return Ok(());
-                    }
-                    HandleInputReturnType::Ignore => {}
-                };
+                }
+            }
+            Some(()) = next_backend_change(watcher.as_mut()) => {
+                reload_deadline = Some(Instant::now() + RELOAD_DEBOUNCE);
             }
-            Err(err) => {
+            () = sleep_until(reload_deadline.unwrap_or_else(Instant::now)), if reload_deadline.is_some() => {
+                reload_deadline = None;
+                reload_pending = true;
+            }
+        }
+
+        // Changes are applied once the popups are closed to keep the entries they show valid.
+        if reload_pending && !ui_components.has_popup() {
+            reload_pending = false;
+            if let Err(err) = UICommand::MergeExternalChanges
+                .execute(&mut ui_components, &mut app)
+                .await
+            {
                 ui_components.show_err_msg(err.to_string());
-                draw_ui(terminal, &mut app, &mut ui_components)?;
             }
+            ui_components.update_current_entry(&mut app);
+            draw_ui(terminal, &mut app, &mut ui_components)?;
         }
     }
 
     Ok(())
 }
 
+/// Waits for the next change on the back-end files if they are watched.
+async fn next_backend_change(watcher: Option<&mut BackendWatcher>) -> Option<()> {
+    match watcher {
+        Some(watcher) => watcher.changed().await,
+        None => std::future::pending().await,
+    }
+}
+
 async fn exec_pending_cmd<B: Backend, D: DataProvider>(
     terminal: &mut Terminal<B>,
     app: &App<D>,
     pending_cmd: PendingCliCommand,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<String>> {
     match pending_cmd {
-        PendingCliCommand::ImportJournals(file_path) => {
+        PendingCliCommand::ImportJournals(file_path, strategy) => {
             terminal.draw(|f| render_message_centered(f, "Importing journals..."))?;
 
-            app.import_entries(file_path).await?;
+            let summary = app.import_entries(file_path, strategy).await?;
+
+            Ok(Some(format!("Import finished: {summary}")))
         }
         PendingCliCommand::AssignPriority(priority) => {
             terminal.draw(|f| render_message_centered(f, "Assigning Priority to Journals..."))?;
             app.assign_priority_to_entries(priority).await?;
+
+            Ok(None)
         }
     }
-
-    Ok(())
 }
 
 fn draw_ui<B: Backend, D: DataProvider>(
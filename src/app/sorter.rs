@@ -1,12 +1,14 @@
 use backend::Entry;
+use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
 use std::{cmp::Ordering, fmt::Display};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
 pub enum SortCriteria {
     Date,
     Priority,
     Title,
+    Modified,
 }
 
 impl Display for SortCriteria {
@@ -15,6 +17,7 @@ impl Display for SortCriteria {
             SortCriteria::Date => write!(f, "Date"),
             SortCriteria::Priority => write!(f, "Priority"),
             SortCriteria::Title => write!(f, "Title"),
+            SortCriteria::Modified => write!(f, "Modified"),
         }
     }
 }
@@ -25,6 +28,7 @@ impl SortCriteria {
             SortCriteria::Date => entry1.date.cmp(&entry2.date),
             SortCriteria::Priority => entry1.priority.cmp(&entry2.priority),
             SortCriteria::Title => entry1.title.cmp(&entry2.title),
+            SortCriteria::Modified => entry1.updated_at.cmp(&entry2.updated_at),
         };
 
         match order {
@@ -42,14 +46,17 @@ impl SortCriteria {
                 S::Date => (),
                 S::Priority => (),
                 S::Title => (),
+                S::Modified => (),
             };
         }
 
-        [S::Date, S::Priority, S::Title].iter().copied()
+        [S::Date, S::Priority, S::Title, S::Modified]
+            .iter()
+            .copied()
     }
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ValueEnum)]
 pub enum SortOrder {
     Ascending,
     Descending,
@@ -189,6 +196,24 @@ mod test {
         assert_eq!(ids, vec![0, 2, 1], "Title Descending");
     }
 
+    #[test]
+    fn sort_single_modified() {
+        let mut sorter = Sorter::default();
+        sorter.set_criteria(vec![SortCriteria::Modified]);
+        sorter.order = SortOrder::Ascending;
+
+        let mut entries = get_default_entries();
+        entries[1].updated_at = Utc.with_ymd_and_hms(2024, 2, 1, 1, 2, 3).unwrap();
+        entries.sort_by(|e1, e2| sorter.sort(e1, e2));
+        let ids = get_ids(&entries);
+        assert_eq!(ids, vec![0, 2, 1], "Modified Ascending");
+
+        sorter.order = SortOrder::Descending;
+        entries.sort_by(|e1, e2| sorter.sort(e1, e2));
+        let ids = get_ids(&entries);
+        assert_eq!(ids, vec![1, 2, 0], "Modified Descending");
+    }
+
     #[test]
     fn sort_multi() {
         let mut sorter = Sorter::default();
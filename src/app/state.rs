@@ -11,6 +11,9 @@ const STATE_FILE_NAME: &str = "state.json";
 pub struct AppState {
     pub sorter: Sorter,
     pub full_screen: bool,
+    /// Last active notebook. The default notebook is used if not set.
+    #[serde(default)]
+    pub notebook: Option<String>,
 }
 
 impl AppState {
@@ -37,10 +40,16 @@ impl AppState {
     }
 
     fn get_persist_path(settings: &Settings) -> anyhow::Result<PathBuf> {
+        Self::get_persist_dir(settings).map(|dir| dir.join(STATE_FILE_NAME))
+    }
+
+    /// Return the directory used to persist the application state, which is the configured one
+    /// if set.
+    pub fn get_persist_dir(settings: &Settings) -> anyhow::Result<PathBuf> {
         if let Some(path) = settings.app_state_dir.as_ref() {
-            Ok(path.join(STATE_FILE_NAME))
+            Ok(path.clone())
         } else {
-            Self::default_persist_dir().map(|dir| dir.join(STATE_FILE_NAME))
+            Self::default_persist_dir()
         }
     }
 
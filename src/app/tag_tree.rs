@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, HashSet};
+
+/// Separator of the levels in hierarchical tags (e.g. `work/projectA/meetings`).
+pub const TAG_PATH_SEPARATOR: char = '/';
+
+/// Checks if the tag is the given ancestor tag itself or one of its descendants.
+pub fn is_tag_or_descendant(tag: &str, ancestor: &str) -> bool {
+    tag.strip_prefix(ancestor)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(TAG_PATH_SEPARATOR))
+}
+
+/// Gets the path of the parent of the tag if it's nested in another one.
+pub fn parent_path(tag: &str) -> Option<&str> {
+    tag.rsplit_once(TAG_PATH_SEPARATOR)
+        .map(|(parent, _)| parent)
+}
+
+/// Node of the tags tree representing a tag path, which can be a parent only without being used
+/// as a tag on its own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagNode {
+    pub path: String,
+    pub depth: usize,
+    pub has_children: bool,
+}
+
+impl TagNode {
+    /// Gets the last level of the tag path.
+    pub fn name(&self) -> &str {
+        self.path
+            .rsplit(TAG_PATH_SEPARATOR)
+            .next()
+            .unwrap_or(&self.path)
+    }
+}
+
+/// Tags arranged as a tree by their paths, with the state of the collapsed nodes.
+#[derive(Debug, Clone, Default)]
+pub struct TagTree {
+    /// Nodes ordered depth-first with the children of each node sorted by their names.
+    nodes: Vec<TagNode>,
+    collapsed: HashSet<String>,
+}
+
+impl TagTree {
+    pub fn new<S: AsRef<str>>(tags: &[S]) -> Self {
+        // Paths are sorted by their levels, so the children come right after their parents.
+        let mut paths: BTreeMap<Vec<&str>, &str> = BTreeMap::new();
+        for tag in tags.iter().map(AsRef::as_ref) {
+            let separators = tag
+                .match_indices(TAG_PATH_SEPARATOR)
+                .map(|(idx, _)| idx)
+                .chain(std::iter::once(tag.len()));
+
+            for end in separators {
+                let path = &tag[..end];
+                paths.insert(path.split(TAG_PATH_SEPARATOR).collect(), path);
+            }
+        }
+
+        let levels: Vec<(usize, &str)> = paths
+            .iter()
+            .map(|(levels, path)| (levels.len() - 1, *path))
+            .collect();
+
+        let nodes = levels
+            .iter()
+            .enumerate()
+            .map(|(idx, (depth, path))| TagNode {
+                path: path.to_string(),
+                depth: *depth,
+                has_children: levels
+                    .get(idx + 1)
+                    .is_some_and(|(next_depth, _)| next_depth > depth),
+            })
+            .collect();
+
+        Self {
+            nodes,
+            collapsed: HashSet::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Gets all the nodes in the tree including the ones inside collapsed nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = &TagNode> {
+        self.nodes.iter()
+    }
+
+    pub fn get(&self, path: &str) -> Option<&TagNode> {
+        self.nodes.iter().find(|node| node.path == path)
+    }
+
+    pub fn is_collapsed(&self, path: &str) -> bool {
+        self.collapsed.contains(path)
+    }
+
+    /// Gets the nodes which aren't hidden inside collapsed nodes.
+    pub fn visible_nodes(&self) -> impl Iterator<Item = &TagNode> {
+        self.nodes.iter().filter(|node| {
+            !self.collapsed.iter().any(|collapsed| {
+                node.path != *collapsed && is_tag_or_descendant(&node.path, collapsed)
+            })
+        })
+    }
+
+    /// Gets the direct children of the node with the given path, or the root nodes if the path
+    /// isn't set.
+    pub fn children(&self, parent: Option<&str>) -> impl Iterator<Item = &TagNode> {
+        self.nodes
+            .iter()
+            .filter(move |node| parent_path(&node.path) == parent)
+    }
+
+    /// Collapses the node with the given path, returning whether it has been expanded before.
+    pub fn collapse(&mut self, path: &str) -> bool {
+        match self.get(path) {
+            Some(node) if node.has_children => self.collapsed.insert(path.to_owned()),
+            _ => false,
+        }
+    }
+
+    /// Expands the node with the given path, returning whether it has been collapsed before.
+    pub fn expand(&mut self, path: &str) -> bool {
+        self.collapsed.remove(path)
+    }
+
+    /// Gets the text to show the node in the lists with indentation for its depth and a marker
+    /// for its collapsing state if it has children.
+    pub fn node_text(&self, node: &TagNode) -> String {
+        let marker = match (node.has_children, self.is_collapsed(&node.path)) {
+            (false, _) => "  ",
+            (true, false) => "▾ ",
+            (true, true) => "▸ ",
+        };
+
+        format!("{}{marker}{}", "  ".repeat(node.depth), node.name())
+    }
+}
@@ -71,6 +71,30 @@ async fn test_content_smart_case() {
     assert_eq!(app.get_active_entries().count(), 2);
 }
 
+#[tokio::test]
+async fn test_search_content_with_filter() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let results = app.search_content("content").await.unwrap();
+    assert_eq!(results.len(), 2);
+
+    let results = app.search_content("CONTENT 2").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, 1);
+
+    // Entries filtered out must not be included in search results
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Title(String::from("Title 1")));
+    app.apply_filter(Some(filter));
+
+    let results = app.search_content("content").await.unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].0, 0);
+}
+
 #[tokio::test]
 async fn test_filter_priority() {
     let mut app = create_default_app();
@@ -93,6 +117,29 @@ async fn test_filter_priority() {
     assert_eq!(app.get_active_entries().count(), 2);
 }
 
+#[tokio::test]
+async fn test_filter_date_range() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let day = |month, day| chrono::NaiveDate::from_ymd_opt(2023, month, day).unwrap();
+
+    let mut filter = Filter::default();
+    filter.criteria.push(FilterCriterion::FromDate(day(10, 12)));
+    filter.criteria.push(FilterCriterion::ToDate(day(12, 1)));
+    app.apply_filter(Some(filter));
+
+    let ids: Vec<u32> = app.get_active_entries().map(|e| e.id).collect();
+    assert_eq!(ids, vec![0]);
+
+    let mut filter = Filter::default();
+    filter.criteria.push(FilterCriterion::FromDate(day(10, 13)));
+    app.apply_filter(Some(filter));
+
+    let ids: Vec<u32> = app.get_active_entries().map(|e| e.id).collect();
+    assert_eq!(ids, vec![1]);
+}
+
 #[tokio::test]
 async fn test_filter_relations() {
     let mut app = create_default_app();
@@ -128,9 +175,14 @@ async fn cycle_tag_no_tags() {
     app.add_entry("Title_1".into(), Utc::now(), Vec::new(), Some(1))
         .await
         .unwrap();
-    app.add_entry("Title_2".into(), Utc::now(), Vec::new(), Some(2))
-        .await
-        .unwrap();
+    app.add_entry(
+        "Title_2".into(),
+        Utc::now() - chrono::Days::new(1),
+        Vec::new(),
+        Some(2),
+    )
+    .await
+    .unwrap();
 
     // No panic on cycle with not tags
     app.cycle_tags_in_filter();
@@ -265,3 +317,131 @@ async fn cycle_tag_existing_filter() {
         1
     );
 }
+
+async fn add_nested_tags_entries(app: &mut App<MockDataProvider>) {
+    let entries_tags = [
+        ("Title_3", "work/projectA/meetings"),
+        ("Title_4", "work/projectB"),
+        ("Title_5", "home"),
+    ];
+
+    for (days, (title, tag)) in entries_tags.into_iter().enumerate() {
+        app.add_entry(
+            title.into(),
+            Utc::now() - chrono::Days::new(days as u64),
+            vec![tag.into()],
+            None,
+        )
+        .await
+        .unwrap();
+    }
+}
+
+fn current_tag_criterion(app: &App<MockDataProvider>) -> &TagFilterOption {
+    match app
+        .filter
+        .as_ref()
+        .and_then(|f| f.criteria.first())
+        .unwrap()
+    {
+        FilterCriterion::Tag(tag) => tag,
+        invalid => panic!("Invalid criteria: {invalid:?}"),
+    }
+}
+
+#[tokio::test]
+async fn filter_parent_tag() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+    add_nested_tags_entries(&mut app).await;
+
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Tag(TagFilterOption::Tag("work".into())));
+    app.apply_filter(Some(filter));
+
+    // Parent tag must match all its descendants
+    let mut titles: Vec<_> = app.get_active_entries().map(|e| e.title.as_str()).collect();
+    titles.sort();
+    assert_eq!(titles, vec!["Title_3", "Title_4"]);
+
+    // Parent tags which aren't used on their own must match their descendants too
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Tag(TagFilterOption::Tag(
+            "work/projectA".into(),
+        )));
+    app.apply_filter(Some(filter));
+
+    assert_eq!(app.get_active_entries().count(), 1);
+    assert_eq!(app.get_active_entries().next().unwrap().title, "Title_3");
+
+    // Parent tags criteria must be kept when the entries change
+    app.add_entry(
+        "Title_6".into(),
+        Utc::now() - chrono::Days::new(5),
+        vec![],
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        current_tag_criterion(&app),
+        &TagFilterOption::Tag("work/projectA".into())
+    );
+
+    // Tags with the same prefix but on another path must not be matched
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Tag(TagFilterOption::Tag("wor".into())));
+    app.apply_filter(Some(filter));
+
+    assert_eq!(app.get_active_entries().count(), 0);
+}
+
+#[tokio::test]
+async fn cycle_tag_within_level() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+    add_nested_tags_entries(&mut app).await;
+
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Tag(TagFilterOption::Tag(
+            "work/projectA".into(),
+        )));
+    app.apply_filter(Some(filter));
+
+    // Nested tags must cycle between their siblings only
+    app.cycle_tags_in_filter();
+    assert_eq!(
+        current_tag_criterion(&app),
+        &TagFilterOption::Tag("work/projectB".into())
+    );
+
+    app.cycle_tags_in_filter();
+    assert_eq!(
+        current_tag_criterion(&app),
+        &TagFilterOption::Tag("work/projectA".into())
+    );
+
+    // Top level tags must cycle between the root tags and the untagged entries
+    let mut filter = Filter::default();
+    filter
+        .criteria
+        .push(FilterCriterion::Tag(TagFilterOption::Tag("work".into())));
+    app.apply_filter(Some(filter));
+
+    app.cycle_tags_in_filter();
+    assert_eq!(current_tag_criterion(&app), &TagFilterOption::NoTags);
+
+    app.cycle_tags_in_filter();
+    assert_eq!(
+        current_tag_criterion(&app),
+        &TagFilterOption::Tag("Tag 1".into())
+    );
+}
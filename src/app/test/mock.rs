@@ -7,6 +7,7 @@ use super::*;
 #[derive(Default)]
 pub struct MockDataProvider {
     entries: RwLock<Vec<Entry>>,
+    trash: RwLock<Vec<Entry>>,
     return_error: bool,
 }
 
@@ -15,6 +16,7 @@ impl MockDataProvider {
         let entries = RwLock::from(get_default_entries());
         MockDataProvider {
             entries,
+            trash: RwLock::default(),
             return_error: false,
         }
     }
@@ -41,7 +43,23 @@ impl DataProvider for MockDataProvider {
     async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
         self.early_return()?;
         let mut entries = self.entries.write().unwrap();
-        let new_id = entries.last().map_or(0, |entry| entry.id + 1);
+
+        if let Some(existing) = entries.iter().find(|e| {
+            e.notebook == entry.notebook && e.date.date_naive() == entry.date.date_naive()
+        }) {
+            return Err(ModifyEntryError::DuplicateDay {
+                day: entry.date.date_naive(),
+                existing_id: existing.id,
+            });
+        }
+
+        let trash = self.trash.read().unwrap();
+        let new_id = entries
+            .iter()
+            .chain(trash.iter())
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(0);
 
         let entry = Entry::from_draft(new_id, entry);
 
@@ -55,14 +73,19 @@ impl DataProvider for MockDataProvider {
 
         let mut entries = self.entries.write().unwrap();
 
-        entries.retain(|entry| entry.id != entry_id);
+        if let Some(pos) = entries.iter().position(|entry| entry.id == entry_id) {
+            let mut entry = entries.remove(pos);
+            entry.deleted_at = Some(Utc::now());
+            self.trash.write().unwrap().push(entry);
+        }
 
         Ok(())
     }
 
-    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+    async fn update_entry(&self, mut entry: Entry) -> Result<Entry, ModifyEntryError> {
         self.early_return()?;
 
+        entry.updated_at = Utc::now();
         let mut entry_clone = entry.clone();
 
         let mut entries = self.entries.write().unwrap();
@@ -77,6 +100,40 @@ impl DataProvider for MockDataProvider {
         Ok(entry)
     }
 
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        self.early_return()?;
+
+        Ok(self.trash.read().unwrap().clone())
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        self.early_return()?;
+
+        let mut trash = self.trash.write().unwrap();
+        let pos = trash
+            .iter()
+            .position(|entry| entry.id == entry_id)
+            .ok_or(anyhow!("No item found in trash"))?;
+
+        let mut entry = trash.remove(pos);
+        entry.deleted_at = None;
+
+        self.entries.write().unwrap().push(entry.clone());
+
+        Ok(entry)
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        self.early_return()?;
+
+        self.trash
+            .write()
+            .unwrap()
+            .retain(|entry| entry.id != entry_id);
+
+        Ok(())
+    }
+
     async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
         self.early_return()?;
 
@@ -6,7 +6,7 @@ use self::mock::MockDataProvider;
 
 use super::*;
 
-mod mock;
+pub(crate) mod mock;
 mod undo_redo;
 
 fn get_default_entries() -> Vec<Entry> {
@@ -70,7 +70,11 @@ async fn test_data_provider_errors() {
     assert!(app.delete_entry(0).await.is_err());
     assert!(app.get_current_entry().is_none());
     assert!(app.export_entries(PathBuf::default()).await.is_err());
-    assert!(app.import_entries(PathBuf::default()).await.is_err());
+    assert!(
+        app.import_entries(PathBuf::default(), ImportStrategy::default())
+            .await
+            .is_err()
+    );
 }
 
 #[tokio::test]
@@ -105,6 +109,61 @@ async fn test_add_entry() {
     assert_eq!(app.get_all_tags().len(), 3);
 }
 
+#[tokio::test]
+async fn test_add_entry_existing_day() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let date = Utc.with_ymd_and_hms(2023, 12, 2, 0, 0, 0).unwrap();
+
+    let entry_id = app
+        .add_entry(String::from("2023_12_02_Saturday"), date, Vec::new(), None)
+        .await
+        .unwrap();
+
+    // Existing entry of the day must be returned instead of adding a new one
+    assert_eq!(entry_id, 1);
+    assert_eq!(app.get_active_entries().count(), 2);
+}
+
+#[tokio::test]
+async fn test_notebooks() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    assert_eq!(app.active_notebook(), DEFAULT_NOTEBOOK);
+    assert_eq!(app.get_notebooks(), vec![String::from(DEFAULT_NOTEBOOK)]);
+
+    app.set_active_notebook(String::from("work"));
+    assert_eq!(app.get_active_entries().count(), 0);
+    assert!(app.get_all_tags().is_empty());
+
+    // Days are unique per notebook only
+    let date = Utc.with_ymd_and_hms(2023, 12, 2, 0, 0, 0).unwrap();
+    let entry_id = app
+        .add_entry(
+            String::from("Work"),
+            date,
+            vec![String::from("Work Tag")],
+            None,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(entry_id, 2);
+    assert_eq!(app.get_entry(entry_id).unwrap().notebook, "work");
+    assert_eq!(app.get_active_entries().count(), 1);
+    assert_eq!(app.get_all_tags(), vec![String::from("Work Tag")]);
+    assert_eq!(
+        app.get_notebooks(),
+        vec![String::from(DEFAULT_NOTEBOOK), String::from("work")]
+    );
+
+    app.set_active_notebook(String::from(DEFAULT_NOTEBOOK));
+    assert_eq!(app.get_active_entries().count(), 2);
+    assert!(app.get_entry(entry_id).is_none());
+}
+
 #[tokio::test]
 async fn test_remove_entry() {
     let mut app = create_default_app();
@@ -118,6 +177,45 @@ async fn test_remove_entry() {
     assert!(app.get_all_tags().is_empty());
 }
 
+#[tokio::test]
+async fn test_reload_external_changes() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    assert!(app.reload_external_changes().await.unwrap().is_none());
+
+    // Change the entry through the data provider directly like other instances would do.
+    let mut changed = app.get_entry(0).unwrap().clone();
+    changed.content = String::from("Changed outside");
+    app.data_provide.update_entry(changed).await.unwrap();
+
+    let previous = app.reload_external_changes().await.unwrap().unwrap();
+
+    let previous_entry = previous.iter().find(|e| e.id == 0).unwrap();
+    assert_eq!(previous_entry.content, "Content 1");
+    assert_eq!(app.get_entry(0).unwrap().content, "Changed outside");
+    assert!(app.reload_external_changes().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_restore_removed_entry() {
+    let mut app = create_default_app();
+    app.load_entries().await.unwrap();
+
+    let mut removed = app.get_entry(0).unwrap().clone();
+    app.data_provide.remove_entry(0).await.unwrap();
+    app.reload_external_changes().await.unwrap().unwrap();
+    assert!(app.get_entry(0).is_none());
+
+    removed.content = String::from("Unsaved content");
+    let entry_id = app.restore_removed_entry(removed).await.unwrap();
+
+    let restored = app.get_entry(entry_id).unwrap();
+    assert_eq!(restored.title, "Title 1");
+    assert_eq!(restored.content, "Unsaved content");
+    assert_eq!(app.get_active_entries().count(), 2);
+}
+
 #[tokio::test]
 async fn test_current_entry() {
     let mut app = create_default_app();
@@ -196,7 +196,8 @@ pub fn exec_delete_current_entry<D: DataProvider>(
     app: &App<D>,
 ) -> CmdResult {
     if app.current_entry_id.is_some() {
-        let msg = MsgBoxType::Question("Do you want to remove the current journal?".into());
+        let msg =
+            MsgBoxType::Question("Do you want to move the current journal to the trash?".into());
         let msg_actions = MsgBoxActions::YesNo;
         ui_components.show_msg_box(msg, msg_actions, Some(UICommand::DeleteCurrentEntry));
     }
@@ -519,6 +520,91 @@ pub async fn continue_show_sort_options<D: DataProvider>(
     Ok(HandleInputReturnType::Handled)
 }
 
+pub fn exec_show_notebooks<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+) -> CmdResult {
+    if ui_components.has_unsaved() {
+        ui_components.show_unsaved_msg_box(Some(UICommand::ShowNotebooks));
+    } else {
+        show_notebooks(ui_components, app);
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+fn show_notebooks<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
+    let notebook_popup = NotebookPopup::new(app.get_notebooks(), app.active_notebook().to_owned());
+    ui_components
+        .popup_stack
+        .push(Popup::Notebook(Box::new(notebook_popup)));
+}
+
+pub async fn continue_show_notebooks<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    match msg_box_result {
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+        MsgBoxResult::Yes => {
+            exec_save_entry_content(ui_components, app).await?;
+            show_notebooks(ui_components, app);
+        }
+        MsgBoxResult::No => {
+            discard_current_content(ui_components, app);
+            show_notebooks(ui_components, app);
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub async fn exec_show_trash<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+) -> CmdResult {
+    if ui_components.has_unsaved() {
+        ui_components.show_unsaved_msg_box(Some(UICommand::ShowTrash));
+    } else {
+        show_trash(ui_components, app).await?;
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+async fn show_trash<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+) -> anyhow::Result<()> {
+    let trash_popup = TrashPopup::new(app.load_trash().await?);
+    ui_components
+        .popup_stack
+        .push(Popup::Trash(Box::new(trash_popup)));
+
+    Ok(())
+}
+
+pub async fn continue_show_trash<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    match msg_box_result {
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+        MsgBoxResult::Yes => {
+            exec_save_entry_content(ui_components, app).await?;
+            show_trash(ui_components, app).await?;
+        }
+        MsgBoxResult::No => {
+            discard_current_content(ui_components, app);
+            show_trash(ui_components, app).await?;
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
 pub fn go_to_top_entry<D: DataProvider>(ui_components: &mut UIComponents, app: &mut App<D>) {
     let top_id = app.get_active_entries().next().map(|entry| entry.id);
 
@@ -3,9 +3,12 @@ use crate::app::{
     ui::{help_popup::KeybindingsTabs, *},
 };
 
-use backend::DataProvider;
+use backend::{DataProvider, Entry, merge_three_way};
 
-use super::{CmdResult, editor_cmd::exec_save_entry_content};
+use super::{
+    CmdResult,
+    editor_cmd::{discard_current_content, exec_save_entry_content},
+};
 
 pub fn exec_quit(ui_components: &mut UIComponents) -> CmdResult {
     if ui_components.has_unsaved() {
@@ -95,6 +98,10 @@ async fn reload_all<D: DataProvider>(
     app.load_entries().await?;
     ui_components.set_current_entry(app.current_entry_id, app);
 
+    if let Some(warning) = app.data_provide.take_load_warning() {
+        ui_components.show_warning_msg(warning);
+    }
+
     Ok(())
 }
 
@@ -115,6 +122,116 @@ pub async fn continue_reload_all<D: DataProvider>(
     Ok(HandleInputReturnType::Handled)
 }
 
+/// Loads the entries if they have been changed outside the app. Unsaved changes in the editor are
+/// kept, offering to merge the external changes into them if the current journal has been changed
+/// too.
+pub async fn exec_reload_external_changes<D: DataProvider>(
+    ui_components: &mut UIComponents<'_>,
+    app: &mut App<D>,
+) -> CmdResult {
+    let unsaved_entry = if ui_components.has_unsaved() {
+        app.get_current_entry().cloned()
+    } else {
+        None
+    };
+
+    let Some(previous_entries) = app.reload_external_changes().await? else {
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    if let Some(warning) = app.data_provide.take_load_warning() {
+        ui_components.show_warning_msg(warning);
+    }
+
+    let find_content = |entries: &[Entry], id: u32| {
+        entries
+            .iter()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.content.clone())
+    };
+
+    let Some(unsaved_entry) = unsaved_entry else {
+        // Editor is reset only if the current journal has been changed to keep its cursor.
+        let current_changed = app
+            .current_entry_id
+            .is_none_or(|id| find_content(&previous_entries, id) != find_content(&app.entries, id));
+
+        if current_changed {
+            ui_components.set_current_entry(app.current_entry_id, app);
+        } else {
+            ui_components.select_current_entry_in_list(app);
+        }
+
+        return Ok(HandleInputReturnType::Handled);
+    };
+
+    match find_content(&app.entries, unsaved_entry.id) {
+        Some(content) if content != unsaved_entry.content => {
+            ui_components.external_merge_base = Some(unsaved_entry.content);
+            ui_components.select_current_entry_in_list(app);
+
+            let msg = MsgBoxType::Question(format!(
+                "Journal '{}' has been changed outside the app. Merge these changes into your unsaved changes?\nNo: Discard your changes. Cancel: Keep your changes only",
+                unsaved_entry.title
+            ));
+            ui_components.show_msg_box(
+                msg,
+                MsgBoxActions::YesNoCancel,
+                Some(UICommand::MergeExternalChanges),
+            );
+        }
+        Some(_) => ui_components.select_current_entry_in_list(app),
+        None => {
+            // Journal removed outside the app is added again to keep the unsaved changes.
+            let title = unsaved_entry.title.clone();
+            let entry = Entry {
+                content: ui_components.editor.get_content(),
+                ..unsaved_entry
+            };
+            let entry_id = app.restore_removed_entry(entry).await?;
+            ui_components.set_current_entry(Some(entry_id), app);
+
+            ui_components.show_info_msg(format!(
+                "Journal '{title}' has been removed outside the app and has been added again with your unsaved changes"
+            ));
+        }
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
+pub fn continue_merge_external_changes<D: DataProvider>(
+    ui_components: &mut UIComponents,
+    app: &mut App<D>,
+    msg_box_result: MsgBoxResult,
+) -> CmdResult {
+    let base = ui_components.external_merge_base.take();
+
+    match msg_box_result {
+        MsgBoxResult::Yes => {
+            let base = base.expect("Merge base must be set when asking to merge external changes");
+            let external = app
+                .get_current_entry()
+                .map(|entry| entry.content.clone())
+                .unwrap_or_default();
+
+            match merge_three_way(&base, &ui_components.editor.get_content(), &external) {
+                Ok(merged) => ui_components.editor.set_entry_content(&merged, app),
+                Err(marked) => {
+                    ui_components.editor.set_entry_content(&marked, app);
+                    ui_components.show_warning_msg(
+                        "Conflicting changes have been marked in the editor. Please resolve them and save the journal".into(),
+                    );
+                }
+            }
+        }
+        MsgBoxResult::No => discard_current_content(ui_components, app),
+        MsgBoxResult::Ok | MsgBoxResult::Cancel => {}
+    }
+
+    Ok(HandleInputReturnType::Handled)
+}
+
 pub async fn exec_undo<D: DataProvider>(
     ui_components: &mut UIComponents<'_>,
     app: &mut App<D>,
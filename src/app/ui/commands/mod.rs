@@ -61,12 +61,15 @@ pub enum UICommand {
     CutOsClipboard,
     PasteOsClipboard,
     ShowSortOptions,
+    ShowNotebooks,
+    ShowTrash,
     GoToTopEntry,
     GoToBottomEntry,
     PageUpEntries,
     PageDownEntries,
     Undo,
     Redo,
+    MergeExternalChanges,
 }
 
 #[derive(Debug, Clone)]
@@ -111,9 +114,10 @@ impl UICommand {
                 "Edit current journal",
                 "Open entry dialog to edit current journal entry if any",
             ),
-            UICommand::DeleteCurrentEntry => {
-                CommandInfo::new("Delete journal", "Delete current journal entry if any")
-            }
+            UICommand::DeleteCurrentEntry => CommandInfo::new(
+                "Delete journal",
+                "Move current journal entry to the trash if any",
+            ),
             UICommand::StartEditEntryContent => CommandInfo::new(
                 "Edit journal content",
                 "Start editing current journal entry content in editor",
@@ -162,7 +166,7 @@ impl UICommand {
             ),
             UICommand::MulSelDeleteEntries => CommandInfo::new(
                 "Delete selection",
-                "Delete selected journals in multi selection mode",
+                "Move selected journals to the trash in multi selection mode",
             ),
             UICommand::MulSelExportEntries => CommandInfo::new(
                 "Export selection",
@@ -204,6 +208,14 @@ impl UICommand {
                 "Open sort options",
                 "Open sort popup to set the sorting options of the journals",
             ),
+            UICommand::ShowNotebooks => CommandInfo::new(
+                "Switch notebook",
+                "Open notebooks popup to switch to another notebook or create a new one",
+            ),
+            UICommand::ShowTrash => CommandInfo::new(
+                "Open trash",
+                "Open trash popup to restore or permanently delete the deleted journals",
+            ),
             UICommand::GoToTopEntry => CommandInfo::new(
                 "Go to top journal",
                 "Go to the top entry in the journals' list",
@@ -221,6 +233,10 @@ impl UICommand {
             ),
             UICommand::Undo => CommandInfo::new("Undo", "Undo the latest change on journals"),
             UICommand::Redo => CommandInfo::new("Redo", "Redo the latest change on journals"),
+            UICommand::MergeExternalChanges => CommandInfo::new(
+                "Merge external changes",
+                "Reload the journals changed outside the app, merging them into unsaved changes",
+            ),
         }
     }
 
@@ -266,6 +282,8 @@ impl UICommand {
             UICommand::CutOsClipboard => exec_cut_os_clipboard(ui_components),
             UICommand::PasteOsClipboard => exec_paste_os_clipboard(ui_components),
             UICommand::ShowSortOptions => exec_show_sort_options(ui_components, app),
+            UICommand::ShowNotebooks => exec_show_notebooks(ui_components, app),
+            UICommand::ShowTrash => exec_show_trash(ui_components, app).await,
             cmd @ UICommand::GoToTopEntry => {
                 check_unsaved_then_exec_cmd(*cmd, go_to_top_entry, ui_components, app)
             }
@@ -280,6 +298,9 @@ impl UICommand {
             }
             UICommand::Undo => exec_undo(ui_components, app).await,
             UICommand::Redo => exec_redo(ui_components, app).await,
+            UICommand::MergeExternalChanges => {
+                exec_reload_external_changes(ui_components, app).await
+            }
         }
     }
 
@@ -351,6 +372,10 @@ impl UICommand {
             UICommand::ShowSortOptions => {
                 continue_show_sort_options(ui_components, app, msg_box_result).await
             }
+            UICommand::ShowNotebooks => {
+                continue_show_notebooks(ui_components, app, msg_box_result).await
+            }
+            UICommand::ShowTrash => continue_show_trash(ui_components, app, msg_box_result).await,
             UICommand::GoToTopEntry => {
                 continue_cmd_after_check_unsaved(
                     go_to_top_entry,
@@ -389,6 +414,9 @@ impl UICommand {
             }
             UICommand::Undo => continue_undo(ui_components, app, msg_box_result).await,
             UICommand::Redo => continue_redo(ui_components, app, msg_box_result).await,
+            UICommand::MergeExternalChanges => {
+                continue_merge_external_changes(ui_components, app, msg_box_result)
+            }
         }
     }
 }
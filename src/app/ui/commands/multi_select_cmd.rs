@@ -119,7 +119,7 @@ pub fn exec_delete_selected_entries<D: DataProvider>(
     }
 
     let msg = MsgBoxType::Question(format!(
-        "Do you want to delete the selected {} entries",
+        "Do you want to move the selected {} entries to the trash?",
         app.selected_entries.len()
     ));
     let msg_action = MsgBoxActions::YesNo;
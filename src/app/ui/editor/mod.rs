@@ -14,7 +14,7 @@ use ratatui::{
 
 use crate::app::{App, keymap::Input, runner::HandleInputReturnType};
 
-use backend::DataProvider;
+use backend::{DataProvider, LogLine};
 use tui_textarea::{CursorMove, Scrolling, TextArea};
 
 use super::Styles;
@@ -125,7 +125,6 @@ impl<'a> Editor<'a> {
                 // Fetch current time
                 let now = chrono::Local::now();
                 let timestamp_full = now.format("%Y_%m_%d_%H_%M_%S").to_string();
-                let timestamp_short = now.format("%H:%M").to_string();
 
                 // Get entry text
                 let entry_text = self.entry_area.lines().first().cloned().unwrap_or_default();
@@ -135,8 +134,8 @@ impl<'a> Editor<'a> {
 
                     // Append to content_area with timestamp
                     let mut lines = self.content_area.lines().to_vec();
-                    let new_line = format!("{} {}", timestamp_short, entry_text);
-                    lines.push(new_line);
+                    let new_line = LogLine::new(now.time(), entry_text);
+                    lines.push(new_line.to_string());
                     let mut new_content = TextArea::new(lines);
                     new_content.move_cursor(CursorMove::Bottom);
                     new_content.move_cursor(CursorMove::End);
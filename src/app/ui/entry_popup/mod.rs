@@ -24,6 +24,7 @@ mod tags;
 
 const FOOTER_TEXT: &str = "Enter or <Ctrl-m>: confirm | Esc or <Ctrl-c>: Cancel | Tab: Change focused control | <Ctrl-Space> or <Ctrl-t>: Open tags";
 const FOOTER_MARGIN: u16 = 15;
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M";
 
 pub struct EntryPopup<'a> {
     date_txt: TextArea<'a>,
@@ -35,6 +36,8 @@ pub struct EntryPopup<'a> {
     tags_err_msg: String,
     priority_err_msg: String,
     tags_popup: Option<TagsPopup>,
+    /// Creation and modification times of the edited entry.
+    timestamps_txt: Option<String>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -92,6 +95,18 @@ impl EntryPopup<'_> {
         let mut priority_txt = TextArea::new(vec![prio]);
         priority_txt.move_cursor(CursorMove::End);
 
+        let timestamps_txt = format!(
+            "Created: {} | Modified: {}",
+            entry
+                .created_at
+                .with_timezone(&Local)
+                .format(TIMESTAMP_FORMAT),
+            entry
+                .updated_at
+                .with_timezone(&Local)
+                .format(TIMESTAMP_FORMAT)
+        );
+
         let mut entry_popup = Self {
             date_txt,
             tags_txt,
@@ -102,6 +117,7 @@ impl EntryPopup<'_> {
             tags_err_msg: String::default(),
             priority_err_msg: String::default(),
             tags_popup: None,
+            timestamps_txt: Some(timestamps_txt),
         };
 
         entry_popup.validate_all();
@@ -109,7 +125,7 @@ impl EntryPopup<'_> {
     }
 
     pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
-        let mut area = centered_rect_exact_height(70, 17, area);
+        let mut area = centered_rect_exact_height(70, 18, area);
         const FOOTER_LEN: u16 = FOOTER_TEXT.len() as u16 + FOOTER_MARGIN;
         if area.width < FOOTER_LEN {
             area.height += FOOTER_LEN / area.width;
@@ -120,7 +136,7 @@ impl EntryPopup<'_> {
         frame.render_widget(Clear, area);
         frame.render_widget(block, area);
 
-        // Layout for 3 fields + footer
+        // Layout for 3 fields + timestamps + footer
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .horizontal_margin(4)
@@ -130,6 +146,7 @@ impl EntryPopup<'_> {
                     Constraint::Length(3), // Date
                     Constraint::Length(3), // Priority
                     Constraint::Length(3), // Tags
+                    Constraint::Length(1), // Timestamps
                     Constraint::Min(1),    // Footer
                 ]
                 .as_ref(),
@@ -243,6 +260,11 @@ impl EntryPopup<'_> {
         frame.render_widget(&self.priority_txt, chunks[1]);
         frame.render_widget(&self.tags_txt, chunks[2]);
 
+        if let Some(timestamps_txt) = self.timestamps_txt.as_deref() {
+            let timestamps = Paragraph::new(timestamps_txt).alignment(Alignment::Center);
+            frame.render_widget(timestamps, chunks[3]);
+        }
+
         // Footer
         let footer = Paragraph::new(FOOTER_TEXT)
             .alignment(Alignment::Center)
@@ -252,7 +274,7 @@ impl EntryPopup<'_> {
                     .borders(Borders::NONE)
                     .style(Style::default()),
             );
-        frame.render_widget(footer, chunks[3]);
+        frame.render_widget(footer, chunks[4]);
 
         if let Some(tags_popup) = self.tags_popup.as_mut() {
             tags_popup.render_widget(frame, area, styles)
@@ -10,13 +10,13 @@ use ratatui::{
 
 use crate::app::{
     keymap::Input,
+    tag_tree::{TagTree, parent_path},
     ui::{Styles, entry_popup::tags_to_text, ui_functions::centered_rect},
 };
 
 use super::text_to_tags;
 
-const FOOTER_TEXT: &str =
-    r"<Space>: Toggle Selected | Enter or <Ctrl-m>: Confirm | Esc, q or <Ctrl-c>: Cancel";
+const FOOTER_TEXT: &str = r"<Space>: Toggle Selected | <h>/<l>: Collapse/Expand | Enter or <Ctrl-m>: Confirm | Esc, q or <Ctrl-c>: Cancel";
 const FOOTER_MARGINE: u16 = 4;
 
 pub enum TagsPopupReturn {
@@ -27,6 +27,8 @@ pub enum TagsPopupReturn {
 
 pub struct TagsPopup {
     state: ListState,
+    tag_tree: TagTree,
+    /// Paths of the visible tags in the tree.
     tags: Vec<String>,
     selected_tags: BTreeSet<String>,
 }
@@ -43,19 +45,18 @@ impl TagsPopup {
             .cloned()
             .collect();
 
-        unsaved_tags
-            .into_iter()
-            .rev()
-            .for_each(|tag| tags.insert(0, tag));
+        tags.extend(unsaved_tags);
 
         let selected_tags = BTreeSet::from_iter(existing_tags);
 
         let mut tags_popup = Self {
             state,
-            tags,
+            tag_tree: TagTree::new(&tags),
+            tags: Vec::new(),
             selected_tags,
         };
 
+        tags_popup.update_visible_tags();
         tags_popup.cycle_next_tag();
 
         tags_popup
@@ -114,10 +115,15 @@ impl TagsPopup {
             .map(|tag| {
                 let is_selected = self.selected_tags.contains(tag);
 
+                let tag_text = match self.tag_tree.get(tag) {
+                    Some(node) => self.tag_tree.node_text(node),
+                    None => tag.to_owned(),
+                };
+
                 let (tag_text, style) = if is_selected {
-                    (format!("* {tag}"), selected_style)
+                    (format!("* {tag_text}"), selected_style)
                 } else {
-                    (tag.to_owned(), Style::reset())
+                    (tag_text, Style::reset())
                 };
 
                 ListItem::new(tag_text).style(style)
@@ -148,6 +154,8 @@ impl TagsPopup {
             KeyCode::Char('j') | KeyCode::Down => self.cycle_next_tag(),
             KeyCode::Char('k') | KeyCode::Up => self.cycle_prev_tag(),
             KeyCode::Char(' ') => self.toggle_selected(),
+            KeyCode::Char('h') | KeyCode::Left => self.collapse_selected(),
+            KeyCode::Char('l') | KeyCode::Right => self.expand_selected(),
             KeyCode::Esc | KeyCode::Char('q') => TagsPopupReturn::Cancel,
             KeyCode::Char('c') if has_control => TagsPopupReturn::Cancel,
             KeyCode::Enter => self.confirm(),
@@ -186,6 +194,57 @@ impl TagsPopup {
         TagsPopupReturn::Keep
     }
 
+    /// Refreshes the tags in the list after the nodes of the tags tree have been collapsed or
+    /// expanded, keeping the selection on the same tag.
+    fn update_visible_tags(&mut self) {
+        let selected_tag = self.selected_tag();
+
+        self.tags = self
+            .tag_tree
+            .visible_nodes()
+            .map(|node| node.path.clone())
+            .collect();
+
+        if let Some(tag) = selected_tag {
+            let idx = self.tags.iter().position(|t| *t == tag);
+            self.state.select(idx.or(Some(0)));
+        }
+    }
+
+    fn selected_tag(&self) -> Option<String> {
+        self.state
+            .selected()
+            .and_then(|idx| self.tags.get(idx))
+            .cloned()
+    }
+
+    /// Collapses the selected tag, or moves the selection to its parent if it can't be collapsed.
+    fn collapse_selected(&mut self) -> TagsPopupReturn {
+        let Some(tag) = self.selected_tag() else {
+            return TagsPopupReturn::Keep;
+        };
+
+        if self.tag_tree.collapse(&tag) {
+            self.update_visible_tags();
+        } else if let Some(parent_idx) =
+            parent_path(&tag).and_then(|parent| self.tags.iter().position(|t| t == parent))
+        {
+            self.state.select(Some(parent_idx));
+        }
+
+        TagsPopupReturn::Keep
+    }
+
+    fn expand_selected(&mut self) -> TagsPopupReturn {
+        if let Some(tag) = self.selected_tag()
+            && self.tag_tree.expand(&tag)
+        {
+            self.update_visible_tags();
+        }
+
+        TagsPopupReturn::Keep
+    }
+
     fn toggle_selected(&mut self) -> TagsPopupReturn {
         if let Some(idx) = self.state.selected() {
             let tag = self
@@ -204,10 +263,12 @@ impl TagsPopup {
     }
 
     fn confirm(&self) -> TagsPopupReturn {
-        // We must take the tags from the tags vector because it matches the order in the tags list
+        // We must take the tags from the tags tree because it matches the order in the tags list,
+        // including the selected tags inside collapsed nodes.
         let selected_tags: Vec<String> = self
-            .tags
-            .iter()
+            .tag_tree
+            .nodes()
+            .map(|node| &node.path)
             .filter(|tag| self.selected_tags.contains(*tag))
             .cloned()
             .collect();
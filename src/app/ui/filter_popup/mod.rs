@@ -12,13 +12,14 @@ use tui_textarea::{CursorMove, TextArea};
 use crate::app::{
     filter::{CriteriaRelation, Filter, FilterCriterion, criterion::TagFilterOption},
     keymap::Input,
+    tag_tree::{TagTree, parent_path},
 };
 
 use super::{PopupReturn, Styles, ui_functions::centered_rect};
 
 type FilterPopupReturn = PopupReturn<Option<Filter>>;
 
-const FOOTER_TEXT: &str = r"Tab: Change focused control | Enter or <Ctrl-m>: Confirm | Esc or <Ctrl-c>: Cancel | <Ctrl-r>: Change Matching Logic | <Space>: Tags Toggle Selected";
+const FOOTER_TEXT: &str = r"Tab: Change focused control | Enter or <Ctrl-m>: Confirm | Esc or <Ctrl-c>: Cancel | <Ctrl-r>: Change Matching Logic | <Space>: Tags Toggle Selected | <h>/<l>: Collapse/Expand Tag";
 const FOOTER_MARGIN: usize = 8;
 
 /// Text to show in tags list indicating that none tagged entries are included.
@@ -31,6 +32,8 @@ const NO_TAGS_TEXT: &str = "NO_TAGS (Entries with no tags)  ";
 pub struct FilterPopup<'a> {
     active_control: FilterControl,
     tags_state: ListState,
+    tag_tree: TagTree,
+    /// Paths of the visible tags in the tree followed by the no-tags option.
     tags: Vec<String>,
     relation: CriteriaRelation,
     selected_tags: HashSet<String>,
@@ -49,12 +52,8 @@ enum FilterControl {
 }
 
 impl FilterPopup<'_> {
-    pub fn new(mut tags: Vec<String>, filter: Option<Filter>) -> Self {
+    pub fn new(tags: Vec<String>, filter: Option<Filter>) -> Self {
         let filter = filter.unwrap_or_default();
-        // Add no tags option to list of tags in case we have some tags.
-        if !tags.is_empty() {
-            tags.push(NO_TAGS_TEXT.into());
-        }
 
         let relation = filter.relation;
 
@@ -73,6 +72,8 @@ impl FilterPopup<'_> {
             FilterCriterion::Title(title_search) => title_text = title_search,
             FilterCriterion::Content(content_search) => content_text = content_search,
             FilterCriterion::Priority(prio) => priority_text = prio.to_string(),
+            // Date ranges are set from the command line only.
+            FilterCriterion::FromDate(_) | FilterCriterion::ToDate(_) => {}
         });
 
         let mut title_txt = TextArea::new(vec![title_text]);
@@ -89,7 +90,8 @@ impl FilterPopup<'_> {
         let mut filter_popup = FilterPopup {
             active_control,
             tags_state: ListState::default(),
-            tags,
+            tag_tree: TagTree::new(&tags),
+            tags: Vec::new(),
             relation,
             selected_tags,
             title_txt,
@@ -98,6 +100,7 @@ impl FilterPopup<'_> {
             priority_err_msg: String::default(),
         };
 
+        filter_popup.update_visible_tags();
         filter_popup.cycle_next_tag();
 
         filter_popup.validate_priority();
@@ -240,10 +243,15 @@ impl FilterPopup<'_> {
             .map(|tag| {
                 let is_selected = self.selected_tags.contains(tag);
 
+                let tag_text = match self.tag_tree.get(tag) {
+                    Some(node) => self.tag_tree.node_text(node),
+                    None => tag.to_owned(),
+                };
+
                 let (tag_text, style) = if is_selected {
-                    (format!("* {tag}"), selected_style)
+                    (format!("* {tag_text}"), selected_style)
                 } else {
-                    (tag.to_owned(), Style::reset())
+                    (tag_text, Style::reset())
                 };
 
                 ListItem::new(tag_text).style(style)
@@ -344,6 +352,14 @@ impl FilterPopup<'_> {
                     self.toggle_selected();
                     FilterPopupReturn::KeepPopup
                 }
+                KeyCode::Char('h') | KeyCode::Left => {
+                    self.collapse_selected();
+                    FilterPopupReturn::KeepPopup
+                }
+                KeyCode::Char('l') | KeyCode::Right => {
+                    self.expand_selected();
+                    FilterPopupReturn::KeepPopup
+                }
                 KeyCode::Char('r') => {
                     self.change_relation();
                     FilterPopupReturn::KeepPopup
@@ -398,6 +414,58 @@ impl FilterPopup<'_> {
         self.tags_state.select(Some(new_index));
     }
 
+    /// Refreshes the tags in the list after the nodes of the tags tree have been collapsed or
+    /// expanded, keeping the selection on the same tag.
+    fn update_visible_tags(&mut self) {
+        let selected_tag = self.selected_tag();
+
+        self.tags = self
+            .tag_tree
+            .visible_nodes()
+            .map(|node| node.path.clone())
+            .collect();
+
+        // Add no tags option to list of tags in case we have some tags.
+        if !self.tag_tree.is_empty() {
+            self.tags.push(NO_TAGS_TEXT.into());
+        }
+
+        if let Some(tag) = selected_tag {
+            let idx = self.tags.iter().position(|t| *t == tag);
+            self.tags_state.select(idx.or(Some(0)));
+        }
+    }
+
+    fn selected_tag(&self) -> Option<String> {
+        self.tags_state
+            .selected()
+            .and_then(|idx| self.tags.get(idx))
+            .cloned()
+    }
+
+    /// Collapses the selected tag, or moves the selection to its parent if it can't be collapsed.
+    fn collapse_selected(&mut self) {
+        let Some(tag) = self.selected_tag() else {
+            return;
+        };
+
+        if self.tag_tree.collapse(&tag) {
+            self.update_visible_tags();
+        } else if let Some(parent_idx) =
+            parent_path(&tag).and_then(|parent| self.tags.iter().position(|t| t == parent))
+        {
+            self.tags_state.select(Some(parent_idx));
+        }
+    }
+
+    fn expand_selected(&mut self) {
+        if let Some(tag) = self.selected_tag()
+            && self.tag_tree.expand(&tag)
+        {
+            self.update_visible_tags();
+        }
+    }
+
     fn change_relation(&mut self) {
         self.relation = match self.relation {
             CriteriaRelation::And => CriteriaRelation::Or,
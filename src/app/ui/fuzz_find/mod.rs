@@ -1,4 +1,6 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
+
+use backend::MatchSpan;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use fuzzy_matcher::{FuzzyMatcher, skim::SkimMatcherV2};
@@ -15,14 +17,20 @@ use crate::app::keymap::Input;
 
 use super::{Styles, ui_functions::centered_rect};
 
-const FOOTER_TEXT: &str = "Esc, Enter, <Ctrl-m>, <Ctrl-c>: Close | Up, Down, <Ctrl-n>, <Ctrl-p>: cycle through filtered list";
+const FOOTER_TEXT: &str = "Esc, Enter, <Ctrl-m>, <Ctrl-c>: Close | Up, Down, <Ctrl-n>, <Ctrl-p>: cycle through filtered list | Tab: Switch between titles and content search";
 const FOOTER_MARGINE: usize = 8;
+/// Maximum length in bytes of the content snippets shown for content search results.
+const SNIPPET_MAX_LEN: usize = 80;
+/// Length in bytes of the content to keep before the first match in snippets.
+const SNIPPET_CONTEXT_LEN: usize = 20;
 
 pub struct FuzzFindPopup<'a> {
     query_text_box: TextArea<'a>,
     entries: HashMap<u32, String>,
     search_query: Option<String>,
+    search_mode: SearchMode,
     filtered_entries: Vec<FilteredEntry>,
+    content_matches: Vec<ContentMatch>,
     list_state: ListState,
     matcher: SkimMatcherV2,
 }
@@ -30,6 +38,82 @@ pub struct FuzzFindPopup<'a> {
 pub enum FuzzFindReturn {
     Close,
     SelectEntry(Option<u32>),
+    /// Content search query has changed and the matches must be provided via
+    /// [`FuzzFindPopup::set_content_matches()`]
+    SearchContent(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SearchMode {
+    /// Fuzzy matching on the titles of the entries.
+    Titles,
+    /// Full-text search on the content of the entries using the data provider.
+    Content,
+}
+
+/// Content search result with a snippet of the content around the matches.
+pub struct ContentMatch {
+    id: u32,
+    snippet: String,
+    /// Ranges of the matches inside the snippet
+    highlights: Vec<Range<usize>>,
+}
+
+impl ContentMatch {
+    /// Creates a content match with a snippet from the line of the first match.
+    pub fn new(id: u32, content: &str, spans: &[MatchSpan]) -> Self {
+        let first_span = spans
+            .iter()
+            .find(|span| span.start <= span.end && span.end <= content.len());
+
+        let (start, end) = match first_span {
+            Some(span) => {
+                let line_start = content[..span.start].rfind('\n').map_or(0, |idx| idx + 1);
+                let line_end = content[span.start..]
+                    .find('\n')
+                    .map_or(content.len(), |idx| span.start + idx);
+
+                let start = if span.start - line_start > SNIPPET_CONTEXT_LEN {
+                    ceil_char_boundary(content, span.start - SNIPPET_CONTEXT_LEN)
+                } else {
+                    line_start
+                };
+
+                (start, line_end.min(start + SNIPPET_MAX_LEN))
+            }
+            None => {
+                let line_end = content.find('\n').unwrap_or(content.len());
+                (0, line_end.min(SNIPPET_MAX_LEN))
+            }
+        };
+
+        let end = floor_char_boundary(content, end);
+
+        let highlights = spans
+            .iter()
+            .filter(|span| span.start >= start && span.end <= end && span.start < span.end)
+            .map(|span| span.start - start..span.end - start)
+            .collect();
+
+        Self {
+            id,
+            snippet: content[start..end].to_owned(),
+            highlights,
+        }
+    }
+}
+
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    (0..=index.min(text.len()))
+        .rev()
+        .find(|idx| text.is_char_boundary(*idx))
+        .unwrap_or(0)
+}
+
+fn ceil_char_boundary(text: &str, index: usize) -> usize {
+    (index..=text.len())
+        .find(|idx| text.is_char_boundary(*idx))
+        .unwrap_or(text.len())
 }
 
 struct FilteredEntry {
@@ -55,7 +139,9 @@ impl FuzzFindPopup<'_> {
             query_text_box,
             entries,
             search_query: None,
+            search_mode: SearchMode::Titles,
             filtered_entries: Vec::new(),
+            content_matches: Vec::new(),
             list_state: ListState::default(),
             matcher: SkimMatcherV2::default().smart_case(),
         }
@@ -67,7 +153,10 @@ impl FuzzFindPopup<'_> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .title("Fuzzy Find");
+            .title(match self.search_mode {
+                SearchMode::Titles => "Fuzzy Find",
+                SearchMode::Content => "Content Search",
+            });
 
         frame.render_widget(Clear, area);
         frame.render_widget(block, area);
@@ -92,7 +181,10 @@ impl FuzzFindPopup<'_> {
 
         frame.render_widget(&self.query_text_box, chunks[0]);
 
-        self.render_entries_list(frame, chunks[1], styles);
+        match self.search_mode {
+            SearchMode::Titles => self.render_entries_list(frame, chunks[1], styles),
+            SearchMode::Content => self.render_content_matches(frame, chunks[1], styles),
+        }
 
         self.render_footer(frame, chunks[2]);
     }
@@ -140,6 +232,52 @@ impl FuzzFindPopup<'_> {
         frame.render_stateful_widget(list, area, &mut self.list_state);
     }
 
+    fn render_content_matches(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let items: Vec<ListItem> = self
+            .content_matches
+            .iter()
+            .map(|content_match| {
+                let entry_title = self
+                    .entries
+                    .get(&content_match.id)
+                    .expect("Entry must be in entries map");
+
+                let mut spans = Vec::new();
+                let mut last_end = 0;
+                for range in content_match.highlights.iter() {
+                    if range.start < last_end {
+                        continue;
+                    }
+                    spans.push(Span::raw(&content_match.snippet[last_end..range.start]));
+                    spans.push(Span::styled(
+                        &content_match.snippet[range.clone()],
+                        Style::default()
+                            .add_modifier(Modifier::BOLD)
+                            .fg(Color::LightBlue),
+                    ));
+                    last_end = range.end;
+                }
+                spans.push(Span::raw(&content_match.snippet[last_end..]));
+
+                ListItem::new(vec![
+                    Line::from(entry_title.as_str()),
+                    Line::from(spans).style(Style::default().add_modifier(Modifier::DIM)),
+                ])
+            })
+            .collect();
+
+        let block_title = format!("Entries: {}", self.content_matches.len());
+
+        let block = Block::default().title(block_title).borders(Borders::ALL);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
     fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
         let footer = Paragraph::new(FOOTER_TEXT)
             .alignment(Alignment::Center)
@@ -163,37 +301,84 @@ impl FuzzFindPopup<'_> {
             KeyCode::Char('p') if has_control => self.cycle_prev_entry(),
             KeyCode::Down => self.cycle_next_entry(),
             KeyCode::Char('n') if has_control => self.cycle_next_entry(),
+            KeyCode::Tab => {
+                self.search_mode = match self.search_mode {
+                    SearchMode::Titles => SearchMode::Content,
+                    SearchMode::Content => SearchMode::Titles,
+                };
+                if let Some(query) = self.update_search_query() {
+                    return FuzzFindReturn::SearchContent(query);
+                }
+            }
             _ => {
                 if self.query_text_box.input(KeyEvent::from(input)) {
-                    self.update_search_query();
+                    if let Some(query) = self.update_search_query() {
+                        return FuzzFindReturn::SearchContent(query);
+                    }
                 }
             }
         }
 
-        let selected_id = self.list_state.selected().map(|idx| {
-            self.filtered_entries
-                .get(idx)
-                .expect("Index must be in the list boundaries")
-                .id
-        });
+        FuzzFindReturn::SelectEntry(self.selected_id())
+    }
+
+    /// Sets the results of the content search, returning the id of the selected entry.
+    pub fn set_content_matches(&mut self, matches: Vec<ContentMatch>) -> Option<u32> {
+        self.content_matches = matches;
+        self.select_first_result();
+
+        self.selected_id()
+    }
+
+    fn results_count(&self) -> usize {
+        match self.search_mode {
+            SearchMode::Titles => self.filtered_entries.len(),
+            SearchMode::Content => self.content_matches.len(),
+        }
+    }
+
+    fn selected_id(&self) -> Option<u32> {
+        self.list_state
+            .selected()
+            .map(|idx| match self.search_mode {
+                SearchMode::Titles => {
+                    self.filtered_entries
+                        .get(idx)
+                        .expect("Index must be in the list boundaries")
+                        .id
+                }
+                SearchMode::Content => {
+                    self.content_matches
+                        .get(idx)
+                        .expect("Index must be in the list boundaries")
+                        .id
+                }
+            })
+    }
 
-        FuzzFindReturn::SelectEntry(selected_id)
+    fn select_first_result(&mut self) {
+        if self.results_count() == 0 {
+            self.list_state.select(None);
+        } else {
+            // Select first item when search query is updated
+            self.list_state.select(Some(0));
+        }
     }
 
     pub fn cycle_next_entry(&mut self) {
-        if self.filtered_entries.is_empty() {
+        if self.results_count() == 0 {
             return;
         }
 
         let mut new_index = self.list_state.selected().map_or(0, |idx| idx + 1);
 
-        new_index = new_index.clamp(0, self.filtered_entries.len() - 1);
+        new_index = new_index.clamp(0, self.results_count() - 1);
 
         self.list_state.select(Some(new_index));
     }
 
     pub fn cycle_prev_entry(&mut self) {
-        if self.filtered_entries.is_empty() {
+        if self.results_count() == 0 {
             return;
         }
 
@@ -205,8 +390,11 @@ impl FuzzFindPopup<'_> {
         self.list_state.select(Some(new_index));
     }
 
-    fn update_search_query(&mut self) {
+    /// Updates the search results with the current query. Returns the query in content search
+    /// mode since the search must be done via the data provider.
+    fn update_search_query(&mut self) -> Option<String> {
         self.filtered_entries.clear();
+        self.content_matches.clear();
 
         let query_text = self
             .query_text_box
@@ -220,6 +408,11 @@ impl FuzzFindPopup<'_> {
             Some(query_text.to_owned())
         };
 
+        if self.search_mode == SearchMode::Content {
+            self.list_state.select(None);
+            return self.search_query.clone();
+        }
+
         if let Some(query) = self.search_query.as_ref() {
             self.filtered_entries = self
                 .entries
@@ -234,11 +427,8 @@ impl FuzzFindPopup<'_> {
             self.filtered_entries.sort_by(|a, b| b.score.cmp(&a.score));
         }
 
-        if self.filtered_entries.is_empty() {
-            self.list_state.select(None);
-        } else {
-            // Select first item when search query is updated
-            self.list_state.select(Some(0));
-        }
+        self.select_first_result();
+
+        None
     }
 }
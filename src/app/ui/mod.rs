@@ -13,7 +13,9 @@ use self::{
     fuzz_find::FuzzFindPopup,
     help_popup::{HelpInputInputReturn, HelpPopup},
     msg_box::{MsgBox, MsgBoxActions, MsgBoxType},
+    notebook_popup::NotebookPopup,
     sort_popup::SortPopup,
+    trash_popup::{TrashPopup, TrashPopupReturn},
 };
 
 use super::{
@@ -41,8 +43,10 @@ mod footer;
 mod fuzz_find;
 mod help_popup;
 mod msg_box;
+mod notebook_popup;
 mod sort_popup;
 pub mod themes;
+mod trash_popup;
 pub mod ui_functions;
 
 pub use commands::UICommand;
@@ -62,6 +66,8 @@ pub enum Popup<'a> {
     Filter(Box<FilterPopup<'a>>),
     FuzzFind(Box<FuzzFindPopup<'a>>),
     Sort(Box<SortPopup>),
+    Notebook(Box<NotebookPopup<'a>>),
+    Trash(Box<TrashPopup>),
 }
 
 #[derive(Debug, Clone)]
@@ -82,6 +88,9 @@ pub struct UIComponents<'a> {
     popup_stack: Vec<Popup<'a>>,
     pub active_control: ControlType,
     pending_command: Option<UICommand>,
+    /// Content of the current journal before it has been changed outside the app, used as base to
+    /// merge the external changes into the unsaved changes.
+    external_merge_base: Option<String>,
 }
 
 impl UIComponents<'_> {
@@ -107,6 +116,7 @@ impl UIComponents<'_> {
             popup_stack: Vec::new(),
             active_control,
             pending_command: None,
+            external_merge_base: None,
         }
     }
 
@@ -124,6 +134,14 @@ impl UIComponents<'_> {
         self.editor.set_current_entry(entry_id, app);
     }
 
+    /// Selects the current entry in the entries list without changing the editor content.
+    pub fn select_current_entry_in_list<D: DataProvider>(&mut self, app: &App<D>) {
+        let entry_index = app
+            .current_entry_id
+            .and_then(|id| app.get_active_entries().position(|entry| entry.id == id));
+        self.entries_list.state.select(entry_index);
+    }
+
     pub fn render_ui<D>(&mut self, f: &mut Frame, app: &App<D>)
     where
         D: DataProvider,
@@ -184,6 +202,10 @@ impl UIComponents<'_> {
                 }
                 Popup::FuzzFind(fuzz_find) => fuzz_find.render_widget(f, f.area(), &self.styles),
                 Popup::Sort(sort_popup) => sort_popup.render_widget(f, f.area(), &self.styles),
+                Popup::Notebook(notebook_popup) => {
+                    notebook_popup.render_widget(f, f.area(), &self.styles)
+                }
+                Popup::Trash(trash_popup) => trash_popup.render_widget(f, f.area(), &self.styles),
             }
         }
     }
@@ -321,6 +343,23 @@ impl UIComponents<'_> {
                                 self.set_current_entry(entry_id, app);
                             }
                         }
+                        fuzz_find::FuzzFindReturn::SearchContent(query) => {
+                            let matches = app
+                                .search_content(&query)
+                                .await?
+                                .into_iter()
+                                .filter_map(|(id, spans)| {
+                                    app.get_entry(id).map(|entry| {
+                                        fuzz_find::ContentMatch::new(id, &entry.content, &spans)
+                                    })
+                                })
+                                .collect();
+
+                            let entry_id = fuzz_find.set_content_matches(matches);
+                            if entry_id.is_some() {
+                                self.set_current_entry(entry_id, app);
+                            }
+                        }
                     },
                     Popup::Sort(sort_popup) => match sort_popup.handle_input(input) {
                         PopupReturn::KeepPopup => {}
@@ -338,6 +377,41 @@ impl UIComponents<'_> {
                             self.set_current_entry(current_entry_id, app);
                         }
                     },
+                    Popup::Notebook(notebook_popup) => match notebook_popup.handle_input(input) {
+                        PopupReturn::KeepPopup => {}
+                        PopupReturn::Cancel => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        PopupReturn::Apply(notebook) => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+
+                            app.set_active_notebook(notebook);
+
+                            let entry_id = app.get_active_entries().next().map(|entry| entry.id);
+                            self.set_current_entry(entry_id, app);
+                        }
+                    },
+                    Popup::Trash(trash_popup) => match trash_popup.handle_input(input) {
+                        TrashPopupReturn::KeepPopup => {}
+                        TrashPopupReturn::Close => {
+                            self.popup_stack.pop().expect("popup stack isn't empty");
+                        }
+                        TrashPopupReturn::Restore(entry_id) => {
+                            match app.restore_from_trash(entry_id).await {
+                                Ok(entry_id) => {
+                                    trash_popup.remove_entry(entry_id);
+                                    self.set_current_entry(Some(entry_id), app);
+                                }
+                                Err(err) => {
+                                    self.show_err_msg(format!("Restoring journal failed: {err}"))
+                                }
+                            }
+                        }
+                        TrashPopupReturn::Purge(entry_id) => {
+                            app.purge_from_trash(entry_id).await?;
+                            trash_popup.remove_entry(entry_id);
+                        }
+                    },
                 }
                 Ok(HandleInputReturnType::Handled)
             }
@@ -439,6 +513,14 @@ impl UIComponents<'_> {
         self.show_msg_box(MsgBoxType::Error(err_txt), MsgBoxActions::Ok, None);
     }
 
+    pub fn show_warning_msg(&mut self, warning_txt: String) {
+        self.show_msg_box(MsgBoxType::Warning(warning_txt), MsgBoxActions::Ok, None);
+    }
+
+    pub fn show_info_msg(&mut self, info_txt: String) {
+        self.show_msg_box(MsgBoxType::Info(info_txt), MsgBoxActions::Ok, None);
+    }
+
     pub fn update_current_entry<D: DataProvider>(&mut self, app: &mut App<D>) {
         if app.get_current_entry().is_none() {
             let first_entry = app.get_active_entries().next().map(|entry| entry.id);
@@ -0,0 +1,242 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+use tui_textarea::TextArea;
+
+use crate::app::keymap::Input;
+
+use super::{PopupReturn, Styles, ui_functions::centered_rect};
+
+type NotebookPopupReturn = PopupReturn<String>;
+
+const FOOTER_TEXT: &str = "Enter, <Ctrl-m>: Switch to selected notebook | Esc, <Ctrl-c>: Cancel | Up, Down, <Ctrl-n>, <Ctrl-p>: cycle through notebooks | Type a new name to create a notebook";
+const FOOTER_MARGIN: usize = 8;
+
+pub struct NotebookPopup<'a> {
+    name_txt: TextArea<'a>,
+    notebooks: Vec<String>,
+    active_notebook: String,
+    /// Notebooks matching the typed name followed by the typed name itself if it's new.
+    items: Vec<NotebookItem>,
+    list_state: ListState,
+}
+
+struct NotebookItem {
+    name: String,
+    is_new: bool,
+}
+
+impl NotebookPopup<'_> {
+    pub fn new(notebooks: Vec<String>, active_notebook: String) -> Self {
+        let mut name_txt = TextArea::default();
+        let block = Block::default().title("Name").borders(Borders::ALL);
+        name_txt.set_cursor_line_style(Style::default());
+        name_txt.set_block(block);
+
+        let mut notebook_popup = Self {
+            name_txt,
+            notebooks,
+            active_notebook,
+            items: Vec::new(),
+            list_state: ListState::default(),
+        };
+
+        notebook_popup.update_items();
+
+        let active_idx = notebook_popup
+            .items
+            .iter()
+            .position(|item| item.name == notebook_popup.active_notebook);
+        if active_idx.is_some() {
+            notebook_popup.list_state.select(active_idx);
+        }
+
+        notebook_popup
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(50, 60, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Notebooks");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(2)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Length(3),
+                    Constraint::Min(4),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        frame.render_widget(&self.name_txt, chunks[0]);
+        self.render_notebooks_list(frame, chunks[1], styles);
+        self.render_footer(frame, chunks[2]);
+    }
+
+    fn render_notebooks_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let items: Vec<ListItem> = self
+            .items
+            .iter()
+            .map(|item| {
+                if item.is_new {
+                    ListItem::new(format!("{} (new)", item.name))
+                        .style(Style::default().add_modifier(Modifier::ITALIC))
+                } else if item.name == self.active_notebook {
+                    ListItem::new(format!("{} (active)", item.name))
+                        .style(Style::default().add_modifier(Modifier::BOLD))
+                } else {
+                    ListItem::new(item.name.as_str())
+                }
+            })
+            .collect();
+
+        let block = Block::default().borders(Borders::ALL);
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect) {
+        let footer = Paragraph::new(FOOTER_TEXT)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .block(
+                Block::default()
+                    .borders(Borders::NONE)
+                    .style(Style::default()),
+            );
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> NotebookPopupReturn {
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc => PopupReturn::Cancel,
+            KeyCode::Char('c') if has_control => PopupReturn::Cancel,
+            KeyCode::Enter => self.confirm(),
+            KeyCode::Char('m') if has_control => self.confirm(),
+            KeyCode::Up => {
+                self.cycle_prev_item();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('p') if has_control => {
+                self.cycle_prev_item();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Down => {
+                self.cycle_next_item();
+                PopupReturn::KeepPopup
+            }
+            KeyCode::Char('n') if has_control => {
+                self.cycle_next_item();
+                PopupReturn::KeepPopup
+            }
+            _ => {
+                if self.name_txt.input(KeyEvent::from(input)) {
+                    self.update_items();
+                    self.select_first_item();
+                }
+                PopupReturn::KeepPopup
+            }
+        }
+    }
+
+    fn confirm(&self) -> NotebookPopupReturn {
+        match self
+            .list_state
+            .selected()
+            .and_then(|idx| self.items.get(idx))
+        {
+            Some(item) => PopupReturn::Apply(item.name.clone()),
+            None => PopupReturn::KeepPopup,
+        }
+    }
+
+    /// Fills the list with the notebooks containing the typed name, adding the name itself as
+    /// a new notebook if there is no notebook with the same name.
+    fn update_items(&mut self) {
+        let name = self
+            .name_txt
+            .lines()
+            .first()
+            .expect("Name text box has one line")
+            .trim();
+        let name_lower = name.to_lowercase();
+
+        self.items = self
+            .notebooks
+            .iter()
+            .filter(|notebook| notebook.to_lowercase().contains(&name_lower))
+            .map(|notebook| NotebookItem {
+                name: notebook.to_owned(),
+                is_new: false,
+            })
+            .collect();
+
+        if !name.is_empty() && !self.notebooks.iter().any(|notebook| notebook == name) {
+            self.items.push(NotebookItem {
+                name: name.to_owned(),
+                is_new: true,
+            });
+        }
+    }
+
+    fn select_first_item(&mut self) {
+        if self.items.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state.select(Some(0));
+        }
+    }
+
+    fn cycle_next_item(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| (idx + 1).min(self.items.len() - 1));
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev_item(&mut self) {
+        if self.items.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| idx.saturating_sub(1));
+
+        self.list_state.select(Some(new_index));
+    }
+}
@@ -3,8 +3,9 @@ mod general_styles;
 mod journals_list_styles;
 mod msgbox;
 mod style;
+mod tags;
 
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 use anyhow::Context;
 use ratatui::style::Color;
@@ -15,6 +16,7 @@ pub use general_styles::GeneralStyles;
 pub use journals_list_styles::JournalsListStyles;
 pub use msgbox::MsgBoxColors;
 pub use style::Style;
+pub use tags::PinnedTagColors;
 
 use crate::settings::settings_default_dir_path;
 
@@ -36,6 +38,9 @@ pub struct Styles {
     pub editor: EditorStyles,
     #[serde(default)]
     pub msgbox: MsgBoxColors,
+    /// Colors pinned to tags by their names.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, PinnedTagColors>,
 }
 
 impl Styles {
@@ -328,4 +333,34 @@ warning = "Blue"
         assert_eq!(style.msgbox.info, def_msg.info);
         assert_eq!(style.msgbox.question, def_msg.question);
     }
+
+    #[test]
+    fn pinned_tags() {
+        let text = r##"
+[tags.work]
+fg = "Black"
+bg = "LightGreen"
+
+[tags."work/meetings"]
+bg = "#4F11BA"
+        "##;
+
+        let style = Styles::deserialize(text).unwrap();
+        assert_eq!(style.tags.len(), 2);
+        assert_eq!(
+            style.tags["work"],
+            PinnedTagColors {
+                fg: Some(Color::Black),
+                bg: Some(Color::LightGreen),
+            }
+        );
+        assert_eq!(style.tags["work/meetings"].fg, None);
+        assert_eq!(
+            style.tags["work/meetings"].bg,
+            Some(Color::from_u32(0x004F11BA))
+        );
+
+        assert_eq!(style.general, GeneralStyles::default());
+        assert_eq!(style.msgbox, MsgBoxColors::default());
+    }
 }
@@ -0,0 +1,13 @@
+use super::*;
+
+/// Colors pinned to a tag in the themes, overriding the generated ones for the tag and its
+/// descendants in the tags hierarchy.
+///
+/// Missing parts are picked to keep the tag readable against the defined one.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub struct PinnedTagColors {
+    #[serde(default)]
+    pub fg: Option<Color>,
+    #[serde(default)]
+    pub bg: Option<Color>,
+}
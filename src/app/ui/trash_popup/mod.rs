@@ -0,0 +1,216 @@
+use backend::Entry;
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+};
+
+use crate::app::keymap::Input;
+
+use super::{Styles, ui_functions::centered_rect};
+
+const FOOTER_TEXT: &str = "Enter, r: Restore selected journal | d, Delete: Purge selected journal | Esc, q, <Ctrl-c>: Close | Up, Down, j, k: cycle through journals";
+const CONFIRM_PURGE_TEXT: &str =
+    "Delete the selected journal permanently? y: Yes | Any other key: No";
+const FOOTER_MARGIN: usize = 8;
+
+pub enum TrashPopupReturn {
+    KeepPopup,
+    Close,
+    Restore(u32),
+    Purge(u32),
+}
+
+pub struct TrashPopup {
+    entries: Vec<Entry>,
+    list_state: ListState,
+    confirm_purge: bool,
+}
+
+impl TrashPopup {
+    pub fn new(entries: Vec<Entry>) -> Self {
+        let mut list_state = ListState::default();
+        if !entries.is_empty() {
+            list_state.select(Some(0));
+        }
+
+        Self {
+            entries,
+            list_state,
+            confirm_purge: false,
+        }
+    }
+
+    /// Removes the entry with the given id from the list after it has been restored or purged.
+    pub fn remove_entry(&mut self, entry_id: u32) {
+        self.entries.retain(|entry| entry.id != entry_id);
+
+        match self.list_state.selected() {
+            _ if self.entries.is_empty() => self.list_state.select(None),
+            Some(idx) => self
+                .list_state
+                .select(Some(idx.min(self.entries.len() - 1))),
+            None => self.list_state.select(Some(0)),
+        }
+    }
+
+    pub fn render_widget(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let area = centered_rect(60, 60, area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .title("Trash");
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(block, area);
+
+        let footer_height = textwrap::fill(FOOTER_TEXT, (area.width as usize) - FOOTER_MARGIN)
+            .lines()
+            .count();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .horizontal_margin(2)
+            .vertical_margin(2)
+            .constraints(
+                [
+                    Constraint::Min(4),
+                    Constraint::Length(footer_height.try_into().unwrap()),
+                ]
+                .as_ref(),
+            )
+            .split(area);
+
+        self.render_entries_list(frame, chunks[0], styles);
+        self.render_footer(frame, chunks[1], styles);
+    }
+
+    fn render_entries_list(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let block = Block::default().borders(Borders::ALL);
+
+        if self.entries.is_empty() {
+            let empty_msg = Paragraph::new("The trash is empty")
+                .alignment(Alignment::Center)
+                .block(block);
+            frame.render_widget(empty_msg, area);
+            return;
+        }
+
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let deleted_at = entry
+                    .deleted_at
+                    .map(|deleted_at| deleted_at.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+
+                ListItem::new(Line::from(vec![
+                    Span::raw(format!("{} ", entry.date.format("%Y-%m-%d"))),
+                    Span::raw(entry.title.as_str()),
+                    Span::styled(
+                        format!("  (deleted {deleted_at})"),
+                        styles.journals_list.date_priority,
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(styles.general.list_highlight_active)
+            .highlight_symbol(">> ");
+
+        frame.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    fn render_footer(&mut self, frame: &mut Frame, area: Rect, styles: &Styles) {
+        let (text, style) = if self.confirm_purge {
+            (
+                CONFIRM_PURGE_TEXT,
+                Style::from(styles.general.input_block_invalid),
+            )
+        } else {
+            (FOOTER_TEXT, Style::default())
+        };
+
+        let footer = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false })
+            .style(style)
+            .block(Block::default().borders(Borders::NONE));
+
+        frame.render_widget(footer, area);
+    }
+
+    pub fn handle_input(&mut self, input: &Input) -> TrashPopupReturn {
+        if self.confirm_purge {
+            self.confirm_purge = false;
+
+            return match (input.key_code, self.selected_id()) {
+                (KeyCode::Char('y'), Some(entry_id)) => TrashPopupReturn::Purge(entry_id),
+                _ => TrashPopupReturn::KeepPopup,
+            };
+        }
+
+        let has_control = input.modifiers.contains(KeyModifiers::CONTROL);
+
+        match input.key_code {
+            KeyCode::Esc | KeyCode::Char('q') => TrashPopupReturn::Close,
+            KeyCode::Char('c') if has_control => TrashPopupReturn::Close,
+            KeyCode::Enter | KeyCode::Char('r') => self
+                .selected_id()
+                .map_or(TrashPopupReturn::KeepPopup, TrashPopupReturn::Restore),
+            KeyCode::Delete | KeyCode::Char('d') => {
+                self.confirm_purge = self.selected_id().is_some();
+                TrashPopupReturn::KeepPopup
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.cycle_prev_entry();
+                TrashPopupReturn::KeepPopup
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.cycle_next_entry();
+                TrashPopupReturn::KeepPopup
+            }
+            _ => TrashPopupReturn::KeepPopup,
+        }
+    }
+
+    fn selected_id(&self) -> Option<u32> {
+        self.list_state
+            .selected()
+            .and_then(|idx| self.entries.get(idx))
+            .map(|entry| entry.id)
+    }
+
+    fn cycle_next_entry(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| (idx + 1).min(self.entries.len() - 1));
+
+        self.list_state.select(Some(new_index));
+    }
+
+    fn cycle_prev_entry(&mut self) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let new_index = self
+            .list_state
+            .selected()
+            .map_or(0, |idx| idx.saturating_sub(1));
+
+        self.list_state.select(Some(new_index));
+    }
+}
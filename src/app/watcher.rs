@@ -0,0 +1,174 @@
+use std::{
+    ffi::OsStr,
+    path::{Component, Path},
+};
+
+use anyhow::Context;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+/// Suffix of the write-ahead log file of SQLite, which holds the changes before checkpoints.
+const SQLITE_WAL_SUFFIX: &str = "-wal";
+
+/// Extensions of the temporary and backup files the app writes next to the back-end files.
+const APP_FILES_EXTENSIONS: [&str; 2] = ["tmp", "bak"];
+
+/// Watches the files of the back-end to detect the changes made outside the app, e.g. by other
+/// instances or sync tools.
+pub struct BackendWatcher {
+    // The watcher stops once it's dropped.
+    _watcher: RecommendedWatcher,
+    changes_rx: mpsc::UnboundedReceiver<()>,
+}
+
+impl BackendWatcher {
+    /// Starts watching the given back-end file or directory.
+    /// Directories are watched recursively to cover the files in their sub-directories, while
+    /// files are watched through their parent directories since they could be replaced on writing.
+    pub fn new(backend_path: &Path) -> anyhow::Result<Self> {
+        let (watch_path, file_name) = if backend_path.is_dir() {
+            (backend_path.to_path_buf(), None)
+        } else {
+            let parent = backend_path
+                .parent()
+                .context("Back-end file must have a parent directory")?;
+            let file_name = backend_path
+                .file_name()
+                .context("Back-end file must have a name")?;
+            (parent.to_path_buf(), Some(file_name.to_owned()))
+        };
+
+        let recursive_mode = if file_name.is_some() {
+            RecursiveMode::NonRecursive
+        } else {
+            RecursiveMode::Recursive
+        };
+
+        let (changes_tx, changes_rx) = mpsc::unbounded_channel();
+        let event_watch_path = watch_path.clone();
+
+        let mut watcher =
+            notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+                Ok(event) => {
+                    if is_backend_change(&event, &event_watch_path, file_name.as_deref()) {
+                        // Sending fails only if the app is closing.
+                        let _ = changes_tx.send(());
+                    }
+                }
+                Err(err) => log::warn!("Watching back-end files failed. Error info: {err}"),
+            })?;
+
+        watcher
+            .watch(&watch_path, recursive_mode)
+            .with_context(|| format!("Watching the path {} failed", watch_path.display()))?;
+
+        Ok(Self {
+            _watcher: watcher,
+            changes_rx,
+        })
+    }
+
+    /// Waits for the next change on the back-end files.
+    pub async fn changed(&mut self) -> Option<()> {
+        self.changes_rx.recv().await
+    }
+}
+
+/// Checks if the event is a change on the back-end files.
+/// For back-end files, only the file itself and the write-ahead log of SQLite are considered.
+/// For back-end directories, all files are considered apart from the hidden ones (e.g. in the
+/// `.git` directory) and the temporary and backup files of the app.
+fn is_backend_change(event: &Event, watch_path: &Path, file_name: Option<&OsStr>) -> bool {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| match file_name {
+        Some(file_name) => is_backend_file(path, file_name),
+        None => is_backend_dir_file(path, watch_path),
+    })
+}
+
+fn is_backend_file(path: &Path, file_name: &OsStr) -> bool {
+    let Some(name) = path.file_name() else {
+        return false;
+    };
+
+    if name == file_name {
+        return true;
+    }
+
+    let mut wal_name = file_name.to_owned();
+    wal_name.push(SQLITE_WAL_SUFFIX);
+
+    name == wal_name
+}
+
+fn is_backend_dir_file(path: &Path, watch_path: &Path) -> bool {
+    let rel_path = path.strip_prefix(watch_path).unwrap_or(path);
+
+    let is_hidden = rel_path.components().any(|comp| match comp {
+        Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+        _ => false,
+    });
+
+    let is_app_file = path
+        .extension()
+        .is_some_and(|ext| APP_FILES_EXTENSIONS.iter().any(|app_ext| ext == *app_ext));
+
+    !is_hidden && !is_app_file
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::event::{CreateKind, ModifyKind};
+
+    use super::*;
+
+    fn modify_event(path: &str) -> Event {
+        Event::new(EventKind::Modify(ModifyKind::Any)).add_path(path.into())
+    }
+
+    #[test]
+    fn backend_file_changes() {
+        let watch_path = Path::new("/journals");
+        let file_name = OsStr::new("entries.db");
+
+        let check = |path| is_backend_change(&modify_event(path), watch_path, Some(file_name));
+
+        assert!(check("/journals/entries.db"));
+        assert!(check("/journals/entries.db-wal"));
+        assert!(!check("/journals/entries.db-shm"));
+        assert!(!check("/journals/entries.db.tmp"));
+        assert!(!check("/journals/entries.db.1.bak"));
+        assert!(!check("/journals/other.db"));
+
+        let access_event = Event::new(EventKind::Access(notify::event::AccessKind::Any))
+            .add_path("/journals/entries.db".into());
+        assert!(!is_backend_change(
+            &access_event,
+            watch_path,
+            Some(file_name)
+        ));
+    }
+
+    #[test]
+    fn backend_dir_changes() {
+        let watch_path = Path::new("/journals");
+
+        let check = |path| is_backend_change(&modify_event(path), watch_path, None);
+
+        assert!(check("/journals/2024-05-01.md"));
+        assert!(check("/journals/work/2024-05-01.md"));
+        assert!(!check("/journals/2024-05-01.md.tmp"));
+        assert!(!check("/journals/.git/index"));
+        assert!(!check("/journals/.hidden.md"));
+
+        let create_event =
+            Event::new(EventKind::Create(CreateKind::File)).add_path("/journals/new.md".into());
+        assert!(is_backend_change(&create_event, watch_path, None));
+    }
+}
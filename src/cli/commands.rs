@@ -1,11 +1,32 @@
-use std::path::PathBuf;
+#[cfg(feature = "http")]
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, ensure};
+use backend::{EntriesDTO, ForeignFormat, ImportStrategy, TRANSFER_DATA_VERSION};
+use chrono::NaiveTime;
 use clap::Subcommand;
 
-use crate::{app::ui::Styles, settings::Settings};
+use crate::{
+    app::{
+        sorter::{SortCriteria, SortOrder},
+        ui::Styles,
+    },
+    settings::{BackendType, Settings},
+};
 
-use super::*;
+use super::{
+    config::{ConfigCommand, exec_config},
+    convert::{BackendLocation, exec_convert},
+    doctor::exec_doctor,
+    export::{ExportFormat, ExportOptions, exec_export},
+    history::exec_history,
+    import::{ImportOptions, exec_import},
+    query::{ListOptions, OutputFormat, exec_list, exec_search, exec_show},
+    quick_log::{QuickLogOptions, exec_quick_log, parse_time},
+    sync::{SyncPrefer, exec_sync},
+    *,
+};
 
 #[derive(Subcommand, Debug, Clone, PartialEq, Eq)]
 pub enum CliCommand {
@@ -18,6 +39,36 @@ pub enum CliCommand {
         /// Path of the JSON file to import from.
         #[arg(short = 'p', long = "path", required = true, value_name = "FILE PATH")]
         file_path: PathBuf,
+        /// Strategy for the journals of days which exist already: skip, replace, append-lines
+        /// or keep-both.
+        #[arg(short, long, value_name = "STRATEGY", default_value_t = ImportStrategy::KeepBoth)]
+        strategy: ImportStrategy,
+    },
+    /// Imports the journals of other apps to the active notebook, merging the notes of each day
+    /// into one journal and adding their inline tags. Prints a preview without changing the
+    /// journals unless applied.
+    Import {
+        /// Format of the journals to import: jrnl, dayone, obsidian (daily notes of Obsidian
+        /// or Logseq) or plain.
+        #[arg(short, long, required = true, value_name = "FORMAT")]
+        format: ForeignFormat,
+        /// Path of the file to import from, or of the directory for daily notes.
+        #[arg(required = true, value_name = "PATH", index = 1)]
+        path: PathBuf,
+        /// Strategy for the journals of days which exist already: skip, replace, append-lines
+        /// or keep-both.
+        #[arg(short, long, value_name = "STRATEGY", default_value_t = ImportStrategy::KeepBoth)]
+        strategy: ImportStrategy,
+        /// Imports the journals instead of previewing them.
+        #[arg(long)]
+        apply: bool,
+    },
+    /// Validates the given transfer JSON file without importing it.
+    #[clap(visible_alias = "ct")]
+    CheckTransfer {
+        /// Path of the JSON file to check.
+        #[arg(required = true, value_name = "FILE PATH", index = 1)]
+        file_path: PathBuf,
     },
     /// Assign priority for all the entries with empty priority field
     #[clap(visible_alias = "ap")]
@@ -26,10 +77,167 @@ pub enum CliCommand {
         #[arg(required = true, value_name = "PRIORITY", index = 1)]
         priority: u32,
     },
+    /// Gets and sets the settings in the configuration file by their keys, keeping the comments
+    /// and formatting of the file.
+    #[command(subcommand)]
+    Config(ConfigCommand),
     /// Provides commands regarding changing themes and styles of the app.
     #[clap(visible_alias = "style")]
     #[command(subcommand)]
     Theme(Themes),
+    /// Lists the versions of the journal of the given day from the git history of the back-end,
+    /// optionally restoring one of them. Requires git versioning to be enabled.
+    History {
+        /// Day of the journal (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(required = true, value_name = "DATE", index = 1)]
+        date: String,
+        /// Restores the journal to its version in the given commit.
+        #[arg(short, long, value_name = "COMMIT")]
+        restore: Option<String>,
+    },
+    /// Syncs the journals with another back-end file in both directions, propagating the new,
+    /// changed and deleted journals since the last sync between them.
+    Sync {
+        /// Path of the back-end file or directory to sync with.
+        #[arg(long = "with", required = true, value_name = "PATH")]
+        peer_path: PathBuf,
+        /// Back-end type of the file to sync with (default: detected from the path).
+        #[arg(short, long, value_enum, value_name = "BACKEND_TYPE")]
+        backend: Option<BackendType>,
+        /// Side to keep the content of for journals changed on both sides in conflicting ways.
+        /// These journals are reported and left unchanged if not set.
+        #[arg(short, long, value_enum, value_name = "SIDE")]
+        prefer: Option<SyncPrefer>,
+        /// Prints the changes without applying them.
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Lists the journals of the active notebook, filtered and sorted by the given options.
+    /// Journals must meet all the given filter criteria.
+    #[clap(visible_alias = "ls")]
+    List {
+        /// Includes the journals with the tag or any of its descendants. Can be used multiple times.
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Includes the journals with the given priority.
+        #[arg(short, long, value_name = "PRIORITY")]
+        priority: Option<u32>,
+        /// Includes the journals on or after the given day (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(long, value_name = "DATE")]
+        from: Option<String>,
+        /// Includes the journals on or before the given day (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(long, value_name = "DATE")]
+        to: Option<String>,
+        /// Includes the journals with titles containing the text (smart-case).
+        #[arg(long, value_name = "TEXT")]
+        title: Option<String>,
+        /// Includes the journals with content containing the text (smart-case).
+        #[arg(long, value_name = "TEXT")]
+        content: Option<String>,
+        /// Sorts by the given criteria in order of their precedence. Can be used multiple times
+        /// (default: sorting of the app).
+        #[arg(short, long, value_enum, value_name = "CRITERIA")]
+        sort: Vec<SortCriteria>,
+        /// Order of the sorting (default: sorting order of the app).
+        #[arg(short, long, value_enum, value_name = "ORDER")]
+        order: Option<SortOrder>,
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Prints the journal with the given id or of the given day in the active notebook.
+    Show {
+        /// Id of the journal or its day (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(required = true, value_name = "DATE|ID", index = 1)]
+        journal: String,
+        /// Output format. Plain prints the content only.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Searches the content of the journals in the active notebook, ranking the results by
+    /// their relevance.
+    Search {
+        /// Text to search for.
+        #[arg(required = true, value_name = "TEXT", index = 1)]
+        query: String,
+        /// Output format.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: OutputFormat,
+    },
+    /// Exports the journals of the active notebook within the given days, built on the export
+    /// of the back-end.
+    Export {
+        /// Exports the journals on or after the given day (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(long, value_name = "DATE")]
+        from: Option<String>,
+        /// Exports the journals on or before the given day (YYYY-MM-DD or YYYY_MM_DD_Day).
+        #[arg(long, value_name = "DATE")]
+        to: Option<String>,
+        /// Exports the journals with the tag or any of its descendants. Can be used multiple
+        /// times, where the journals must have all the tags.
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Format of the exported document.
+        #[arg(short, long, value_enum, default_value_t)]
+        format: ExportFormat,
+        /// Path of the output file, or '-' to write to the standard output.
+        #[arg(short, long, value_name = "PATH", default_value = "-")]
+        out: PathBuf,
+    },
+    /// Appends a timestamped line to the journal of today or the given day without starting the
+    /// TUI, creating the journal if it doesn't exist.
+    Log {
+        /// Text of the line.
+        #[arg(
+            value_name = "TEXT",
+            required_unless_present = "stdin",
+            conflicts_with = "stdin"
+        )]
+        text: Vec<String>,
+        /// Time of the line in HH:MM format (default: current time).
+        #[arg(long, value_name = "HH:MM", value_parser = parse_time)]
+        time: Option<NaiveTime>,
+        /// Day of the journal (YYYY-MM-DD or YYYY_MM_DD_Day) (default: today).
+        #[arg(short, long, value_name = "DATE")]
+        date: Option<String>,
+        /// Adds the tag to the line and the journal. Can be used multiple times.
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+        /// Reads the text from the standard input, adding a line for each line of the input.
+        #[arg(long)]
+        stdin: bool,
+    },
+    /// Converts the journals of a back-end file to another back-end type, keeping their ids
+    /// where possible and verifying the converted journals afterwards.
+    Convert {
+        /// Source back-end as '<TYPE>:<PATH>' (e.g. json:entries.json).
+        #[arg(long, required = true, value_name = "TYPE:PATH")]
+        from: BackendLocation,
+        /// Empty target back-end as '<TYPE>:<PATH>' (e.g. sqlite:entries.db).
+        #[arg(long, required = true, value_name = "TYPE:PATH")]
+        to: BackendLocation,
+        /// Switches the back-end in the configuration file to the target once converted.
+        #[arg(long)]
+        switch: bool,
+    },
+    /// Scans the journals of the configured back-end for problems like corrupted files, invalid
+    /// titles, duplicate days and orphaned tags, reporting them with the journals ids.
+    Doctor {
+        /// Fixes the found problems: Renames titles from the journals dates, merges the journals
+        /// of the same day, and removes duplicate and orphaned tags.
+        #[arg(long)]
+        fix: bool,
+    },
+    /// Serves the journals of the configured back-end over HTTP with JSON, to be used with the
+    /// HTTP back-end of other instances. Authorization token is read from the configurations or
+    /// generated and printed if none is configured.
+    #[cfg(feature = "http")]
+    Serve {
+        /// Address to listen on (default: bind address from the configurations or
+        /// 127.0.0.1:7373).
+        #[arg(long, value_name = "ADDRESS")]
+        bind: Option<SocketAddr>,
+    },
 }
 
 #[derive(Debug, Clone, Subcommand, Eq, PartialEq)]
@@ -48,29 +256,121 @@ pub enum Themes {
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum PendingCliCommand {
-    ImportJournals(PathBuf),
+    ImportJournals(PathBuf, ImportStrategy),
     AssignPriority(u32),
 }
 
 impl CliCommand {
-    pub fn exec(
+    pub async fn exec(
         self,
         settings: &mut Settings,
         custom_config_dir: Option<&PathBuf>,
     ) -> anyhow::Result<CliResult> {
         match self {
             CliCommand::PrintConfig => exec_print_config(settings),
-            CliCommand::ImportJournals { file_path: path } => Ok(CliResult::PendingCommand(
-                PendingCliCommand::ImportJournals(path),
+            CliCommand::ImportJournals {
+                file_path: path,
+                strategy,
+            } => Ok(CliResult::PendingCommand(
+                PendingCliCommand::ImportJournals(path, strategy),
             )),
+            CliCommand::Import {
+                format,
+                path,
+                strategy,
+                apply,
+            } => {
+                let options = ImportOptions {
+                    format,
+                    path,
+                    strategy,
+                    apply,
+                };
+                exec_import(settings, options).await
+            }
+            CliCommand::CheckTransfer { file_path } => exec_check_transfer(&file_path),
             CliCommand::AssignPriority { priority } => Ok(CliResult::PendingCommand(
                 PendingCliCommand::AssignPriority(priority),
             )),
+            CliCommand::Config(cmd) => exec_config(cmd, custom_config_dir).await,
             CliCommand::Theme(cmd) => match cmd {
                 Themes::PrintPath => exec_print_themes_path(custom_config_dir),
                 Themes::DumpDefaults => exec_print_themes_defaults(),
                 Themes::WriteDefaults => exec_write_themes_defaults(custom_config_dir),
             },
+            CliCommand::History { date, restore } => {
+                exec_history(settings, &date, restore.as_deref()).await
+            }
+            CliCommand::Sync {
+                peer_path,
+                backend,
+                prefer,
+                dry_run,
+            } => exec_sync(settings, &peer_path, backend, prefer, dry_run).await,
+            CliCommand::List {
+                tags,
+                priority,
+                from,
+                to,
+                title,
+                content,
+                sort,
+                order,
+                format,
+            } => {
+                let options = ListOptions {
+                    tags,
+                    priority,
+                    from,
+                    to,
+                    title,
+                    content,
+                    sort,
+                    order,
+                    format,
+                };
+                exec_list(settings, options).await
+            }
+            CliCommand::Show { journal, format } => exec_show(settings, &journal, format).await,
+            CliCommand::Search { query, format } => exec_search(settings, &query, format).await,
+            CliCommand::Export {
+                from,
+                to,
+                tags,
+                format,
+                out,
+            } => {
+                let options = ExportOptions {
+                    from,
+                    to,
+                    tags,
+                    format,
+                    out,
+                };
+                exec_export(settings, options).await
+            }
+            CliCommand::Log {
+                text,
+                time,
+                date,
+                tags,
+                stdin,
+            } => {
+                let options = QuickLogOptions {
+                    text,
+                    time,
+                    date,
+                    tags,
+                    stdin,
+                };
+                exec_quick_log(settings, options).await
+            }
+            CliCommand::Convert { from, to, switch } => {
+                exec_convert(from, to, switch, custom_config_dir).await
+            }
+            CliCommand::Doctor { fix } => exec_doctor(settings, fix).await,
+            #[cfg(feature = "http")]
+            CliCommand::Serve { bind } => serve::exec_serve(settings, bind).await,
         }
     }
 }
@@ -83,6 +383,28 @@ fn exec_print_config(settings: &mut Settings) -> anyhow::Result<CliResult> {
     Ok(CliResult::Return)
 }
 
+fn exec_check_transfer(file_path: &Path) -> anyhow::Result<CliResult> {
+    let file = fs::File::open(file_path).with_context(|| {
+        format!(
+            "Opening transfer file failed. Path: {}",
+            file_path.display()
+        )
+    })?;
+
+    let (entries_dto, source_version) = EntriesDTO::parse_versioned(file)?;
+
+    println!(
+        "Transfer file is valid. Version: {source_version}, Journals: {}",
+        entries_dto.entries.len()
+    );
+
+    if source_version < TRANSFER_DATA_VERSION {
+        println!("Journals will be upgraded to version {TRANSFER_DATA_VERSION} on import");
+    }
+
+    Ok(CliResult::Return)
+}
+
 fn exec_print_themes_path(custom_config_dir: Option<&PathBuf>) -> anyhow::Result<CliResult> {
     let themes_path = Styles::file_path(custom_config_dir)?;
 
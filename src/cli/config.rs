@@ -0,0 +1,80 @@
+use std::path::PathBuf;
+
+use anyhow::bail;
+use clap::Subcommand;
+
+use crate::settings::ConfigFile;
+
+use super::CliResult;
+
+#[derive(Debug, Clone, Subcommand, Eq, PartialEq)]
+pub enum ConfigCommand {
+    /// Prints the value of the given key, using the default value if it isn't set in the file.
+    Get {
+        /// Key of the setting with dots for nested keys (e.g. external_editor.command).
+        #[arg(required = true, value_name = "KEY", index = 1)]
+        key: String,
+    },
+    /// Sets the value of the given key in the configuration file.
+    Set {
+        /// Key of the setting with dots for nested keys (e.g. external_editor.command).
+        #[arg(required = true, value_name = "KEY", index = 1)]
+        key: String,
+        /// Value as TOML value (e.g. 20, true) or as plain text for strings.
+        #[arg(required = true, value_name = "VALUE", index = 2)]
+        value: String,
+    },
+    /// Removes the given key from the configuration file to use its default value.
+    Unset {
+        /// Key of the setting with dots for nested keys (e.g. external_editor.command).
+        #[arg(required = true, value_name = "KEY", index = 1)]
+        key: String,
+    },
+    /// Checks the configuration file for invalid values and unknown keys.
+    Validate,
+}
+
+pub async fn exec_config(
+    command: ConfigCommand,
+    custom_config_dir: Option<&PathBuf>,
+) -> anyhow::Result<CliResult> {
+    let mut config = ConfigFile::load(custom_config_dir).await?;
+
+    match command {
+        ConfigCommand::Get { key } => match config.get(&key)? {
+            Some(value) => println!("{value}"),
+            None => eprintln!("Key '{key}' has no value"),
+        },
+        ConfigCommand::Set { key, value } => {
+            config.set(&key, &value)?;
+            config.save().await?;
+        }
+        ConfigCommand::Unset { key } => {
+            if config.unset(&key)? {
+                config.save().await?;
+            } else {
+                eprintln!(
+                    "Key '{key}' isn't set in the configuration file {}",
+                    config.path().display()
+                );
+            }
+        }
+        ConfigCommand::Validate => {
+            let problems = config.validate()?;
+            if !problems.is_empty() {
+                for problem in problems.iter() {
+                    println!("{problem}");
+                }
+                bail!(
+                    "{} problem(s) found in the configuration file {}",
+                    problems.len(),
+                    config.path().display()
+                );
+            }
+
+            println!("Configuration file is valid: {}", config.path().display());
+        }
+    }
+
+    Ok(CliResult::Return)
+}
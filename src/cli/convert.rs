@@ -0,0 +1,111 @@
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{Context, ensure};
+use backend::convert_entries;
+use clap::ValueEnum;
+use path_absolutize::Absolutize;
+
+use crate::settings::{BackendType, Settings};
+
+use super::{CliResult, provider::AnyDataProvide};
+
+/// Back-end type with the path of its file or directory, given as `<TYPE>:<PATH>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BackendLocation {
+    pub backend_type: BackendType,
+    pub path: PathBuf,
+}
+
+impl FromStr for BackendLocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (type_name, path) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Expected '<TYPE>:<PATH>' but got '{s}'"))?;
+
+        let backend_type = BackendType::from_str(type_name, true)?;
+        if backend_type == BackendType::Http {
+            return Err(String::from("HTTP back-end can't be converted"));
+        }
+
+        if path.is_empty() {
+            return Err(String::from("Path of the back-end can't be empty"));
+        }
+
+        Ok(Self {
+            backend_type,
+            path: PathBuf::from(path),
+        })
+    }
+}
+
+impl fmt::Display for BackendLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let type_name = self
+            .backend_type
+            .to_possible_value()
+            .map(|value| value.get_name().to_owned())
+            .unwrap_or_default();
+
+        write!(f, "{type_name}:{}", self.path.display())
+    }
+}
+
+pub async fn exec_convert(
+    from: BackendLocation,
+    to: BackendLocation,
+    switch: bool,
+    custom_config_dir: Option<&PathBuf>,
+) -> anyhow::Result<CliResult> {
+    let source_path = from.path.absolutize()?.to_path_buf();
+    let target_path = to.path.absolutize()?.to_path_buf();
+
+    ensure!(
+        source_path != target_path,
+        "Source and target back-ends must have different paths"
+    );
+    ensure!(
+        source_path.exists(),
+        "Source back-end doesn't exist. Path: {}",
+        source_path.display()
+    );
+
+    let source = AnyDataProvide::open(from.backend_type, source_path).await?;
+    let target = AnyDataProvide::open(to.backend_type, target_path.clone()).await?;
+
+    let summary = convert_entries(&source, &target)
+        .await
+        .with_context(|| format!("Converting journals from {from} to {to} failed"))?;
+
+    println!("Converted journals from {from} to {to}");
+    println!("  {summary}");
+    println!("  Counts and content hashes of the journals have been verified");
+
+    if switch {
+        switch_backend(custom_config_dir, to.backend_type, &target_path).await?;
+    }
+
+    Ok(CliResult::Return)
+}
+
+async fn switch_backend(
+    custom_config_dir: Option<&PathBuf>,
+    backend_type: BackendType,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let config_file = Settings::write_backend_to_config(custom_config_dir, backend_type, path)
+        .await
+        .context("Switching the back-end in the configuration file failed")?;
+
+    println!(
+        "Back-end has been switched to {backend_type:?} in the configuration file {}",
+        config_file.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anyhow::Context;
+use backend::{DataProvider, GitDataProvide, diagnose_entries, fix_entries};
+
+use crate::settings::Settings;
+
+use super::{CliResult, provider::AnyDataProvide};
+
+pub async fn exec_doctor(settings: &Settings, fix: bool) -> anyhow::Result<CliResult> {
+    let provider = AnyDataProvide::from_settings(settings)
+        .await
+        .context("Opening the back-end failed")?;
+
+    if settings.git_versioning {
+        let provider = GitDataProvide::new(provider, settings.get_backend_path()?)?;
+        check_entries(&provider, fix).await
+    } else {
+        check_entries(&provider, fix).await
+    }
+}
+
+/// Reports the problems of the entries of the given back-end, fixing them if requested.
+async fn check_entries<D: DataProvider>(provider: &D, fix: bool) -> anyhow::Result<CliResult> {
+    let issues = diagnose_entries(provider).await;
+    if issues.is_empty() {
+        println!("No problems found");
+        return Ok(CliResult::Return);
+    }
+
+    for issue in issues.iter() {
+        println!("{issue}");
+    }
+
+    let fixable_count = issues.iter().filter(|issue| issue.is_fixable()).count();
+    println!();
+    println!(
+        "{} problem(s) found, {fixable_count} of them can be fixed",
+        issues.len()
+    );
+
+    if fixable_count == 0 {
+        return Ok(CliResult::Return);
+    }
+
+    if fix {
+        let summary = fix_entries(provider).await?;
+        println!("{summary}");
+    } else {
+        println!("Run the command with --fix to fix them");
+    }
+
+    Ok(CliResult::Return)
+}
@@ -0,0 +1,386 @@
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use backend::{DataProvider, EntriesDTO, Entry, EntryDraft};
+use chrono::NaiveDate;
+use clap::ValueEnum;
+
+use crate::{
+    app::filter::{Filter, FilterCriterion, criterion::TagFilterOption},
+    settings::Settings,
+};
+
+use super::{CliResult, active_notebook, journal_day::parse_day, provider::AnyDataProvide};
+
+/// Path given to the export command to write to the standard output.
+const STDOUT_PATH: &str = "-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ExportFormat {
+    /// Transfer JSON file which can be imported again.
+    #[default]
+    Json,
+    /// Single markdown document with a heading per day.
+    Markdown,
+    /// Plain text with a title per day.
+    Txt,
+    /// Standalone HTML page with a section per day.
+    Html,
+}
+
+/// Options of the export command to select the journals and the output.
+pub struct ExportOptions {
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub tags: Vec<String>,
+    pub format: ExportFormat,
+    pub out: PathBuf,
+}
+
+pub async fn exec_export(settings: &Settings, options: ExportOptions) -> anyhow::Result<CliResult> {
+    let from = options.from.as_deref().map(parse_day).transpose()?;
+    let to = options.to.as_deref().map(parse_day).transpose()?;
+
+    let filter = create_filter(from, to, &options.tags);
+
+    let provider = AnyDataProvide::from_settings(settings).await?;
+    let notebook = active_notebook(settings);
+
+    let entries = provider.load_all_entries().await?;
+    let ids = filter_entry_ids(&entries, &notebook, &filter);
+
+    let mut entries_dto = provider.get_export_object(&ids).await?;
+    entries_dto.entries.sort_by_key(|entry| entry.date);
+
+    let output = match options.format {
+        ExportFormat::Json => serde_json::to_string_pretty(&entries_dto)
+            .context("Serializing journals to JSON failed")?,
+        ExportFormat::Markdown => render_markdown(&entries_dto, from, to),
+        ExportFormat::Txt => render_txt(&entries_dto, from, to),
+        ExportFormat::Html => render_html(&entries_dto, from, to),
+    };
+
+    if options.out == Path::new(STDOUT_PATH) {
+        let mut stdout = io::stdout().lock();
+        writeln!(stdout, "{}", output.trim_end()).context("Writing to stdout failed")?;
+    } else {
+        if let Some(parent) = options.out.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&options.out, output).with_context(|| {
+            format!(
+                "Writing export file failed. Path: {}",
+                options.out.display()
+            )
+        })?;
+
+        println!(
+            "{} journal(s) exported to {}",
+            entries_dto.entries.len(),
+            options.out.display()
+        );
+    }
+
+    Ok(CliResult::Return)
+}
+
+/// Creates the filter of the journals within the given days, with the both ends included, which
+/// have all the given tags.
+fn create_filter(from: Option<NaiveDate>, to: Option<NaiveDate>, tags: &[String]) -> Filter {
+    let mut filter = Filter::default();
+    filter.criteria.extend(from.map(FilterCriterion::FromDate));
+    filter.criteria.extend(to.map(FilterCriterion::ToDate));
+    filter.criteria.extend(
+        tags.iter()
+            .map(|tag| FilterCriterion::Tag(TagFilterOption::Tag(tag.to_owned()))),
+    );
+
+    filter
+}
+
+/// Gets the ids of the entries of the given notebook which meet the filter.
+fn filter_entry_ids(entries: &[Entry], notebook: &str, filter: &Filter) -> Vec<u32> {
+    entries
+        .iter()
+        .filter(|entry| entry.notebook == notebook && filter.check_entry(entry))
+        .map(|entry| entry.id)
+        .collect()
+}
+
+/// Gets the title of the exported document describing the range of the days.
+fn document_title(from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    match (from, to) {
+        (Some(from), Some(to)) => format!("Journals {from} to {to}"),
+        (Some(from), None) => format!("Journals since {from}"),
+        (None, Some(to)) => format!("Journals until {to}"),
+        (None, None) => String::from("Journals"),
+    }
+}
+
+fn day_heading(entry: &EntryDraft) -> String {
+    entry.date.format("%Y-%m-%d (%A)").to_string()
+}
+
+/// Gets the line with the tags and the priority of the entry if any of them is set.
+fn attributes_line(entry: &EntryDraft) -> Option<String> {
+    let mut parts = Vec::new();
+    if !entry.tags.is_empty() {
+        parts.push(format!("Tags: {}", entry.tags.join(", ")));
+    }
+    if let Some(priority) = entry.priority {
+        parts.push(format!("Priority: {priority}"));
+    }
+
+    (!parts.is_empty()).then(|| parts.join(" | "))
+}
+
+fn render_markdown(dto: &EntriesDTO, from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let mut doc = format!("# {}\n", document_title(from, to));
+
+    for entry in &dto.entries {
+        let _ = writeln!(doc, "\n## {}\n", day_heading(entry));
+        if let Some(attributes) = attributes_line(entry) {
+            let _ = writeln!(doc, "*{attributes}*\n");
+        }
+        if !entry.content.trim().is_empty() {
+            let _ = writeln!(doc, "{}", demote_headings(entry.content.trim_end()));
+        }
+    }
+
+    doc
+}
+
+/// Demotes the headings in the content by two levels to keep them below the day headings of the
+/// document, leaving the lines in code blocks untouched.
+fn demote_headings(content: &str) -> String {
+    let mut in_code_block = false;
+
+    content
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                return line.to_owned();
+            }
+
+            let indent = line.len() - trimmed.len();
+            let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+            let is_heading = !in_code_block
+                && indent <= 3
+                && (1..=6).contains(&level)
+                && trimmed[level..]
+                    .chars()
+                    .next()
+                    .is_none_or(|ch| ch == ' ' || ch == '\t');
+            if !is_heading {
+                return line.to_owned();
+            }
+
+            format!(
+                "{}{}{}",
+                &line[..indent],
+                "#".repeat((level + 2).min(6)),
+                &trimmed[level..]
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render_txt(dto: &EntriesDTO, from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let mut doc = format!("{}\n", document_title(from, to));
+
+    for entry in &dto.entries {
+        let heading = day_heading(entry);
+        let _ = writeln!(doc, "\n{heading}\n{}", "=".repeat(heading.chars().count()));
+        if let Some(attributes) = attributes_line(entry) {
+            let _ = writeln!(doc, "{attributes}");
+        }
+        if !entry.content.trim().is_empty() {
+            let _ = writeln!(doc, "\n{}", entry.content.trim_end());
+        }
+    }
+
+    doc
+}
+
+fn render_html(dto: &EntriesDTO, from: Option<NaiveDate>, to: Option<NaiveDate>) -> String {
+    let title = escape_html(&document_title(from, to));
+    let mut doc = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+
+    for entry in &dto.entries {
+        let _ = writeln!(
+            doc,
+            "<section>\n<h2>{}</h2>",
+            escape_html(&day_heading(entry))
+        );
+        if let Some(attributes) = attributes_line(entry) {
+            let _ = writeln!(doc, "<p><em>{}</em></p>", escape_html(&attributes));
+        }
+        if !entry.content.trim().is_empty() {
+            let _ = writeln!(
+                doc,
+                "<pre style=\"white-space: pre-wrap\">{}</pre>",
+                escape_html(entry.content.trim_end())
+            );
+        }
+        doc.push_str("</section>\n");
+    }
+
+    doc.push_str("</body>\n</html>\n");
+
+    doc
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use backend::DEFAULT_NOTEBOOK;
+    use chrono::{TimeZone, Utc};
+
+    use super::*;
+
+    fn draft(day: u32, content: &str, tags: Vec<String>, priority: Option<u32>) -> EntryDraft {
+        let date = Utc.with_ymd_and_hms(2024, 5, day, 0, 0, 0).unwrap();
+        let mut draft = EntryDraft::new(date, format!("Title {day}"), tags, priority);
+        draft.content = String::from(content);
+        draft
+    }
+
+    fn dto() -> EntriesDTO {
+        EntriesDTO::new(vec![
+            draft(1, "08:00 First day", vec![String::from("work")], Some(1)),
+            draft(2, "Second day", Vec::new(), None),
+        ])
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, day).unwrap()
+    }
+
+    #[test]
+    fn markdown() {
+        let doc = render_markdown(&dto(), Some(day(1)), Some(day(2)));
+
+        assert_eq!(
+            doc,
+            "# Journals 2024-05-01 to 2024-05-02\n\
+             \n## 2024-05-01 (Wednesday)\n\n\
+             *Tags: work | Priority: 1*\n\n\
+             08:00 First day\n\
+             \n## 2024-05-02 (Thursday)\n\n\
+             Second day\n"
+        );
+    }
+
+    #[test]
+    fn markdown_demotes_content_headings() {
+        let content =
+            "# Plans\n## Morning\n#work tag\n```\n# Code comment\n```\n    # Indented code";
+        let dto = EntriesDTO::new(vec![draft(1, content, Vec::new(), None)]);
+
+        let doc = render_markdown(&dto, None, None);
+
+        assert!(doc.ends_with(
+            "### Plans\n#### Morning\n#work tag\n```\n# Code comment\n```\n    # Indented code\n"
+        ));
+        assert_eq!(demote_headings("##### Deep\n######"), "###### Deep\n######");
+    }
+
+    #[test]
+    fn txt() {
+        let doc = render_txt(&dto(), Some(day(1)), None);
+
+        assert_eq!(
+            doc,
+            "Journals since 2024-05-01\n\
+             \n2024-05-01 (Wednesday)\n======================\n\
+             Tags: work | Priority: 1\n\
+             \n08:00 First day\n\
+             \n2024-05-02 (Thursday)\n=====================\n\
+             \nSecond day\n"
+        );
+    }
+
+    #[test]
+    fn html() {
+        let dto = EntriesDTO::new(vec![
+            draft(2, "Second <day>", Vec::new(), None),
+            draft(1, "", vec![String::from("a&b")], None),
+        ]);
+
+        let doc = render_html(&dto, None, Some(day(2)));
+
+        assert!(doc.contains("<title>Journals until 2024-05-02</title>"));
+        assert_eq!(doc.matches("<h2>").count(), 2);
+        assert!(doc.contains("<pre style=\"white-space: pre-wrap\">Second &lt;day&gt;</pre>"));
+        assert!(doc.contains("<p><em>Tags: a&amp;b</em></p>"));
+        // Entries without attributes have no attributes line.
+        assert_eq!(doc.matches("<em>").count(), 1);
+    }
+
+    #[test]
+    fn escape() {
+        assert_eq!(
+            escape_html(r#"<a href="x">Tom & Jerry's</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn filter_days() {
+        let mut entries: Vec<Entry> = (1..=4)
+            .map(|day| {
+                let date = Utc.with_ymd_and_hms(2024, 5, day, 10, 0, 0).unwrap();
+                Entry::new(
+                    day,
+                    date,
+                    format!("Title {day}"),
+                    String::new(),
+                    Vec::new(),
+                    None,
+                )
+            })
+            .collect();
+        entries[2].tags.push(String::from("work"));
+        entries[3].notebook = String::from("other");
+
+        let filter = create_filter(Some(day(2)), Some(day(3)), &[]);
+        assert_eq!(
+            filter_entry_ids(&entries, DEFAULT_NOTEBOOK, &filter),
+            vec![2, 3]
+        );
+
+        let filter = create_filter(None, Some(day(3)), &[String::from("work")]);
+        assert_eq!(
+            filter_entry_ids(&entries, DEFAULT_NOTEBOOK, &filter),
+            vec![3]
+        );
+
+        let filter = create_filter(Some(day(4)), None, &[]);
+        assert!(filter_entry_ids(&entries, DEFAULT_NOTEBOOK, &filter).is_empty());
+    }
+}
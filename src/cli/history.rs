@@ -0,0 +1,187 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, ensure};
+use backend::{DataProvider, Entry, EntryDraft, GitDataProvide};
+use chrono::{Local, NaiveDate};
+use scopeguard::defer;
+
+use crate::settings::{BackendType, Settings};
+
+use super::{
+    CliResult, active_notebook,
+    journal_day::{is_entry_of_day, parse_day},
+    provider::AnyDataProvide,
+};
+
+const SHORT_COMMIT_ID_LEN: usize = 8;
+
+pub async fn exec_history(
+    settings: &Settings,
+    date: &str,
+    restore_commit: Option<&str>,
+) -> anyhow::Result<CliResult> {
+    ensure!(
+        settings.git_versioning,
+        "Git versioning isn't enabled. Set 'git_versioning = true' in the configurations to use the history"
+    );
+
+    let day = parse_day(date)?;
+    let notebook = active_notebook(settings);
+    let backend_type = settings.backend_type.unwrap_or_default();
+    let backend_path = settings.get_backend_path()?;
+
+    let provider = AnyDataProvide::from_settings(settings).await?;
+    let provider = GitDataProvide::new(provider, backend_path.clone())?;
+
+    let journal = JournalRef {
+        backend_type,
+        backend_path: &backend_path,
+        notebook: &notebook,
+        day,
+    };
+
+    match restore_commit {
+        Some(commit) => restore_version(&provider, &journal, commit).await,
+        None => print_versions(&provider, &journal).await,
+    }
+}
+
+/// Identifies the journal of a day in the back-end.
+struct JournalRef<'a> {
+    backend_type: BackendType,
+    backend_path: &'a Path,
+    notebook: &'a str,
+    day: NaiveDate,
+}
+
+impl JournalRef<'_> {
+    fn matches(&self, entry: &Entry) -> bool {
+        entry.notebook == self.notebook && is_entry_of_day(entry, self.day)
+    }
+}
+
+async fn print_versions(
+    provider: &GitDataProvide<AnyDataProvide>,
+    journal: &JournalRef<'_>,
+) -> anyhow::Result<CliResult> {
+    let mut versions = provider.versions()?;
+    // Go through the versions starting with the oldest to detect the changes on the journal.
+    versions.reverse();
+
+    let mut last_state: Option<EntryDraft> = None;
+    let mut changes = Vec::new();
+
+    for version in versions {
+        let state = load_version_entry(provider, journal, &version.commit_id)
+            .await?
+            .map(EntryDraft::from_entry);
+
+        if state != last_state {
+            changes.push((version, state.is_some()));
+        }
+
+        last_state = state;
+    }
+
+    if changes.is_empty() {
+        println!("No versions found for the journal of {}", journal.day);
+    }
+
+    for (version, exists) in changes.iter().rev() {
+        let short_id: String = version
+            .commit_id
+            .chars()
+            .take(SHORT_COMMIT_ID_LEN)
+            .collect();
+        let removed_note = if *exists { "" } else { " (removed)" };
+        println!(
+            "{short_id}  {}  {}{removed_note}",
+            version.time.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+            version.message,
+        );
+    }
+
+    Ok(CliResult::Return)
+}
+
+async fn restore_version(
+    provider: &GitDataProvide<AnyDataProvide>,
+    journal: &JournalRef<'_>,
+    commit: &str,
+) -> anyhow::Result<CliResult> {
+    let version_entry = load_version_entry(provider, journal, commit)
+        .await?
+        .with_context(|| {
+            format!(
+                "Journal of {} doesn't exist in commit {commit}",
+                journal.day
+            )
+        })?;
+
+    let current_entry = provider
+        .load_all_entries()
+        .await?
+        .into_iter()
+        .find(|entry| journal.matches(entry));
+
+    let title = version_entry.title.clone();
+
+    match current_entry {
+        Some(current) => {
+            let entry = Entry {
+                id: current.id,
+                uuid: current.uuid,
+                created_at: current.created_at,
+                ..version_entry
+            };
+            provider.update_entry(entry).await?;
+        }
+        None => {
+            provider
+                .add_entry(EntryDraft::from_entry(version_entry))
+                .await?;
+        }
+    }
+
+    println!("Journal {title} has been restored to its version in commit {commit}");
+
+    Ok(CliResult::Return)
+}
+
+/// Loads the journal of the given day as it was in the given commit.
+async fn load_version_entry(
+    provider: &GitDataProvide<AnyDataProvide>,
+    journal: &JournalRef<'_>,
+    commit: &str,
+) -> anyhow::Result<Option<Entry>> {
+    let temp_dir = env::temp_dir().join(format!("tjournal-history-{}", std::process::id()));
+    defer! {
+        if temp_dir.exists() {
+            if let Err(err) = fs::remove_dir_all(&temp_dir) {
+                log::error!("Removing history temporary directory failed. Error info: {err}");
+            }
+        }
+    }
+
+    let file_name = journal
+        .backend_path
+        .file_name()
+        .context("Back-end path must have a name")?;
+    let temp_path: PathBuf = temp_dir.join(file_name);
+
+    if !provider.checkout_version(commit, &temp_path)? {
+        return Ok(None);
+    }
+
+    let version_provider = AnyDataProvide::open(journal.backend_type, temp_path).await?;
+    let entry = version_provider
+        .load_all_entries()
+        .await?
+        .into_iter()
+        .find(|entry| journal.matches(entry));
+
+    Ok(entry)
+}
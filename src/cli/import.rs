@@ -0,0 +1,103 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use backend::{
+    DataProvider, EntriesDTO, EntryDraft, ForeignFormat, GitDataProvide, ImportStrategy,
+    read_foreign_entries,
+};
+
+use crate::settings::Settings;
+
+use super::{
+    CliResult, active_notebook,
+    provider::AnyDataProvide,
+    query::{OutputFormat, print_rows},
+};
+
+/// Options of the import command for the journals of other apps.
+pub struct ImportOptions {
+    pub format: ForeignFormat,
+    pub path: PathBuf,
+    pub strategy: ImportStrategy,
+    pub apply: bool,
+}
+
+pub async fn exec_import(settings: &Settings, options: ImportOptions) -> anyhow::Result<CliResult> {
+    let notebook = active_notebook(settings);
+    let drafts: Vec<EntryDraft> = read_foreign_entries(options.format, &options.path)?
+        .into_iter()
+        .map(|draft| draft.with_notebook(notebook.clone()))
+        .collect();
+
+    if drafts.is_empty() {
+        println!("No journals found to import");
+        return Ok(CliResult::Return);
+    }
+
+    let provider = AnyDataProvide::from_settings(settings)
+        .await
+        .context("Opening the back-end failed")?;
+
+    if settings.git_versioning {
+        let provider = GitDataProvide::new(provider, settings.get_backend_path()?)?;
+        import_drafts(&provider, drafts, options).await
+    } else {
+        import_drafts(&provider, drafts, options).await
+    }
+}
+
+/// Previews the journals to import with the action for each of them, importing them if
+/// requested.
+async fn import_drafts<D: DataProvider>(
+    provider: &D,
+    drafts: Vec<EntryDraft>,
+    options: ImportOptions,
+) -> anyhow::Result<CliResult> {
+    let existing_entries = provider.load_all_entries().await?;
+
+    let rows = drafts
+        .iter()
+        .map(|draft| {
+            let day_exists = existing_entries.iter().any(|entry| {
+                entry.notebook == draft.notebook
+                    && entry.date.date_naive() == draft.date.date_naive()
+            });
+            let action = if day_exists {
+                options.strategy.as_str()
+            } else {
+                "create"
+            };
+
+            vec![
+                draft.title.clone(),
+                draft.content.lines().count().to_string(),
+                draft.tags.join(", "),
+                action.to_owned(),
+            ]
+        })
+        .collect();
+
+    print_rows(
+        OutputFormat::Table,
+        &["TITLE", "LINES", "TAGS", "ACTION"],
+        rows,
+    );
+    println!();
+
+    if !options.apply {
+        println!(
+            "{} journal(s) read from {}. Run the command with --apply to import them",
+            drafts.len(),
+            options.path.display()
+        );
+        return Ok(CliResult::Return);
+    }
+
+    let summary = provider
+        .import_entries_with_strategy(EntriesDTO::new(drafts), options.strategy)
+        .await?;
+
+    println!("Import finished: {summary}");
+
+    Ok(CliResult::Return)
+}
@@ -0,0 +1,22 @@
+use anyhow::Context;
+use backend::Entry;
+use chrono::NaiveDate;
+
+/// Parses a day given either in `YYYY-MM-DD` format or in the journals title format
+/// `YYYY_MM_DD_Day`.
+pub fn parse_day(text: &str) -> anyhow::Result<NaiveDate> {
+    NaiveDate::parse_from_str(text, "%Y-%m-%d")
+        .or_else(|_| {
+            let date_part = text.splitn(4, '_').take(3).collect::<Vec<_>>().join("-");
+            NaiveDate::parse_from_str(&date_part, "%Y-%m-%d")
+        })
+        .with_context(|| format!("Invalid date '{text}'. Use YYYY-MM-DD or YYYY_MM_DD_Day"))
+}
+
+/// Checks if the entry is the journal of the given day.
+pub fn is_entry_of_day(entry: &Entry, day: NaiveDate) -> bool {
+    entry
+        .title
+        .starts_with(&day.format("%Y_%m_%d").to_string())
+        || entry.date.date_naive() == day
+}
@@ -1,15 +1,32 @@
+use anyhow::ensure;
 use clap::Parser;
 use std::{
     fs,
     path::{Path, PathBuf},
 };
 
+use backend::default_notebook;
+
 use crate::{
+    app::state::AppState,
     logging::{get_default_path as default_log_path, setup_logging},
     settings::{BackendType, Settings, settings_default_dir_path},
 };
 
 pub mod commands;
+mod config;
+mod convert;
+mod doctor;
+mod export;
+mod history;
+mod import;
+mod journal_day;
+pub mod provider;
+mod query;
+mod quick_log;
+#[cfg(feature = "http")]
+mod serve;
+mod sync;
 pub use commands::CliCommand;
 pub use commands::PendingCliCommand;
 use path_absolutize::Absolutize;
@@ -27,10 +44,24 @@ pub struct Cli {
     #[cfg(feature = "sqlite")]
     sqlite_file_path: Option<PathBuf>,
 
+    /// Sets the entries markdown directory path and starts using it.
+    #[arg(short, long, value_name = "DIR PATH")]
+    #[cfg(feature = "markdown_dir")]
+    markdown_dir_path: Option<PathBuf>,
+
+    /// Sets the URL of the journal server and starts using the HTTP back-end.
+    #[arg(short = 'u', long, value_name = "URL")]
+    #[cfg(feature = "http")]
+    http_url: Option<String>,
+
     /// Sets the backend type and starts using it.
     #[arg(short, long, value_enum)]
     backend_type: Option<BackendType>,
 
+    /// Sets the notebook to work with, which is the last active notebook if not set.
+    #[arg(short = 'n', long, value_name = "NAME")]
+    notebook: Option<String>,
+
     #[arg(short = 'c', long = "config", value_name = "DIR PATH", help = config_help())]
     pub config_path: Option<PathBuf>,
 
@@ -53,6 +84,12 @@ pub enum CliResult {
 }
 
 impl Cli {
+    /// Checks if the command manages the configuration file, which must work without loading
+    /// the settings to be able to fix invalid configuration files.
+    pub fn is_config_command(&self) -> bool {
+        matches!(self.command, Some(CliCommand::Config(_)))
+    }
+
     pub async fn handle_cli(mut self, settings: &mut Settings) -> anyhow::Result<CliResult> {
         #[cfg(feature = "json")]
         if let Some(json_path) = self.json_file_path.take() {
@@ -66,14 +103,31 @@ impl Cli {
             set_backend_type(BackendType::Sqlite, settings);
         }
 
+        #[cfg(feature = "markdown_dir")]
+        if let Some(dir_path) = self.markdown_dir_path.take() {
+            set_markdown_dir_path(dir_path, settings).await?;
+            set_backend_type(BackendType::MarkdownDir, settings);
+        }
+
+        #[cfg(feature = "http")]
+        if let Some(url) = self.http_url.take() {
+            settings.http_backend.url = Some(url);
+            set_backend_type(BackendType::Http, settings);
+        }
+
         if let Some(backend) = self.backend_type.take() {
             set_backend_type(backend, settings);
         }
 
+        if let Some(notebook) = self.notebook.take() {
+            ensure!(!notebook.trim().is_empty(), "Notebook name can't be empty");
+            settings.notebook = Some(notebook);
+        }
+
         setup_logging(self.verbose, self.log_file.take())?;
 
         if let Some(cmd) = self.command.take() {
-            cmd.exec(settings, self.config_path.as_ref())
+            cmd.exec(settings, self.config_path.as_ref()).await
         } else {
             Ok(CliResult::Continue)
         }
@@ -110,6 +164,32 @@ async fn set_sqlite_path(path: PathBuf, settings: &mut Settings) -> anyhow::Resu
     Ok(())
 }
 
+#[cfg(feature = "markdown_dir")]
+async fn set_markdown_dir_path(path: PathBuf, settings: &mut Settings) -> anyhow::Result<()> {
+    if !path.exists() {
+        fs::create_dir_all(&path)?;
+    }
+
+    settings.markdown_dir_backend.dir_path = path.absolutize().map(PathBuf::from).ok();
+
+    Ok(())
+}
+
+/// Gets the notebook to work with, which is the one set from the command line if any, otherwise
+/// the last active notebook in the app.
+pub fn active_notebook(settings: &Settings) -> String {
+    settings
+        .notebook
+        .clone()
+        .or_else(|| {
+            AppState::load(settings)
+                .inspect_err(|err| log::warn!("Loading app state failed. Error info: {err}"))
+                .ok()
+                .and_then(|state| state.notebook)
+        })
+        .unwrap_or_else(default_notebook)
+}
+
 #[inline]
 fn set_backend_type(backend: BackendType, settings: &mut Settings) {
     settings.backend_type = Some(backend);
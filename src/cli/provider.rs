@@ -0,0 +1,196 @@
+use std::path::PathBuf;
+
+use anyhow::bail;
+#[cfg(feature = "http")]
+use backend::HttpDataProvide;
+#[cfg(feature = "json")]
+use backend::JsonDataProvide;
+#[cfg(feature = "markdown_dir")]
+use backend::MarkdownDirDataProvide;
+use backend::{
+    DataProvider, EntriesDTO, Entry, EntryDraft, EntryLogLine, ImportStrategy, ImportSummary,
+    LogLine, MatchSpan, ModifyEntryError,
+};
+#[cfg(feature = "sqlite")]
+use backend::{SqliteDataProvide, SqliteDurability};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::settings::{BackendType, Settings};
+
+/// Data provider for any of the installed back-end types, used by the commands which run
+/// without the TUI.
+pub enum AnyDataProvide {
+    #[cfg(feature = "json")]
+    Json(JsonDataProvide),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteDataProvide),
+    #[cfg(feature = "markdown_dir")]
+    MarkdownDir(MarkdownDirDataProvide),
+    #[cfg(feature = "http")]
+    Http(HttpDataProvide),
+}
+
+impl AnyDataProvide {
+    /// Opens the data provider of the given back-end type with the given file or directory path.
+    pub async fn open(backend_type: BackendType, path: PathBuf) -> anyhow::Result<Self> {
+        match backend_type {
+            #[cfg(feature = "json")]
+            BackendType::Json => Ok(Self::Json(JsonDataProvide::new(path))),
+            #[cfg(feature = "sqlite")]
+            BackendType::Sqlite => Ok(Self::Sqlite(
+                SqliteDataProvide::from_file(path, SqliteDurability::default()).await?,
+            )),
+            #[cfg(feature = "markdown_dir")]
+            BackendType::MarkdownDir => Ok(Self::MarkdownDir(MarkdownDirDataProvide::new(path))),
+            BackendType::Http => bail!("HTTP back-end can't be opened from a local path"),
+            #[allow(unreachable_patterns)]
+            backend => bail!("Feature for back-end type {backend:?} is not installed"),
+        }
+    }
+
+    /// Opens the data provider of the back-end configured in the given settings.
+    pub async fn from_settings(settings: &Settings) -> anyhow::Result<Self> {
+        let backend_type = settings.backend_type.unwrap_or_default();
+
+        #[cfg(feature = "http")]
+        if backend_type == BackendType::Http {
+            let url = settings
+                .http_backend
+                .url
+                .clone()
+                .unwrap_or_else(crate::settings::http_backend::get_default_http_url);
+            let provider =
+                HttpDataProvide::new(url).with_token(settings.http_backend.token.clone());
+            return Ok(Self::Http(provider));
+        }
+
+        let path = settings.get_backend_path()?;
+
+        match backend_type {
+            #[cfg(feature = "json")]
+            BackendType::Json => Ok(Self::Json(
+                JsonDataProvide::new(path).with_backup_count(settings.json_backend.backup_count),
+            )),
+            #[cfg(feature = "sqlite")]
+            BackendType::Sqlite => Ok(Self::Sqlite(
+                SqliteDataProvide::from_file(path, settings.sqlite_backend.durability).await?,
+            )),
+            #[allow(unreachable_patterns)]
+            _ => Self::open(backend_type, path).await,
+        }
+    }
+}
+
+/// Calls the given method on the inner data provider of [`AnyDataProvide`].
+macro_rules! dispatch {
+    ($self:ident, $provider:ident => $call:expr) => {
+        match $self {
+            #[cfg(feature = "json")]
+            AnyDataProvide::Json($provider) => $call,
+            #[cfg(feature = "sqlite")]
+            AnyDataProvide::Sqlite($provider) => $call,
+            #[cfg(feature = "markdown_dir")]
+            AnyDataProvide::MarkdownDir($provider) => $call,
+            #[cfg(feature = "http")]
+            AnyDataProvide::Http($provider) => $call,
+        }
+    };
+}
+
+impl DataProvider for AnyDataProvide {
+    async fn load_all_entries(&self) -> anyhow::Result<Vec<Entry>> {
+        dispatch!(self, p => p.load_all_entries().await)
+    }
+
+    async fn add_entry(&self, entry: EntryDraft) -> Result<Entry, ModifyEntryError> {
+        dispatch!(self, p => p.add_entry(entry).await)
+    }
+
+    async fn remove_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        dispatch!(self, p => p.remove_entry(entry_id).await)
+    }
+
+    async fn update_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        dispatch!(self, p => p.update_entry(entry).await)
+    }
+
+    async fn add_existing_entry(&self, entry: Entry) -> Result<Entry, ModifyEntryError> {
+        dispatch!(self, p => p.add_existing_entry(entry).await)
+    }
+
+    async fn load_trash(&self) -> anyhow::Result<Vec<Entry>> {
+        dispatch!(self, p => p.load_trash().await)
+    }
+
+    async fn restore_entry(&self, entry_id: u32) -> Result<Entry, ModifyEntryError> {
+        dispatch!(self, p => p.restore_entry(entry_id).await)
+    }
+
+    async fn purge_entry(&self, entry_id: u32) -> anyhow::Result<()> {
+        dispatch!(self, p => p.purge_entry(entry_id).await)
+    }
+
+    async fn purge_trash(&self, deleted_before: DateTime<Utc>) -> anyhow::Result<usize> {
+        dispatch!(self, p => p.purge_trash(deleted_before).await)
+    }
+
+    async fn get_export_object(&self, entries_ids: &[u32]) -> anyhow::Result<EntriesDTO> {
+        dispatch!(self, p => p.get_export_object(entries_ids).await)
+    }
+
+    async fn import_entries(&self, entries_dto: EntriesDTO) -> anyhow::Result<()> {
+        dispatch!(self, p => p.import_entries(entries_dto).await)
+    }
+
+    async fn import_entries_with_strategy(
+        &self,
+        entries_dto: EntriesDTO,
+        strategy: ImportStrategy,
+    ) -> anyhow::Result<ImportSummary> {
+        dispatch!(self, p => p.import_entries_with_strategy(entries_dto, strategy).await)
+    }
+
+    async fn assign_priority_to_entries(&self, priority: u32) -> anyhow::Result<()> {
+        dispatch!(self, p => p.assign_priority_to_entries(priority).await)
+    }
+
+    async fn search(&self, query: &str) -> anyhow::Result<Vec<(u32, Vec<MatchSpan>)>> {
+        dispatch!(self, p => p.search(query).await)
+    }
+
+    async fn append_log_line(
+        &self,
+        entry_id: u32,
+        line: LogLine,
+    ) -> Result<Entry, ModifyEntryError> {
+        dispatch!(self, p => p.append_log_line(entry_id, line).await)
+    }
+
+    async fn query_log_lines(
+        &self,
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+    ) -> anyhow::Result<Vec<EntryLogLine>> {
+        dispatch!(self, p => p.query_log_lines(start, end).await)
+    }
+
+    async fn load_orphaned_tags(&self) -> anyhow::Result<Vec<(u32, String)>> {
+        dispatch!(self, p => p.load_orphaned_tags().await)
+    }
+
+    async fn remove_orphaned_tags(&self) -> anyhow::Result<usize> {
+        dispatch!(self, p => p.remove_orphaned_tags().await)
+    }
+
+    async fn flush_to_files(&self) -> anyhow::Result<()> {
+        dispatch!(self, p => p.flush_to_files().await)
+    }
+
+    async fn find_recovery_backup(&self) -> anyhow::Result<Option<std::path::PathBuf>> {
+        dispatch!(self, p => p.find_recovery_backup().await)
+    }
+
+    fn take_load_warning(&self) -> Option<String> {
+        dispatch!(self, p => p.take_load_warning())
+    }
+}
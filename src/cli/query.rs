@@ -0,0 +1,347 @@
+use anyhow::{Context, anyhow};
+use backend::{DataProvider, Entry, MatchSpan};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::{
+    app::{
+        filter::{Filter, FilterCriterion, criterion::TagFilterOption},
+        sorter::{SortCriteria, SortOrder, Sorter},
+        state::AppState,
+    },
+    settings::Settings,
+};
+
+use super::{
+    CliResult, active_notebook,
+    journal_day::{is_entry_of_day, parse_day},
+    provider::AnyDataProvide,
+};
+
+/// Output format of the commands reading the journals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Aligned columns with headers, meant to be read by humans.
+    #[default]
+    Table,
+    /// JSON to be processed by other tools like jq.
+    Json,
+    /// Tab-separated values without headers.
+    Plain,
+}
+
+/// Options of the list command to filter and sort the journals.
+pub struct ListOptions {
+    pub tags: Vec<String>,
+    pub priority: Option<u32>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub sort: Vec<SortCriteria>,
+    pub order: Option<SortOrder>,
+    pub format: OutputFormat,
+}
+
+impl ListOptions {
+    /// Builds the filter of the given options where the journals must meet all the criteria.
+    fn filter(&self) -> anyhow::Result<Option<Filter>> {
+        let mut filter = Filter::default();
+        let criteria = &mut filter.criteria;
+
+        criteria.extend(
+            self.tags
+                .iter()
+                .map(|tag| FilterCriterion::Tag(TagFilterOption::Tag(tag.to_owned()))),
+        );
+
+        if let Some(priority) = self.priority {
+            criteria.push(FilterCriterion::Priority(priority));
+        }
+
+        if let Some(from) = self.from.as_deref() {
+            criteria.push(FilterCriterion::FromDate(parse_day(from)?));
+        }
+
+        if let Some(to) = self.to.as_deref() {
+            criteria.push(FilterCriterion::ToDate(parse_day(to)?));
+        }
+
+        if let Some(title) = self.title.as_ref() {
+            criteria.push(FilterCriterion::Title(title.to_owned()));
+        }
+
+        if let Some(content) = self.content.as_ref() {
+            criteria.push(FilterCriterion::Content(content.to_owned()));
+        }
+
+        Ok((!filter.criteria.is_empty()).then_some(filter))
+    }
+
+    /// Gets the sorter from the options, using the sorting of the app for the missing parts.
+    fn sorter(&self, settings: &Settings) -> Sorter {
+        let mut sorter = AppState::load(settings)
+            .inspect_err(|err| log::warn!("Loading app state failed. Error info: {err}"))
+            .map(|state| state.sorter)
+            .unwrap_or_default();
+
+        if !self.sort.is_empty() {
+            sorter.set_criteria(self.sort.clone());
+        }
+
+        if let Some(order) = self.order {
+            sorter.order = order;
+        }
+
+        sorter
+    }
+}
+
+/// Summary of a journal without its content, used in the JSON output of the lists.
+#[derive(Debug, Serialize)]
+struct EntrySummary<'a> {
+    id: u32,
+    date: NaiveDate,
+    title: &'a str,
+    tags: &'a [String],
+    priority: Option<u32>,
+    notebook: &'a str,
+    updated_at: DateTime<Utc>,
+}
+
+impl<'a> From<&'a Entry> for EntrySummary<'a> {
+    fn from(entry: &'a Entry) -> Self {
+        Self {
+            id: entry.id,
+            date: entry.date.date_naive(),
+            title: &entry.title,
+            tags: &entry.tags,
+            priority: entry.priority,
+            notebook: &entry.notebook,
+            updated_at: entry.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SearchResult<'a> {
+    #[serde(flatten)]
+    entry: EntrySummary<'a>,
+    /// Line of the content with the first match.
+    snippet: &'a str,
+    /// Byte ranges of the matches in the content.
+    matches: &'a [MatchSpan],
+}
+
+pub async fn exec_list(settings: &Settings, options: ListOptions) -> anyhow::Result<CliResult> {
+    let filter = options.filter()?;
+    let sorter = options.sorter(settings);
+
+    let mut entries = load_notebook_entries(settings).await?;
+    if let Some(filter) = filter.as_ref() {
+        entries.retain(|entry| filter.check_entry(entry));
+    }
+    entries.sort_by(|entry1, entry2| sorter.sort(entry1, entry2));
+
+    match options.format {
+        OutputFormat::Json => {
+            let summaries: Vec<_> = entries.iter().map(EntrySummary::from).collect();
+            print_json(&summaries)?;
+        }
+        OutputFormat::Table | OutputFormat::Plain => {
+            let rows = entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.id.to_string(),
+                        entry.date.date_naive().to_string(),
+                        entry.title.to_owned(),
+                        entry.priority.map(|p| p.to_string()).unwrap_or_default(),
+                        entry.tags.join(", "),
+                    ]
+                })
+                .collect();
+
+            print_rows(
+                options.format,
+                &["ID", "DATE", "TITLE", "PRIORITY", "TAGS"],
+                rows,
+            );
+        }
+    }
+
+    Ok(CliResult::Return)
+}
+
+pub async fn exec_show(
+    settings: &Settings,
+    journal: &str,
+    format: OutputFormat,
+) -> anyhow::Result<CliResult> {
+    let provider = AnyDataProvide::from_settings(settings).await?;
+    let entries = provider.load_all_entries().await?;
+
+    // Ids are unique across the notebooks while days are looked up in the active notebook.
+    let entry = match journal.parse::<u32>() {
+        Ok(id) => entries.into_iter().find(|entry| entry.id == id),
+        Err(_) => {
+            let day = parse_day(journal)?;
+            let notebook = active_notebook(settings);
+            entries
+                .into_iter()
+                .find(|entry| entry.notebook == notebook && is_entry_of_day(entry, day))
+        }
+    }
+    .ok_or_else(|| anyhow!("No journal found for '{journal}'"))?;
+
+    match format {
+        OutputFormat::Json => print_json(&entry)?,
+        OutputFormat::Plain => println!("{}", entry.content),
+        OutputFormat::Table => {
+            println!("Title:    {}", entry.title);
+            println!("Date:     {}", entry.date.date_naive());
+            println!("Tags:     {}", entry.tags.join(", "));
+            println!(
+                "Priority: {}",
+                entry.priority.map(|p| p.to_string()).unwrap_or_default()
+            );
+            println!();
+            println!("{}", entry.content);
+        }
+    }
+
+    Ok(CliResult::Return)
+}
+
+pub async fn exec_search(
+    settings: &Settings,
+    query: &str,
+    format: OutputFormat,
+) -> anyhow::Result<CliResult> {
+    let provider = AnyDataProvide::from_settings(settings).await?;
+    let notebook = active_notebook(settings);
+
+    let entries: Vec<Entry> = provider
+        .load_all_entries()
+        .await?
+        .into_iter()
+        .filter(|entry| entry.notebook == notebook)
+        .collect();
+
+    // Search results are ranked by the back-end already.
+    let matches: Vec<(&Entry, Vec<MatchSpan>)> = provider
+        .search(query)
+        .await?
+        .into_iter()
+        .filter_map(|(id, spans)| {
+            let entry = entries.iter().find(|entry| entry.id == id)?;
+            Some((entry, spans))
+        })
+        .collect();
+
+    let results: Vec<SearchResult> = matches
+        .iter()
+        .map(|(entry, spans)| SearchResult {
+            entry: EntrySummary::from(*entry),
+            snippet: first_match_line(&entry.content, spans),
+            matches: spans,
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json => print_json(&results)?,
+        OutputFormat::Table | OutputFormat::Plain => {
+            let rows = results
+                .iter()
+                .map(|result| {
+                    vec![
+                        result.entry.id.to_string(),
+                        result.entry.date.to_string(),
+                        result.entry.title.to_owned(),
+                        result.snippet.to_owned(),
+                    ]
+                })
+                .collect();
+
+            print_rows(format, &["ID", "DATE", "TITLE", "MATCH"], rows);
+        }
+    }
+
+    Ok(CliResult::Return)
+}
+
+async fn load_notebook_entries(settings: &Settings) -> anyhow::Result<Vec<Entry>> {
+    let provider = AnyDataProvide::from_settings(settings).await?;
+    let notebook = active_notebook(settings);
+
+    let entries = provider
+        .load_all_entries()
+        .await?
+        .into_iter()
+        .filter(|entry| entry.notebook == notebook)
+        .collect();
+
+    Ok(entries)
+}
+
+/// Gets the trimmed line of the content which contains the first of the given matches.
+fn first_match_line<'a>(content: &'a str, spans: &[MatchSpan]) -> &'a str {
+    let Some(span) = spans
+        .iter()
+        .find(|span| content.is_char_boundary(span.start))
+    else {
+        return "";
+    };
+
+    let line_start = content[..span.start].rfind('\n').map_or(0, |idx| idx + 1);
+    let line_end = content[span.start..]
+        .find('\n')
+        .map_or(content.len(), |idx| span.start + idx);
+
+    content[line_start..line_end].trim()
+}
+
+fn print_json<T: Serialize + ?Sized>(value: &T) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(value).context("Serializing output to JSON failed")?;
+    println!("{json}");
+
+    Ok(())
+}
+
+/// Prints the rows either as aligned columns with the given headers or as tab-separated values.
+pub(super) fn print_rows(format: OutputFormat, headers: &[&str], rows: Vec<Vec<String>>) {
+    if format == OutputFormat::Plain {
+        for row in rows {
+            println!("{}", row.join("\t"));
+        }
+        return;
+    }
+
+    if rows.is_empty() {
+        println!("No journals found");
+        return;
+    }
+
+    let mut widths: Vec<usize> = headers.iter().map(|header| header.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_row = |cells: Vec<&str>| {
+        let line = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        line.trim_end().to_owned()
+    };
+
+    println!("{}", format_row(headers.to_vec()));
+    for row in &rows {
+        println!("{}", format_row(row.iter().map(String::as_str).collect()));
+    }
+}
@@ -0,0 +1,269 @@
+use std::io::{self, Read};
+
+use anyhow::{Context, ensure};
+use backend::{
+    DataProvider, Entry, EntryDraft, GitDataProvide, LogLine, ModifyEntryError, append_log_line,
+    day_title,
+};
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use crate::settings::Settings;
+
+use super::{
+    CliResult, active_notebook,
+    journal_day::{is_entry_of_day, parse_day},
+    provider::AnyDataProvide,
+};
+
+/// Options of the log command to append lines to the journal of a day without the TUI.
+pub struct QuickLogOptions {
+    pub text: Vec<String>,
+    pub time: Option<NaiveTime>,
+    pub date: Option<String>,
+    pub tags: Vec<String>,
+    pub stdin: bool,
+}
+
+/// Parses the time of the log line given in `HH:MM` format.
+pub fn parse_time(text: &str) -> anyhow::Result<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M")
+        .with_context(|| format!("Invalid time '{text}'. Use HH:MM"))
+}
+
+pub async fn exec_quick_log(
+    settings: &Settings,
+    options: QuickLogOptions,
+) -> anyhow::Result<CliResult> {
+    let now = Local::now();
+    let day = match options.date.as_deref() {
+        Some(date) => parse_day(date)?,
+        None => now.date_naive(),
+    };
+    let time = options.time.unwrap_or(now.time());
+
+    let texts = if options.stdin {
+        let mut input = String::new();
+        io::stdin()
+            .read_to_string(&mut input)
+            .context("Reading the log text from stdin failed")?;
+
+        split_input_lines(&input)
+    } else {
+        vec![options.text.join(" ")]
+    };
+
+    ensure!(
+        texts.iter().any(|text| !text.trim().is_empty()),
+        "Log text can't be empty"
+    );
+
+    let lines = create_log_lines(texts, time, &options.tags);
+
+    let notebook = active_notebook(settings);
+    let provider = AnyDataProvide::from_settings(settings).await?;
+
+    if settings.git_versioning {
+        let provider = GitDataProvide::new(provider, settings.get_backend_path()?)?;
+        append_lines(&provider, &notebook, day, lines, &options.tags).await
+    } else {
+        append_lines(&provider, &notebook, day, lines, &options.tags).await
+    }
+}
+
+/// Splits the piped input into the texts of the log lines, since log lines can't span lines,
+/// skipping the empty ones.
+fn split_input_lines(input: &str) -> Vec<String> {
+    input
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.trim().is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Creates the log lines of the given texts, adding the given tags to the tags of each line.
+fn create_log_lines(texts: Vec<String>, time: NaiveTime, tags: &[String]) -> Vec<LogLine> {
+    texts
+        .into_iter()
+        .map(|text| {
+            let mut line = LogLine::new(time, text);
+            for tag in tags {
+                if !line.tags.contains(tag) {
+                    line.tags.push(tag.to_owned());
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+/// Appends the lines to the journal of the given day at once, creating the journal if it doesn't
+/// exist. The given tags are added to the tags of the journal too, to find it by them.
+async fn append_lines<D: DataProvider>(
+    provider: &D,
+    notebook: &str,
+    day: NaiveDate,
+    lines: Vec<LogLine>,
+    tags: &[String],
+) -> anyhow::Result<CliResult> {
+    let existing = find_day_entry(provider, notebook, day).await?;
+
+    let mut entry = match existing {
+        Some(entry) => entry,
+        None => create_day_entry(provider, notebook, day).await?,
+    };
+
+    for line in lines.iter() {
+        entry.content = append_log_line(&entry.content, line);
+    }
+
+    for tag in tags {
+        if !entry.tags.contains(tag) {
+            entry.tags.push(tag.to_owned());
+        }
+    }
+
+    let entry = provider.update_entry(entry).await?;
+
+    println!(
+        "{} line(s) logged to the journal {}",
+        lines.len(),
+        entry.title
+    );
+
+    Ok(CliResult::Return)
+}
+
+/// Finds the journal of the given day in the given notebook.
+async fn find_day_entry<D: DataProvider>(
+    provider: &D,
+    notebook: &str,
+    day: NaiveDate,
+) -> anyhow::Result<Option<Entry>> {
+    let entry = provider
+        .load_all_entries()
+        .await?
+        .into_iter()
+        .find(|entry| entry.notebook == notebook && is_entry_of_day(entry, day));
+
+    Ok(entry)
+}
+
+/// Creates the journal of the given day with the title of the day.
+async fn create_day_entry<D: DataProvider>(
+    provider: &D,
+    notebook: &str,
+    day: NaiveDate,
+) -> anyhow::Result<Entry> {
+    let date = Utc.from_utc_datetime(&day.and_time(NaiveTime::MIN));
+    let draft =
+        EntryDraft::new(date, day_title(date), Vec::new(), None).with_notebook(notebook.to_owned());
+
+    match provider.add_entry(draft).await {
+        Ok(entry) => Ok(entry),
+        // The journal of the day has been added by another instance in the meantime.
+        Err(ModifyEntryError::DuplicateDay { .. }) => find_day_entry(provider, notebook, day)
+            .await?
+            .context("Journal of the day can't be found"),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use backend::DEFAULT_NOTEBOOK;
+
+    use crate::app::{
+        filter::{Filter, FilterCriterion, criterion::TagFilterOption},
+        test::mock::MockDataProvider,
+    };
+
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn split_input() {
+        let texts = split_input_lines("First line  \n\n   \r\n  Second line\r\nThird\n");
+
+        assert_eq!(texts, vec!["First line", "  Second line", "Third"]);
+    }
+
+    #[test]
+    fn merge_tags() {
+        let texts = vec![String::from("Reviewed #work items"), String::from("Lunch")];
+        let tags = vec![String::from("work"), String::from("daily")];
+
+        let lines = create_log_lines(texts, time(9, 30), &tags);
+
+        assert_eq!(lines[0].tags, vec!["work", "daily"]);
+        assert_eq!(lines[1].tags, vec!["work", "daily"]);
+        assert_eq!(lines[0].to_string(), "09:30 Reviewed #work items #daily");
+        assert_eq!(lines[1].to_string(), "09:30 Lunch #work #daily");
+    }
+
+    #[tokio::test]
+    async fn append_to_existing_entry() {
+        let provider = MockDataProvider::default();
+        let day = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+
+        let date = Utc.from_utc_datetime(&day.and_time(NaiveTime::MIN));
+        let mut draft = EntryDraft::new(date, day_title(date), Vec::new(), None);
+        draft.content = String::from("08:00 Breakfast");
+        provider.add_entry(draft).await.unwrap();
+
+        let texts = split_input_lines("Standup\nCode review\n");
+        let lines = create_log_lines(texts, time(10, 0), &[]);
+        append_lines(&provider, DEFAULT_NOTEBOOK, day, lines, &[])
+            .await
+            .unwrap();
+
+        let entries = provider.load_all_entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            entries[0].content,
+            "08:00 Breakfast\n10:00 Standup\n10:00 Code review"
+        );
+    }
+
+    #[tokio::test]
+    async fn append_creates_entry() {
+        let provider = MockDataProvider::default();
+        let day = NaiveDate::from_ymd_opt(2024, 5, 2).unwrap();
+
+        let lines = create_log_lines(vec![String::from("Started")], time(7, 5), &[]);
+        append_lines(&provider, DEFAULT_NOTEBOOK, day, lines, &[])
+            .await
+            .unwrap();
+
+        let entries = provider.load_all_entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].title, "2024_05_02_Thursday");
+        assert_eq!(entries[0].content, "07:05 Started");
+    }
+
+    #[tokio::test]
+    async fn list_by_tag() {
+        let provider = MockDataProvider::default();
+        let day = NaiveDate::from_ymd_opt(2024, 5, 3).unwrap();
+        let tags = vec![String::from("work")];
+
+        let lines = create_log_lines(vec![String::from("Standup")], time(9, 0), &tags);
+        append_lines(&provider, DEFAULT_NOTEBOOK, day, lines, &tags)
+            .await
+            .unwrap();
+
+        let mut filter = Filter::default();
+        filter
+            .criteria
+            .push(FilterCriterion::Tag(TagFilterOption::Tag(String::from(
+                "work",
+            ))));
+
+        let entries = provider.load_all_entries().await.unwrap();
+        assert_eq!(entries[0].tags, vec!["work"]);
+        assert!(filter.check_entry(&entries[0]));
+    }
+}
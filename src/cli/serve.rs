@@ -0,0 +1,74 @@
+use std::net::SocketAddr;
+
+use anyhow::{Context, bail};
+use backend::{GitDataProvide, Uuid};
+use tokio::net::TcpListener;
+
+use crate::settings::{BackendType, Settings, server::get_default_bind_address};
+
+use super::{CliResult, provider::AnyDataProvide};
+
+pub async fn exec_serve(
+    settings: &Settings,
+    bind: Option<SocketAddr>,
+) -> anyhow::Result<CliResult> {
+    let backend_type = settings.backend_type.unwrap_or_default();
+    if backend_type == BackendType::Http {
+        bail!("Serving the HTTP back-end isn't supported. Please configure a local back-end");
+    }
+
+    let bind = bind
+        .or(settings.server.bind)
+        .unwrap_or_else(get_default_bind_address);
+
+    let listener = TcpListener::bind(bind)
+        .await
+        .with_context(|| format!("Binding the server to {bind} failed"))?;
+
+    println!(
+        "Serving journals of the {backend_type:?} back-end on http://{}. Press <Ctrl-c> to stop",
+        listener.local_addr()?
+    );
+
+    // Requests are always authorized since local processes and web pages can reach loopback
+    // addresses too.
+    let token = match settings.server.token.clone() {
+        Some(token) => token,
+        None => {
+            let token = Uuid::new_v4().simple().to_string();
+            println!(
+                "No token is set in the 'server' section of the configurations. Clients must use the generated token: {token}"
+            );
+            token
+        }
+    };
+    let allowed_hosts = settings.server.allowed_hosts.clone();
+
+    let provider = AnyDataProvide::from_settings(settings).await?;
+
+    let result = if settings.git_versioning {
+        let provider = GitDataProvide::new(provider, settings.get_backend_path()?)?;
+        serve_until_stopped(provider, listener, token, allowed_hosts).await
+    } else {
+        serve_until_stopped(provider, listener, token, allowed_hosts).await
+    };
+
+    result.map(|()| CliResult::Return)
+}
+
+/// Serves the provider until the server fails or the user stops it with <Ctrl-c>.
+async fn serve_until_stopped<D: backend::DataProvider>(
+    provider: D,
+    listener: TcpListener,
+    token: String,
+    allowed_hosts: Vec<String>,
+) -> anyhow::Result<()> {
+    tokio::select! {
+        result = backend::serve(provider, listener, token, allowed_hosts) => result,
+        result = tokio::signal::ctrl_c() => {
+            result.context("Listening to stop signal failed")?;
+            println!("Server stopped");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, bail, ensure};
+use backend::{
+    DataProvider, GitDataProvide, SyncAction, SyncBase, SyncPlan, SyncSide, apply_sync_plan,
+    plan_sync,
+};
+use clap::ValueEnum;
+use path_absolutize::Absolutize;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::state::AppState,
+    settings::{BackendType, Settings},
+};
+
+use super::{CliResult, provider::AnyDataProvide};
+
+const SYNC_STATE_FILE_NAME: &str = "sync.json";
+
+/// Side whose content is kept for journals changed on both sides in conflicting ways.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SyncPrefer {
+    Local,
+    Peer,
+}
+
+impl From<SyncPrefer> for SyncSide {
+    fn from(prefer: SyncPrefer) -> Self {
+        match prefer {
+            SyncPrefer::Local => SyncSide::Local,
+            SyncPrefer::Peer => SyncSide::Peer,
+        }
+    }
+}
+
+/// Bases of the last syncs between the local back-ends and their peers.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SyncState {
+    pairs: Vec<SyncPair>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SyncPair {
+    /// Path or URL of the local back-end.
+    local: String,
+    peer: PathBuf,
+    base: SyncBase,
+}
+
+impl SyncState {
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(path)
+            .with_context(|| format!("Opening sync state file failed. Path: {}", path.display()))?;
+        serde_json::from_reader(file)
+            .with_context(|| format!("Parsing sync state file failed. Path: {}", path.display()))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(BufWriter::new(file), self)?;
+
+        Ok(())
+    }
+
+    fn base(&self, local: &str, peer: &Path) -> Option<&SyncBase> {
+        self.pairs
+            .iter()
+            .find(|pair| pair.local == local && pair.peer == peer)
+            .map(|pair| &pair.base)
+    }
+
+    fn set_base(&mut self, local: String, peer: PathBuf, base: SyncBase) {
+        match self
+            .pairs
+            .iter_mut()
+            .find(|pair| pair.local == local && pair.peer == peer)
+        {
+            Some(pair) => pair.base = base,
+            None => self.pairs.push(SyncPair { local, peer, base }),
+        }
+    }
+}
+
+pub async fn exec_sync(
+    settings: &Settings,
+    peer_path: &Path,
+    peer_backend: Option<BackendType>,
+    prefer: Option<SyncPrefer>,
+    dry_run: bool,
+) -> anyhow::Result<CliResult> {
+    let peer_path = peer_path.absolutize()?.to_path_buf();
+    let peer_backend = match peer_backend {
+        Some(backend) => backend,
+        None => detect_backend_type(&peer_path)?,
+    };
+
+    let local_key = local_backend_key(settings)?;
+    ensure!(
+        Path::new(&local_key) != peer_path,
+        "Can't sync the back-end with itself"
+    );
+
+    let peer = AnyDataProvide::open(peer_backend, peer_path.clone()).await?;
+    let local = AnyDataProvide::from_settings(settings).await?;
+
+    let options = SyncOptions {
+        settings,
+        local_key,
+        peer_path,
+        prefer: prefer.map(SyncSide::from),
+        dry_run,
+    };
+
+    if settings.git_versioning {
+        let local = GitDataProvide::new(local, settings.get_backend_path()?)?;
+        sync_with(&local, &peer, options).await
+    } else {
+        sync_with(&local, &peer, options).await
+    }
+}
+
+struct SyncOptions<'a> {
+    settings: &'a Settings,
+    local_key: String,
+    peer_path: PathBuf,
+    prefer: Option<SyncSide>,
+    dry_run: bool,
+}
+
+async fn sync_with<L: DataProvider>(
+    local: &L,
+    peer: &AnyDataProvide,
+    options: SyncOptions<'_>,
+) -> anyhow::Result<CliResult> {
+    let state_path = AppState::get_persist_dir(options.settings)?.join(SYNC_STATE_FILE_NAME);
+    let mut state = SyncState::load(&state_path)?;
+
+    let empty_base = SyncBase::default();
+    let base = state
+        .base(&options.local_key, &options.peer_path)
+        .unwrap_or(&empty_base);
+
+    let plan = plan_sync(
+        local.load_all_entries().await?,
+        peer.load_all_entries().await?,
+        base,
+        options.prefer,
+    );
+
+    if options.dry_run {
+        print_actions(&plan);
+    } else {
+        apply_sync_plan(local, peer, &plan).await?;
+    }
+
+    print_report(&plan, &options.peer_path, options.dry_run);
+
+    if !options.dry_run {
+        state.set_base(options.local_key, options.peer_path, plan.base);
+        state.save(&state_path)?;
+    }
+
+    Ok(CliResult::Return)
+}
+
+fn print_actions(plan: &SyncPlan) {
+    for action in &plan.actions {
+        let (verb, side, title) = match action {
+            SyncAction::Add(side, draft) => ("Add", side, &draft.title),
+            SyncAction::Update(side, entry) => ("Update", side, &entry.title),
+            SyncAction::Remove(side, entry) => ("Remove", side, &entry.title),
+        };
+        println!("{verb} {title} on {side}");
+    }
+}
+
+fn print_report(plan: &SyncPlan, peer_path: &Path, dry_run: bool) {
+    let prefix = if dry_run { "Dry run: " } else { "" };
+    println!("{prefix}Synced with {}", peer_path.display());
+    println!("  Local: {}", plan.summary(SyncSide::Local));
+    println!("  Peer: {}", plan.summary(SyncSide::Peer));
+
+    if plan.conflicts.is_empty() {
+        return;
+    }
+
+    println!();
+    println!(
+        "{} journals have been changed on both sides and have been left unchanged:",
+        plan.conflicts.len()
+    );
+    for conflict in &plan.conflicts {
+        println!(
+            "\n{} (local id: {}, peer id: {})",
+            conflict.local.title, conflict.local.id, conflict.peer.id
+        );
+        for line in conflict.marked_content.lines() {
+            println!("  {line}");
+        }
+    }
+    println!();
+    println!(
+        "Resolve them by editing the journal on one side, or sync with '--prefer local|peer' to keep the content of one side"
+    );
+}
+
+/// Gets the path or URL of the configured back-end, identifying it in the sync state.
+fn local_backend_key(settings: &Settings) -> anyhow::Result<String> {
+    #[cfg(feature = "http")]
+    if settings.backend_type.unwrap_or_default() == BackendType::Http {
+        return settings
+            .http_backend
+            .url
+            .clone()
+            .context("URL of the HTTP back-end isn't set");
+    }
+
+    let path = settings.get_backend_path()?;
+    Ok(path.absolutize()?.to_string_lossy().into_owned())
+}
+
+/// Detects the back-end type of the peer from its path.
+fn detect_backend_type(path: &Path) -> anyhow::Result<BackendType> {
+    if path.is_dir() {
+        return Ok(BackendType::MarkdownDir);
+    }
+
+    let extension = path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase());
+
+    match extension.as_deref() {
+        Some("json") => Ok(BackendType::Json),
+        Some("db" | "sqlite" | "sqlite3") => Ok(BackendType::Sqlite),
+        _ => bail!(
+            "Back-end type of '{}' couldn't be detected. Please set it with '--backend'",
+            path.display()
+        ),
+    }
+}
@@ -20,7 +20,11 @@ async fn main() -> Result<()> {
     let cli = cli::Cli::parse();
 
     let custom_config = cli.config_path.clone();
-    let mut settings = Settings::new(custom_config.clone()).await?;
+    let mut settings = if cli.is_config_command() {
+        Settings::default()
+    } else {
+        Settings::new(custom_config.clone()).await?
+    };
 
     let mut pending_cmd = None;
 
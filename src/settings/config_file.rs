@@ -0,0 +1,458 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, anyhow, bail, ensure};
+use serde_json::Value as JsonValue;
+use toml_edit::{DocumentMut, Item, TableLike, Value};
+
+use super::{Settings, config_file_path};
+
+/// Separator of the keys of nested settings, e.g. `external_editor.command`.
+const KEY_SEPARATOR: char = '.';
+
+/// Configuration file loaded with its comments and formatting, to get and change the settings by
+/// their keys while keeping the rest of the file unchanged.
+///
+/// Keys are validated against the tree of the serialized [`Settings`] and values against their
+/// deserialization, so the file stays readable by the app after each change.
+pub struct ConfigFile {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl ConfigFile {
+    /// Loads the configuration file in the given custom configuration directory or in the
+    /// default one. Missing file is loaded as empty.
+    pub async fn load(custom_config_dir: Option<&PathBuf>) -> anyhow::Result<Self> {
+        let path = config_file_path(custom_config_dir)?;
+
+        let text = if path.exists() {
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| anyhow!("Failed to load configuration file. Error infos: {err}"))?
+        } else {
+            String::new()
+        };
+
+        Self::from_text(path, &text)
+    }
+
+    fn from_text(path: PathBuf, text: &str) -> anyhow::Result<Self> {
+        let doc = text
+            .parse::<DocumentMut>()
+            .map_err(|err| anyhow!("Failed to read configuration file. Error infos: {err}"))?;
+
+        Ok(Self { path, doc })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the configuration to its file, creating the file if it doesn't exist.
+    pub async fn save(&self) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(&self.path, self.doc.to_string())
+            .await
+            .context("Failed to write configuration file")
+    }
+
+    /// Gets the value of the given key as text, including the default values of the keys
+    /// missing in the file. Returns `None` for optional settings without a value.
+    pub fn get(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let parts = split_key(key)?;
+        ensure_known_key(key, &parts)?;
+
+        let mut settings = self.parse_settings()?;
+        settings.complete_missing_options()?;
+        let tree = settings_tree(&settings)?;
+
+        let value = lookup(&tree, &parts)
+            .and_then(json_to_toml)
+            .map(|value| match value {
+                toml::Value::String(text) => Ok(text),
+                toml::Value::Table(table) => toml::to_string(&table)
+                    .map(|text| text.trim_end().to_owned())
+                    .context("Serializing settings failed"),
+                value => Ok(value.to_string()),
+            })
+            .transpose()?;
+
+        Ok(value)
+    }
+
+    /// Sets the value of the given key. The value is read as TOML value (e.g. numbers and
+    /// booleans) falling back to a string if the key doesn't accept it.
+    pub fn set(&mut self, key: &str, value: &str) -> anyhow::Result<()> {
+        let parts = split_key(key)?;
+        let node = ensure_known_key(key, &parts)?;
+        if let Some(children) = node.as_object() {
+            let keys: Vec<String> = children
+                .keys()
+                .map(|child| format!("{key}.{child}"))
+                .collect();
+            bail!(
+                "Key '{key}' is a table. Set one of its keys instead: {}",
+                keys.join(", ")
+            );
+        }
+
+        let settings = self.parse_settings()?;
+        self.expand_short_forms(&settings, &parts)?;
+
+        let mut candidates = Vec::with_capacity(2);
+        if let Ok(typed) = value.parse::<Value>() {
+            candidates.push(typed);
+        }
+        candidates.push(Value::from(value));
+
+        let mut first_err = None;
+        for candidate in candidates {
+            let mut doc = self.doc.clone();
+            set_value(&mut doc, &parts, candidate)?;
+
+            match toml::from_str::<Settings>(&doc.to_string()) {
+                Ok(_) => {
+                    self.doc = doc;
+                    return Ok(());
+                }
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                }
+            }
+        }
+
+        let err = first_err.expect("At least one value must be tried");
+        bail!(
+            "Invalid value '{value}' for key '{key}': {}",
+            err.message().trim()
+        );
+    }
+
+    /// Removes the given key from the file, so its default value is used.
+    /// Returns `false` if the key isn't set in the file.
+    pub fn unset(&mut self, key: &str) -> anyhow::Result<bool> {
+        let parts = split_key(key)?;
+        ensure_known_key(key, &parts)?;
+
+        if parts.len() > 1 {
+            let settings = self.parse_settings()?;
+            self.expand_short_forms(&settings, &parts)?;
+        }
+
+        let (last, parents) = parts.split_last().expect("Key parts can't be empty");
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        for part in parents {
+            match table.get_mut(part).and_then(Item::as_table_like_mut) {
+                Some(child) => table = child,
+                None => return Ok(false),
+            }
+        }
+
+        Ok(table.remove(last).is_some())
+    }
+
+    /// Checks the configuration file for values the settings can't be read from and for unknown
+    /// keys, returning the found problems.
+    pub fn validate(&self) -> anyhow::Result<Vec<String>> {
+        let mut problems = Vec::new();
+
+        if let Err(err) = toml::from_str::<Settings>(&self.doc.to_string()) {
+            problems.push(err.to_string().trim().to_owned());
+        }
+
+        let schema = settings_tree(&Settings::default())?;
+        collect_unknown_keys(self.doc.as_table(), &schema, "", &mut problems);
+
+        Ok(problems)
+    }
+
+    fn parse_settings(&self) -> anyhow::Result<Settings> {
+        toml::from_str(&self.doc.to_string()).map_err(|err| {
+            anyhow!(
+                "Configuration file is invalid: {}. Run 'tjournal config validate' for details",
+                err.message().trim()
+            )
+        })
+    }
+
+    /// Replaces the tables of the parents of the given key which are set in a short form (e.g.
+    /// the external editor as command string) with their full tables.
+    fn expand_short_forms(&mut self, settings: &Settings, parts: &[&str]) -> anyhow::Result<()> {
+        let tree = toml::Value::try_from(settings).context("Serializing settings failed")?;
+
+        let mut table: &mut dyn TableLike = self.doc.as_table_mut();
+        let mut node = &tree;
+        for part in &parts[..parts.len() - 1] {
+            node = node
+                .get(part)
+                .with_context(|| format!("Settings are missing the table '{part}'"))?;
+
+            if table.get(part).is_some_and(|item| !item.is_table_like()) {
+                let table_text = toml::to_string(node).context("Serializing settings failed")?;
+                let expanded = table_text
+                    .parse::<DocumentMut>()
+                    .context("Parsing serialized settings failed")?;
+
+                // Key is inserted again to drop its formatting as value key.
+                table.remove(part);
+                table.insert(part, Item::Table(expanded.as_table().clone()));
+            }
+
+            table = table
+                .entry(part)
+                .or_insert(toml_edit::table())
+                .as_table_like_mut()
+                .expect("Parents of the key are tables");
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits the given key into the keys of its nested tables.
+fn split_key(key: &str) -> anyhow::Result<Vec<&str>> {
+    let parts: Vec<&str> = key.split(KEY_SEPARATOR).map(str::trim).collect();
+    ensure!(
+        parts.iter().all(|part| !part.is_empty()),
+        "Invalid configuration key '{key}'"
+    );
+
+    Ok(parts)
+}
+
+/// Gets the tree of the given settings with all their keys, including the optional ones
+/// without values.
+fn settings_tree(settings: &Settings) -> anyhow::Result<JsonValue> {
+    serde_json::to_value(settings).context("Serializing settings failed")
+}
+
+fn lookup<'a>(tree: &'a JsonValue, parts: &[&str]) -> Option<&'a JsonValue> {
+    parts.iter().try_fold(tree, |node, part| node.get(part))
+}
+
+/// Makes sure the given key exists in the settings, returning its node in the tree of the
+/// default settings.
+fn ensure_known_key(key: &str, parts: &[&str]) -> anyhow::Result<JsonValue> {
+    let schema = settings_tree(&Settings::default())?;
+
+    let mut node = &schema;
+    for part in parts {
+        node = match node.get(part) {
+            Some(child) => child,
+            None => {
+                let available = node
+                    .as_object()
+                    .map(|children| children.keys().cloned().collect::<Vec<_>>().join(", "))
+                    .unwrap_or_default();
+                if available.is_empty() {
+                    bail!("Unknown configuration key '{key}'");
+                }
+                bail!("Unknown configuration key '{key}'. Available keys: {available}");
+            }
+        };
+    }
+
+    Ok(node.to_owned())
+}
+
+/// Sets the value of the given key in the document, creating its missing tables and keeping
+/// the formatting and comments of the existing value.
+fn set_value(doc: &mut DocumentMut, parts: &[&str], mut value: Value) -> anyhow::Result<()> {
+    let (last, parents) = parts.split_last().expect("Key parts can't be empty");
+
+    let mut table: &mut dyn TableLike = doc.as_table_mut();
+    for part in parents {
+        table = table
+            .entry(part)
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .with_context(|| format!("Key '{part}' is set to a value instead of a table"))?;
+    }
+
+    value.decor_mut().clear();
+    match table.get_mut(last) {
+        Some(Item::Value(existing)) => {
+            *value.decor_mut() = existing.decor().clone();
+            *existing = value;
+        }
+        _ => {
+            table.insert(last, Item::Value(value));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the value from the tree of the settings to a TOML value, skipping the optional
+/// settings without values which can't be represented in TOML.
+fn json_to_toml(value: &JsonValue) -> Option<toml::Value> {
+    match value {
+        JsonValue::Null => None,
+        JsonValue::Bool(flag) => Some(toml::Value::Boolean(*flag)),
+        JsonValue::Number(number) => number
+            .as_i64()
+            .map(toml::Value::Integer)
+            .or_else(|| number.as_f64().map(toml::Value::Float)),
+        JsonValue::String(text) => Some(toml::Value::String(text.to_owned())),
+        JsonValue::Array(items) => Some(toml::Value::Array(
+            items.iter().filter_map(json_to_toml).collect(),
+        )),
+        JsonValue::Object(children) => Some(toml::Value::Table(
+            children
+                .iter()
+                .filter_map(|(key, child)| Some((key.to_owned(), json_to_toml(child)?)))
+                .collect(),
+        )),
+    }
+}
+
+fn collect_unknown_keys(
+    table: &dyn TableLike,
+    schema: &JsonValue,
+    prefix: &str,
+    problems: &mut Vec<String>,
+) {
+    for (key, item) in table.iter() {
+        let path = if prefix.is_empty() {
+            key.to_owned()
+        } else {
+            format!("{prefix}{KEY_SEPARATOR}{key}")
+        };
+
+        match schema.get(key) {
+            None => problems.push(format!("Unknown key '{path}'")),
+            Some(node) if node.is_object() => {
+                if let Some(child_table) = item.as_table_like() {
+                    collect_unknown_keys(child_table, node, &path, problems);
+                }
+            }
+            Some(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG_TEXT: &str = r#"# Settings of tjournal
+history_limit = 5 # Undo steps
+external_editor = "vim"
+
+[export]
+# Directory of the exports
+default_path = "/tmp/exports"
+"#;
+
+    fn config() -> ConfigFile {
+        ConfigFile::from_text(PathBuf::from("config.toml"), CONFIG_TEXT).unwrap()
+    }
+
+    #[test]
+    fn set_keeps_formatting() {
+        let mut config = config();
+
+        config.set("history_limit", "20").unwrap();
+        config.set("sync_os_clipboard", "true").unwrap();
+        config.set("export.default_path", "/home/exports").unwrap();
+
+        assert_eq!(
+            config.doc.to_string(),
+            r#"# Settings of tjournal
+history_limit = 20 # Undo steps
+external_editor = "vim"
+sync_os_clipboard = true
+
+[export]
+# Directory of the exports
+default_path = "/home/exports"
+"#
+        );
+        assert_eq!(config.get("history_limit").unwrap().as_deref(), Some("20"));
+    }
+
+    #[test]
+    fn set_external_editor_short_form() {
+        let mut config = config();
+
+        assert_eq!(
+            config.get("external_editor.command").unwrap().as_deref(),
+            Some("vim")
+        );
+
+        config.set("external_editor.auto_save", "true").unwrap();
+        assert_eq!(
+            config.get("external_editor.command").unwrap().as_deref(),
+            Some("vim")
+        );
+        assert_eq!(
+            config.get("external_editor.auto_save").unwrap().as_deref(),
+            Some("true")
+        );
+
+        // Values which aren't valid TOML are set as strings.
+        config
+            .set("external_editor.command", "code --wait")
+            .unwrap();
+        assert_eq!(
+            config.get("external_editor.command").unwrap().as_deref(),
+            Some("code --wait")
+        );
+    }
+
+    #[test]
+    fn invalid_keys_and_values() {
+        let mut config = config();
+
+        let err = config.set("history_limt", "5").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Unknown configuration key 'history_limt'")
+        );
+
+        let err = config.set("export.path", "/tmp").unwrap_err();
+        assert!(err.to_string().contains("default_path, show_confirmation"));
+
+        let err = config.set("history_limit", "many").unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Invalid value 'many' for key 'history_limit'")
+        );
+
+        let err = config.set("export", "5").unwrap_err();
+        assert!(err.to_string().starts_with("Key 'export' is a table"));
+
+        assert!(config.get("notebook").is_err());
+        assert_eq!(config.doc.to_string(), CONFIG_TEXT);
+    }
+
+    #[test]
+    fn unset() {
+        let mut config = config();
+
+        assert!(config.unset("export.default_path").unwrap());
+        assert!(!config.unset("export.default_path").unwrap());
+        assert!(!config.unset("default_journal_priority").unwrap());
+        assert!(config.unset("not_a_key").is_err());
+
+        assert_eq!(config.get("export.default_path").unwrap(), None);
+        assert_eq!(config.get("history_limit").unwrap().as_deref(), Some("5"));
+    }
+
+    #[test]
+    fn validate() {
+        assert!(config().validate().unwrap().is_empty());
+
+        let text = "history_limit = \"many\"\nunknown = 1\n\n[export]\nshow_confirm = false\n";
+        let config = ConfigFile::from_text(PathBuf::from("config.toml"), text).unwrap();
+        let problems = config.validate().unwrap();
+
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("history_limit"));
+        assert_eq!(problems[1], "Unknown key 'unknown'");
+        assert_eq!(problems[2], "Unknown key 'export.show_confirm'");
+    }
+}
@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+
+use super::server::DEFAULT_BIND_ADDRESS;
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct HttpBackend {
+    /// URL of the journal server started with the `serve` command.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Token to authorize on the journal server with.
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+pub fn get_default_http_url() -> String {
+    format!("http://{DEFAULT_BIND_ADDRESS}")
+}
@@ -4,10 +4,26 @@ use serde::{Deserialize, Serialize};
 
 use super::get_default_data_dir;
 
-#[derive(Debug, Deserialize, Serialize, Default)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct JsonBackend {
     #[serde(default)]
     pub file_path: Option<PathBuf>,
+    /// Count of the rotating backup generations to keep for the entries file.
+    #[serde(default = "default_backup_count")]
+    pub backup_count: usize,
+}
+
+impl Default for JsonBackend {
+    fn default() -> Self {
+        Self {
+            file_path: None,
+            backup_count: default_backup_count(),
+        }
+    }
+}
+
+const fn default_backup_count() -> usize {
+    3
 }
 
 pub fn get_default_json_path() -> anyhow::Result<PathBuf> {
@@ -0,0 +1,15 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::get_default_data_dir;
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct MarkdownDirBackend {
+    #[serde(default)]
+    pub dir_path: Option<PathBuf>,
+}
+
+pub fn get_default_markdown_dir_path() -> anyhow::Result<PathBuf> {
+    Ok(get_default_data_dir()?.join("entries"))
+}
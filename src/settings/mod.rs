@@ -13,20 +13,35 @@ use serde::{
     Deserialize, Deserializer, Serialize,
     de::{self, MapAccess, Visitor},
 };
+use toml_edit::{DocumentMut, value};
 
 use crate::app::state::AppState;
 
+pub use self::config_file::ConfigFile;
+#[cfg(feature = "http")]
+use self::http_backend::{HttpBackend, get_default_http_url};
 #[cfg(feature = "json")]
 use self::json_backend::{JsonBackend, get_default_json_path};
+#[cfg(feature = "markdown_dir")]
+use self::markdown_dir_backend::{MarkdownDirBackend, get_default_markdown_dir_path};
+#[cfg(feature = "http")]
+use self::server::{ServerSettings, get_default_bind_address};
 #[cfg(feature = "sqlite")]
 use self::sqlite_backend::{SqliteBackend, get_default_sqlite_path};
 use self::{export::ExportSettings, external_editor::ExternalEditor};
 
+#[cfg(feature = "http")]
+pub mod http_backend;
 #[cfg(feature = "json")]
 pub mod json_backend;
+#[cfg(feature = "markdown_dir")]
+pub mod markdown_dir_backend;
+#[cfg(feature = "http")]
+pub mod server;
 #[cfg(feature = "sqlite")]
 pub mod sqlite_backend;
 
+mod config_file;
 mod export;
 mod external_editor;
 
@@ -46,6 +61,16 @@ pub struct Settings {
     #[cfg(feature = "sqlite")]
     #[serde(default)]
     pub sqlite_backend: SqliteBackend,
+    #[cfg(feature = "markdown_dir")]
+    #[serde(default)]
+    pub markdown_dir_backend: MarkdownDirBackend,
+    #[cfg(feature = "http")]
+    #[serde(default)]
+    pub http_backend: HttpBackend,
+    /// Settings of the journal server started with the `serve` command.
+    #[cfg(feature = "http")]
+    #[serde(default)]
+    pub server: ServerSettings,
     #[serde(default)]
     pub default_journal_priority: Option<u32>,
     #[serde(default)]
@@ -55,6 +80,10 @@ pub struct Settings {
     #[serde(default = "default_history_limit")]
     /// Set the maximum size of the history stacks (undo & redo) size.
     pub history_limit: usize,
+    #[serde(default = "default_trash_retention_days")]
+    /// Days the deleted journals are kept in the trash before being purged. Zero keeps them
+    /// until they are purged manually.
+    pub trash_retention_days: u32,
     #[serde(default = "default_colored_tags")]
     pub colored_tags: bool,
     #[serde(default)]
@@ -62,6 +91,13 @@ pub struct Settings {
     pub datum_visibility: DatumVisibility,
     /// Overwrite the path for the directory used to persist the app state.
     pub app_state_dir: Option<PathBuf>,
+    #[serde(default)]
+    /// Commits each change on the back-end file or directory to a git repository in the directory
+    /// of the back-end or in its parent.
+    pub git_versioning: bool,
+    /// Notebook set from the command line, overriding the last active notebook.
+    #[serde(skip)]
+    pub notebook: Option<String>,
 }
 
 impl Default for Settings {
@@ -74,13 +110,22 @@ impl Default for Settings {
             json_backend: Default::default(),
             #[cfg(feature = "sqlite")]
             sqlite_backend: Default::default(),
+            #[cfg(feature = "markdown_dir")]
+            markdown_dir_backend: Default::default(),
+            #[cfg(feature = "http")]
+            http_backend: Default::default(),
+            #[cfg(feature = "http")]
+            server: Default::default(),
             default_journal_priority: Default::default(),
             scroll_per_page: Default::default(),
             sync_os_clipboard: Default::default(),
             history_limit: default_history_limit(),
+            trash_retention_days: default_trash_retention_days(),
             colored_tags: default_colored_tags(),
             datum_visibility: Default::default(),
             app_state_dir: Default::default(),
+            git_versioning: Default::default(),
+            notebook: Default::default(),
         }
     }
 }
@@ -105,37 +150,27 @@ pub enum BackendType {
     Json,
     #[cfg_attr(feature = "sqlite", default)]
     Sqlite,
+    #[cfg_attr(all(not(feature = "json"), not(feature = "sqlite")), default)]
+    MarkdownDir,
+    /// Journal server started with the `serve` command.
+    Http,
 }
 
 const fn default_history_limit() -> usize {
     10
 }
 
+const fn default_trash_retention_days() -> u32 {
+    30
+}
+
 const fn default_colored_tags() -> bool {
     true
 }
 
 impl Settings {
     pub async fn new(custom_config_dir: Option<PathBuf>) -> anyhow::Result<Self> {
-        let config_file = if let Some(path) = custom_config_dir {
-            ensure!(
-                path.exists(),
-                "Provided custom directory doesn't exit. Path: {}",
-                path.display()
-            );
-
-            // Accept path configuration file for backward compatibility.
-            if path.is_file() {
-                path
-            } else if path.is_dir() {
-                settings_file_path(&path)
-            } else {
-                bail!("Provided configuration files is nighter file nor directory");
-            }
-        } else {
-            let default_dir = settings_default_dir_path()?;
-            settings_file_path(&default_dir)
-        };
+        let config_file = config_file_path(custom_config_dir.as_ref())?;
 
         let settings = if config_file.exists() {
             let file_content = tokio::fs::read_to_string(config_file)
@@ -159,20 +194,32 @@ impl Settings {
 
     pub fn complete_missing_options(&mut self) -> anyhow::Result<()> {
         // This check is to ensure that all added fields to settings struct are considered here
-        #[cfg(all(debug_assertions, feature = "sqlite", feature = "json"))]
+        #[cfg(all(
+            debug_assertions,
+            feature = "sqlite",
+            feature = "json",
+            feature = "markdown_dir",
+            feature = "http"
+        ))]
         let Settings {
             backend_type: _,
             json_backend: _,
             sqlite_backend: _,
+            markdown_dir_backend: _,
+            http_backend: _,
+            server: _,
             export: _,
             external_editor: _,
             default_journal_priority: _,
             scroll_per_page: _,
             sync_os_clipboard: _,
             history_limit: _,
+            trash_retention_days: _,
             colored_tags: _,
             datum_visibility: _,
             app_state_dir: _,
+            git_versioning: _,
+            notebook: _,
         } = self;
 
         if self.backend_type.is_none() {
@@ -189,6 +236,21 @@ impl Settings {
             self.sqlite_backend.file_path = Some(get_default_sqlite_path()?)
         }
 
+        #[cfg(feature = "markdown_dir")]
+        if self.markdown_dir_backend.dir_path.is_none() {
+            self.markdown_dir_backend.dir_path = Some(get_default_markdown_dir_path()?)
+        }
+
+        #[cfg(feature = "http")]
+        if self.http_backend.url.is_none() {
+            self.http_backend.url = Some(get_default_http_url())
+        }
+
+        #[cfg(feature = "http")]
+        if self.server.bind.is_none() {
+            self.server.bind = Some(get_default_bind_address())
+        }
+
         if self.scroll_per_page.is_none() {
             self.scroll_per_page = Some(DEFAULT_SCROLL_PER_PAGE);
         }
@@ -203,6 +265,73 @@ impl Settings {
     pub fn get_scroll_per_page(&self) -> usize {
         self.scroll_per_page.unwrap_or(DEFAULT_SCROLL_PER_PAGE)
     }
+
+    /// Sets the back-end type with its file or directory path in the configuration file, keeping
+    /// the rest of the file unchanged. Returns the path of the configuration file.
+    pub async fn write_backend_to_config(
+        custom_config_dir: Option<&PathBuf>,
+        backend_type: BackendType,
+        path: &Path,
+    ) -> anyhow::Result<PathBuf> {
+        let (table, key) = match backend_type {
+            BackendType::Json => ("json_backend", "file_path"),
+            BackendType::Sqlite => ("sqlite_backend", "file_path"),
+            BackendType::MarkdownDir => ("markdown_dir_backend", "dir_path"),
+            BackendType::Http => bail!("HTTP back-end doesn't have a local file or directory"),
+        };
+
+        let config_file = config_file_path(custom_config_dir)?;
+
+        let mut config = if config_file.exists() {
+            tokio::fs::read_to_string(&config_file)
+                .await
+                .map_err(|err| anyhow!("Failed to load configuration file. Error infos: {err}"))?
+                .parse::<DocumentMut>()
+                .map_err(|err| anyhow!("Failed to read configuration file. Error infos: {err}"))?
+        } else {
+            DocumentMut::new()
+        };
+
+        let backend_name = toml::Value::try_from(backend_type)?;
+        config["backend_type"] = value(backend_name.as_str().unwrap_or_default());
+        config[table][key] = value(path.to_string_lossy().as_ref());
+
+        if let Some(parent) = config_file.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&config_file, config.to_string())
+            .await
+            .context("Failed to write configuration file")?;
+
+        Ok(config_file)
+    }
+
+    /// Gets the path of the file or directory used by the current back-end type.
+    pub fn get_backend_path(&self) -> anyhow::Result<PathBuf> {
+        match self.backend_type.unwrap_or_default() {
+            #[cfg(feature = "json")]
+            BackendType::Json => self
+                .json_backend
+                .file_path
+                .clone()
+                .map_or_else(get_default_json_path, Ok),
+            #[cfg(feature = "sqlite")]
+            BackendType::Sqlite => self
+                .sqlite_backend
+                .file_path
+                .clone()
+                .map_or_else(get_default_sqlite_path, Ok),
+            #[cfg(feature = "markdown_dir")]
+            BackendType::MarkdownDir => self
+                .markdown_dir_backend
+                .dir_path
+                .clone()
+                .map_or_else(get_default_markdown_dir_path, Ok),
+            BackendType::Http => bail!("HTTP back-end doesn't have a local file or directory"),
+            #[allow(unreachable_patterns)]
+            backend => bail!("Feature for back-end type {backend:?} is not installed"),
+        }
+    }
 }
 
 pub fn settings_default_dir_path() -> anyhow::Result<PathBuf> {
@@ -211,6 +340,30 @@ pub fn settings_default_dir_path() -> anyhow::Result<PathBuf> {
         .context("Config directory path couldn't be retrieved")
 }
 
+/// Gets the path of the configuration file in the given custom configuration directory or in the
+/// default one.
+fn config_file_path(custom_config_dir: Option<&PathBuf>) -> anyhow::Result<PathBuf> {
+    let Some(path) = custom_config_dir else {
+        let default_dir = settings_default_dir_path()?;
+        return Ok(settings_file_path(&default_dir));
+    };
+
+    ensure!(
+        path.exists(),
+        "Provided custom directory doesn't exit. Path: {}",
+        path.display()
+    );
+
+    // Accept path configuration file for backward compatibility.
+    if path.is_file() {
+        Ok(path.to_owned())
+    } else if path.is_dir() {
+        Ok(settings_file_path(path))
+    } else {
+        bail!("Provided configuration files is nighter file nor directory");
+    }
+}
+
 fn settings_file_path(config_dir: &Path) -> PathBuf {
     config_dir.join("config.toml")
 }
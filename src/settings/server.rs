@@ -0,0 +1,26 @@
+use std::net::SocketAddr;
+
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BIND_ADDRESS: &str = "127.0.0.1:7373";
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub struct ServerSettings {
+    /// Address the `serve` command listens on if not given in the command arguments.
+    #[serde(default)]
+    pub bind: Option<SocketAddr>,
+    /// Token the clients must provide to access the server. A token is generated on each start if
+    /// not set.
+    #[serde(default)]
+    pub token: Option<String>,
+    /// Host names the clients can address the server with, apart from IP addresses and
+    /// `localhost`.
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+}
+
+pub fn get_default_bind_address() -> SocketAddr {
+    DEFAULT_BIND_ADDRESS
+        .parse()
+        .expect("Default bind address must be valid")
+}
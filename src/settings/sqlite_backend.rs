@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use backend::SqliteDurability;
 use serde::{Deserialize, Serialize};
 
 use super::get_default_data_dir;
@@ -8,6 +9,9 @@ use super::get_default_data_dir;
 pub struct SqliteBackend {
     #[serde(default)]
     pub file_path: Option<PathBuf>,
+    #[serde(default)]
+    /// Durability level of the database: `off`, `normal` or `wal-full`.
+    pub durability: SqliteDurability,
 }
 
 pub fn get_default_sqlite_path() -> anyhow::Result<PathBuf> {